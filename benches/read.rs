@@ -0,0 +1,177 @@
+//! Read benchmarks over flat and nested documents.
+//!
+//! To compare a change against the current tree, save a baseline before making it:
+//! ```text
+//! cargo bench --bench read --features partial -- --save-baseline before
+//! cargo bench --bench read --features partial -- --baseline before
+//! ```
+//!
+//! The `unmarshall_file` group compares reading a file directly, where prefixes are read a byte per call
+//! so nothing past the element is consumed, against reading it through a `BufReader`.
+
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+#[cfg(feature="partial")]
+use std::io::{Seek, SeekFrom};
+use std::path::PathBuf;
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+use tycho::{Element, ElementMap, marshall_vec, Number, unmarshall, unmarshall_buffered, unmarshall_vec, Uuid, Value};
+
+/// A list of many small values, with long strings.
+fn flat() -> Element {
+    Element::List((0..5000u32).map(|x| match x % 4 {
+        0 => Element::Value(Value::String(format!("a flat string value, number {}", x))),
+        1 => Element::Value(Value::Number(Number::Unsigned32(x))),
+        2 => Element::Value(Value::Char('x')),
+        _ => Element::Value(Value::UUID(Uuid::v4())),
+    }).collect())
+}
+
+/// Structs within lists within structs, with many short field names.
+fn nested() -> Element {
+    fn node(depth: u32) -> Element {
+        let mut fields = ElementMap::new();
+        fields.insert("name".to_string(), Element::Value(Value::String(format!("node {}", depth))));
+        fields.insert("id".to_string(), Element::Value(Value::Number(Number::Unsigned64(depth as u64))));
+        fields.insert("tags".to_string(), Element::List(vec![
+            Element::Value(Value::String("red".to_string())),
+            Element::Value(Value::String("green".to_string())),
+        ]));
+        if depth > 0 {
+            fields.insert("children".to_string(), Element::List((0..4).map(|_| node(depth - 1)).collect()));
+        }
+        Element::Struct(fields)
+    }
+    node(5)
+}
+
+fn documents() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("flat", marshall_vec(flat()).unwrap()),
+        ("nested", marshall_vec(nested()).unwrap()),
+    ]
+}
+
+/// Write a document to a file within the temp directory, to read without an in-memory buffer.
+fn document_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tycho-bench-{}-{}", std::process::id(), name));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn bench_unmarshall(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unmarshall");
+    for (name, bytes) in documents() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("vec", name), &bytes, |b, bytes| {
+            b.iter(|| unmarshall_vec(black_box(bytes.clone())).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("reader", name), &bytes, |b, bytes| {
+            b.iter(|| unmarshall(&mut Cursor::new(black_box(bytes))).unwrap())
+        });
+
+        let path = document_file(name, &bytes);
+        group.bench_with_input(BenchmarkId::new("buffered_file", name), &path, |b, path| {
+            b.iter(|| unmarshall_buffered(&mut BufReader::new(File::open(path).unwrap())).unwrap())
+        });
+        std::fs::remove_file(path).unwrap();
+    }
+    group.finish();
+
+    // reading prefixes a byte per call on a file is a system call for each byte, so fewer samples are taken.
+    let mut group = c.benchmark_group("unmarshall_file");
+    group.sample_size(10);
+    for (name, bytes) in documents() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+        let path = document_file(name, &bytes);
+        group.bench_with_input(BenchmarkId::new("file", name), &path, |b, path| {
+            b.iter(|| unmarshall(&mut File::open(path).unwrap()).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("buffered_file", name), &path, |b, path| {
+            b.iter(|| unmarshall_buffered(&mut BufReader::new(File::open(path).unwrap())).unwrap())
+        });
+        std::fs::remove_file(path).unwrap();
+    }
+    group.finish();
+}
+
+#[cfg(feature="partial")]
+fn bench_partial(c: &mut Criterion) {
+    use tycho::partial::{PartialElement, PartialReader};
+
+    let mut group = c.benchmark_group("partial");
+    for (name, bytes) in documents() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("read_full", name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut reader = PartialReader::from(Cursor::new(black_box(bytes.as_slice())));
+                let root = reader.element().unwrap();
+                root.read_full(&mut reader).unwrap()
+            })
+        });
+    }
+
+    let bytes = marshall_vec(flat()).unwrap();
+    group.bench_function("nth/flat", |b| {
+        let mut reader = PartialReader::from(Cursor::new(bytes.as_slice()));
+        let list = match reader.element().unwrap() {
+            PartialElement::List(list) => list,
+            _ => unreachable!()
+        };
+        b.iter(|| list.nth(&mut reader, black_box(4000)).unwrap())
+    });
+    group.finish();
+
+    // every read of an unbuffered file is a system call, so fewer samples are taken.
+    let mut group = c.benchmark_group("partial_file");
+    group.sample_size(10);
+    for (name, bytes) in documents() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+        let path = document_file(name, &bytes);
+        let mut file = File::open(&path).unwrap();
+        group.bench_function(BenchmarkId::new("read_full", name), |b| {
+            b.iter(|| {
+                file.seek(SeekFrom::Start(0)).unwrap();
+                let mut reader = PartialReader::from(&mut file);
+                let root = reader.element().unwrap();
+                root.read_full(&mut reader).unwrap()
+            })
+        });
+        std::fs::remove_file(path).unwrap();
+    }
+    group.finish();
+}
+
+#[cfg(not(feature="partial"))]
+fn bench_partial(_: &mut Criterion) {}
+
+#[cfg(feature="serde")]
+fn bench_serde(c: &mut Criterion) {
+    use tycho::{from_bytes, from_reader};
+
+    // strings, chars and uuids as plain serde types.
+    let records: Vec<(String, u32, char, String)> = (0..5000)
+        .map(|x| (format!("a flat string value, number {}", x), x, 'x', Uuid::v4().string()))
+        .collect();
+    let bytes = tycho::to_bytes(&records).unwrap();
+
+    let mut group = c.benchmark_group("serde");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function("from_bytes/flat", |b| {
+        b.iter(|| from_bytes::<Vec<(String, u32, char, String)>>(black_box(&bytes)).unwrap())
+    });
+    group.bench_function("from_reader/flat", |b| {
+        b.iter(|| from_reader::<_, Vec<(String, u32, char, String)>>(&mut Cursor::new(black_box(&bytes))).unwrap())
+    });
+    group.finish();
+}
+
+#[cfg(not(feature="serde"))]
+fn bench_serde(_: &mut Criterion) {}
+
+criterion_group!(benches, bench_unmarshall, bench_partial, bench_serde);
+criterion_main!(benches);
@@ -0,0 +1,349 @@
+//! Codecs for framing elements over a byte stream, with `tokio_util::codec`. (requires `async_tokio`)
+//!
+//! ### Example
+//! ```
+//! use bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//! use tycho::{Element, Value};
+//! use tycho::codec::TychoCodec;
+//!
+//! let mut codec = TychoCodec::new();
+//! let mut buffer = BytesMut::new();
+//!
+//! codec.encode(Value::Boolean(true), &mut buffer).unwrap();
+//! codec.encode(Element::Unit, &mut buffer).unwrap();
+//!
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), Some(Element::Value(Value::Boolean(true))));
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), Some(Element::Unit));
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+//! ```
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::Element;
+use crate::error::{Limit, TychoError, TychoResult, TychoStatus};
+use crate::read::element::{read_element, read_element_ident};
+use crate::read::length::read_length;
+use crate::read::options::{Limiter, ReadOptions};
+use crate::read::position::PositionReader;
+use crate::read::slice::{read_slice, read_tslice};
+use crate::read::value::{read_value, read_value_ident};
+use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::write::element::write_element;
+use crate::write::length::write_length;
+
+#[cfg(feature="serde")]
+use std::marker::PhantomData;
+#[cfg(feature="serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature="serde")]
+use serde::Serialize;
+#[cfg(feature="serde")]
+use crate::serde::read::{SliceSource, TychoReadDeserializer};
+#[cfg(feature="serde")]
+use crate::serde::read::source::Source;
+
+/// The default maximum size of a frame, 8 MiB.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// The default maximum depth of nested elements within a frame.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How elements are separated within a stream.
+pub enum Framing {
+    /// Each element is prefixed with its size, as a length prefix.
+    LengthPrefixed,
+    /// Elements are written one after the other, and separated by reading their prefixes.
+    SelfDelimited,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A codec which frames each element within a stream.
+///
+/// Frames are `Framing::LengthPrefixed` by default, and limited to `DEFAULT_MAX_FRAME_SIZE`.
+/// Elements within a frame are limited to a depth of `DEFAULT_MAX_DEPTH`.
+pub struct TychoCodec {
+    framing: Framing,
+    max_frame_size: usize,
+    options: ReadOptions,
+}
+
+impl Default for TychoCodec {
+    fn default() -> Self {
+        TychoCodec {
+            framing: Framing::LengthPrefixed,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            options: ReadOptions { max_depth: Some(DEFAULT_MAX_DEPTH), ..ReadOptions::default() }
+        }
+    }
+}
+
+impl TychoCodec {
+    /// Create a new length prefixed codec.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how elements are separated within the stream.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Set the maximum size of a frame, in bytes, when encoding or decoding.
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Set the limits used while reading each element.
+    ///
+    /// This replaces the default depth limit, which should be kept for untrusted input.
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    fn check_frame_size(&self, size: usize) -> TychoResult<()> {
+        if size > self.max_frame_size {
+            Err(TychoError::LimitExceeded { limit: Limit::FrameSize, max: self.max_frame_size })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Split the next frame from the buffer, or `None` if it is not yet complete.
+    fn decode_frame(&mut self, src: &mut BytesMut) -> TychoResult<Option<BytesMut>> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let (prefix, size) = match self.framing {
+            Framing::LengthPrefixed => {
+                let mut bytes = &src[..];
+                match incomplete(read_length(&mut bytes))? {
+                    Some(size) => (src.len() - bytes.len(), size),
+                    None => return Ok(None)
+                }
+            }
+            Framing::SelfDelimited => {
+                let mut bytes = &src[..];
+                match incomplete(skip_element(&mut bytes, &mut Limiter::new(self.options), src.len(), self.max_frame_size))? {
+                    Some(()) => (0, src.len() - bytes.len()),
+                    None => {
+                        // the frame is already too large, even though it is incomplete.
+                        self.check_frame_size(src.len())?;
+                        return Ok(None);
+                    }
+                }
+            }
+        };
+
+        self.check_frame_size(size)?;
+
+        if src.len() < prefix + size {
+            src.reserve(prefix + size - src.len());
+            return Ok(None);
+        }
+
+        src.advance(prefix);
+        Ok(Some(src.split_to(size)))
+    }
+
+    /// Write a frame to the buffer.
+    fn encode_frame(&mut self, bytes: &[u8], dst: &mut BytesMut) -> TychoResult<()> {
+        self.check_frame_size(bytes.len())?;
+
+        if self.framing == Framing::LengthPrefixed {
+            let mut prefix = Vec::new();
+            write_length(&mut prefix, bytes.len())?;
+            dst.extend_from_slice(&prefix);
+        }
+
+        dst.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl Decoder for TychoCodec {
+    type Item = Element;
+    type Error = TychoError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = match self.decode_frame(src)? {
+            Some(frame) => frame,
+            None => return Ok(None)
+        };
+
+        let mut reader = PositionReader::new(&frame[..]);
+        let element = read_element(&mut reader, &mut Limiter::new(self.options))?;
+
+        if reader.position() != frame.len() as u64 {
+            return Err(TychoError::TrailingData.located(reader.position()));
+        }
+        Ok(Some(element))
+    }
+}
+
+impl<E: Into<Element>> Encoder<E> for TychoCodec {
+    type Error = TychoError;
+
+    fn encode(&mut self, item: E, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut buffer = Vec::new();
+        write_element(&mut buffer, &item.into())?;
+        self.encode_frame(&buffer, dst)
+    }
+}
+
+#[cfg(feature="serde")]
+/// A codec which frames serde serializable objects within a stream. (requires `serde`)
+///
+/// Framing is the same as `TychoCodec`, which can be configured with `SerdeCodec::from`.
+///
+/// ### Example
+/// ```
+/// use bytes::BytesMut;
+/// use tokio_util::codec::{Decoder, Encoder};
+/// use tycho::codec::SerdeCodec;
+///
+/// let mut codec = SerdeCodec::<Vec<String>>::new();
+/// let mut buffer = BytesMut::new();
+///
+/// codec.encode(vec!["Hello".to_string()], &mut buffer).unwrap();
+///
+/// assert_eq!(codec.decode(&mut buffer).unwrap(), Some(vec!["Hello".to_string()]));
+/// ```
+pub struct SerdeCodec<T> {
+    codec: TychoCodec,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature="serde")]
+impl<T> SerdeCodec<T> {
+    /// Create a new length prefixed codec.
+    pub fn new() -> Self {
+        Self::from(TychoCodec::new())
+    }
+}
+
+#[cfg(feature="serde")]
+impl<T> Default for SerdeCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature="serde")]
+impl<T> From<TychoCodec> for SerdeCodec<T> {
+    fn from(codec: TychoCodec) -> Self {
+        SerdeCodec { codec, _phantom: PhantomData }
+    }
+}
+
+#[cfg(feature="serde")]
+impl<T: DeserializeOwned> Decoder for SerdeCodec<T> {
+    type Item = T;
+    type Error = TychoError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = match self.codec.decode_frame(src)? {
+            Some(frame) => frame,
+            None => return Ok(None)
+        };
+
+        let mut source = SliceSource::with_options(&frame, self.codec.options);
+        let item = T::deserialize(TychoReadDeserializer::new(&mut source))?;
+
+        if source.position() != frame.len() as u64 {
+            return Err(TychoError::TrailingData.located(source.position()));
+        }
+        Ok(Some(item))
+    }
+}
+
+#[cfg(feature="serde")]
+impl<T: Serialize> Encoder<T> for SerdeCodec<T> {
+    type Error = TychoError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let bytes = crate::to_bytes(item)?;
+        self.codec.encode_frame(&bytes, dst)
+    }
+}
+
+/// Treat running out of bytes as an incomplete frame.
+fn incomplete<T>(result: TychoResult<T>) -> TychoResult<Option<T>> {
+    match result {
+        Ok(x) => Ok(Some(x)),
+        Err(TychoError::UnexpectedEof) => Ok(None),
+        Err(e) => Err(e)
+    }
+}
+
+/// Move past an element, without reading the contents of its containers.
+///
+/// Container and string sizes are checked against the limits before their contents arrive.
+/// Options and variants are walked in a loop, so deeply nested frames can not overflow the stack.
+fn skip_element(bytes: &mut &[u8], limiter: &mut Limiter, total: usize, max: usize) -> TychoStatus {
+    loop {
+        limiter.enter()?;
+
+        match read_element_ident(bytes)? {
+            ElementIdent::Unit | ElementIdent::None => (),
+            ElementIdent::Value => {
+                let ident = read_value_ident(bytes)?;
+                skip_value(bytes, &ident, limiter, total, max)?;
+            }
+            ElementIdent::Some => continue,
+            ElementIdent::Variant => {
+                skip_tstring(bytes, limiter)?;
+                continue;
+            }
+            ElementIdent::Struct | ElementIdent::List | ElementIdent::Compression => {
+                skip_sized(bytes, total, max, |size| limiter.container(size))?;
+            }
+            ElementIdent::Array | ElementIdent::Map => {
+                if read_value_ident(bytes)? != ValueIdent::Null {
+                    skip_sized(bytes, total, max, |size| limiter.container(size))?;
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn skip_value(bytes: &mut &[u8], ident: &ValueIdent, limiter: &mut Limiter, total: usize, max: usize) -> TychoStatus {
+    match ident {
+        ValueIdent::String | ValueIdent::Bytes => skip_sized(bytes, total, max, |size| limiter.string(size)),
+        ValueIdent::UUID => read_slice(bytes, 16).map(|_| ()),
+        _ => read_value(bytes, ident, limiter).map(|_| ())
+    }
+}
+
+/// Move past a terminated string.
+///
+/// While the terminator has not arrived, the bytes so far are checked against the string limit.
+/// The frame size is checked by the caller, against the whole buffer.
+fn skip_tstring(bytes: &mut &[u8], limiter: &mut Limiter) -> TychoStatus {
+    match read_tslice(bytes) {
+        Ok(string) => limiter.string(string.len()),
+        Err(e) => {
+            limiter.string(bytes.len())?;
+            Err(e)
+        }
+    }
+}
+
+/// Move past a length prefixed slice, where `total` is the size of the buffer being framed.
+///
+/// The length is checked with `limit` before the slice arrives.
+fn skip_sized(bytes: &mut &[u8], total: usize, max: usize, limit: impl FnOnce(usize) -> TychoStatus) -> TychoStatus {
+    let length = read_length(bytes)?;
+    limit(length)?;
+    if (total - bytes.len()).saturating_add(length) > max {
+        return Err(TychoError::LimitExceeded { limit: Limit::FrameSize, max });
+    }
+    read_slice(bytes, length).map(|_| ())
+}
@@ -2,7 +2,10 @@ use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
 
 use crate::{Element, Value};
+use crate::error::TychoError;
+use crate::into::ident::Ident;
 use crate::into::value::ValueType;
+use crate::types::ident::{ElementIdent, ValueIdent};
 
 /// Maps to `Vec<Value>` where items are homogeneous
 #[derive(Debug)]
@@ -39,21 +42,22 @@ impl<T: ValueType> From<Vec<T>> for Array<T> {
     }
 }
 
-impl<K: ValueType + TryFrom<Value>> TryFrom<Element> for Array<K> {
-    type Error = ();
+impl<K: ValueType + TryFrom<Value, Error = TychoError>> TryFrom<Element> for Array<K> {
+    type Error = TychoError;
 
     fn try_from(value: Element) -> Result<Self, Self::Error> {
         if let Element::Array(ident, set) = value {
-            if K::IDENT == ident {
+            // empty arrays are written without a type.
+            if K::IDENT == ident || (ident == ValueIdent::Null && set.is_empty()) {
                 Ok(Array(set.into_iter()
-                    .filter_map(|x| K::try_from(x).ok() )
-                    .collect()
+                    .map(K::try_from)
+                    .collect::<Result<_, _>>()?
                 ))
             } else {
-                Err(())
+                Err(TychoError::ValueMismatch { expected: K::IDENT, found: ident })
             }
         } else {
-            Err(())
+            Err(TychoError::ElementMismatch { expected: ElementIdent::Array, found: value.ident() })
         }
     }
 }
@@ -2,6 +2,9 @@ use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
 
 use crate::Element;
+use crate::error::TychoError;
+use crate::into::ident::Ident;
+use crate::types::ident::ElementIdent;
 
 /// Maps to `Vec<Element>` where items are heterogeneous
 #[derive(Debug)]
@@ -40,13 +43,13 @@ impl From<Vec<Element>> for List {
 
 
 impl TryFrom<Element> for List {
-    type Error = ();
+    type Error = TychoError;
 
     fn try_from(value: Element) -> Result<Self, Self::Error> {
         if let Element::List(set) = value {
             Ok(List(set))
         } else {
-            Err(())
+            Err(TychoError::ElementMismatch { expected: ElementIdent::List, found: value.ident() })
         }
     }
 }
\ No newline at end of file
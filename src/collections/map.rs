@@ -1,14 +1,16 @@
-use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::hash::Hash;
 use std::ops::{Deref, DerefMut};
 
-use crate::{Element, Value};
+use crate::{Element, ElementMap, Value};
+use crate::error::TychoError;
+use crate::into::ident::Ident;
 use crate::into::value::ValueType;
+use crate::types::ident::{ElementIdent, ValueIdent};
 
-/// Maps to `HashMap<Value, Element>` where value is homogeneous
+/// Maps to `ElementMap<Value, Element>` where value is homogeneous
 #[derive(Debug)]
-pub struct Map<K: ValueType + Hash + Eq>(pub HashMap<K, Element>);
+pub struct Map<K: ValueType + Hash + Eq>(pub ElementMap<K, Element>);
 
 impl<K: ValueType + Hash + Eq> From<Map<K>> for Element {
     fn from(m: Map<K>) -> Self {
@@ -17,7 +19,7 @@ impl<K: ValueType + Hash + Eq> From<Map<K>> for Element {
 }
 
 impl<K: ValueType + Hash + Eq> Deref for Map<K> {
-    type Target = HashMap<K, Element>;
+    type Target = ElementMap<K, Element>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -31,29 +33,29 @@ impl<K: ValueType + Hash + Eq> DerefMut for Map<K> {
 
 impl<T: ValueType + Hash + Eq> Map<T> {
     /// Create a new empty map.
-    pub fn new() -> Self { Self(HashMap::new()) }
+    pub fn new() -> Self { Self(ElementMap::new()) }
 }
-impl<T: ValueType + Hash + Eq> From<HashMap<T, Element>> for Map<T> {
-    fn from(v: HashMap<T, Element>) -> Self {
+impl<T: ValueType + Hash + Eq> From<ElementMap<T, Element>> for Map<T> {
+    fn from(v: ElementMap<T, Element>) -> Self {
         Self(v)
     }
 }
 
-impl<K: ValueType + Hash + Eq + TryFrom<Value>> TryFrom<Element> for Map<K> {
-    type Error = ();
+impl<K: ValueType + Hash + Eq + TryFrom<Value, Error = TychoError>> TryFrom<Element> for Map<K> {
+    type Error = TychoError;
 
     fn try_from(value: Element) -> Result<Self, Self::Error> {
         if let Element::Map(ident, map) = value {
-            if K::IDENT == ident {
+            // empty maps are written without a key type.
+            if K::IDENT == ident || (ident == ValueIdent::Null && map.is_empty()) {
                 Ok(Map(map.into_iter()
-                    .filter_map(|(k, v)|
-                        Some((K::try_from(k).ok()?, v)))
-                    .collect()))
+                    .map(|(k, v)| Ok((K::try_from(k)?, v)))
+                    .collect::<Result<_, TychoError>>()?))
             } else {
-                Err(())
+                Err(TychoError::ValueMismatch { expected: K::IDENT, found: ident })
             }
         } else {
-            Err(())
+            Err(TychoError::ElementMismatch { expected: ElementIdent::Map, found: value.ident() })
         }
     }
 }
@@ -1,4 +1,4 @@
-//! Wrappers around `HashMap` and `Vec` mapping to a respective type within tycho.
+//! Wrappers around `ElementMap` and `Vec` mapping to a respective type within tycho.
 //!
 //!
 //! These functions come in handy when creating objects/elements manually or want a specific
@@ -1,13 +1,15 @@
-use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
 
-use crate::Element;
+use crate::{Element, ElementMap};
+use crate::error::TychoError;
+use crate::into::ident::Ident;
+use crate::types::ident::ElementIdent;
 
 
-/// Maps to `HashMap<String, Element>`
+/// Maps to `ElementMap<String, Element>`
 #[derive(Debug)]
-pub struct Struct(pub HashMap<String, Element>);
+pub struct Struct(pub ElementMap<String, Element>);
 
 impl From<Struct> for Element {
     fn from(s: Struct) -> Self {
@@ -16,7 +18,7 @@ impl From<Struct> for Element {
 }
 
 impl Deref for Struct {
-    type Target = HashMap<String, Element>;
+    type Target = ElementMap<String, Element>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -31,7 +33,7 @@ impl DerefMut for Struct {
 
 impl Struct {
     /// Create a new empty struct.
-    pub fn new() -> Self { Self(HashMap::new()) }
+    pub fn new() -> Self { Self(ElementMap::new()) }
 
     /// Insert an item into the struct.
     ///
@@ -79,20 +81,20 @@ impl Struct {
         }
     }
 }
-impl From<HashMap<String, Element>> for Struct {
-    fn from(v: HashMap<String, Element>) -> Self {
+impl From<ElementMap<String, Element>> for Struct {
+    fn from(v: ElementMap<String, Element>) -> Self {
         Self(v)
     }
 }
 
 impl TryFrom<Element> for Struct {
-    type Error = ();
+    type Error = TychoError;
 
     fn try_from(value: Element) -> Result<Self, Self::Error> {
         if let Element::Struct(map) = value {
             Ok(Struct(map))
         } else {
-            Err(())
+            Err(TychoError::ElementMismatch { expected: ElementIdent::Struct, found: value.ident() })
         }
     }
 }
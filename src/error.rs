@@ -1,10 +1,12 @@
 //! Error types returned from tycho marshall/unmarshall/serialise/deserialize processes.
 use std::fmt;
 
-#[cfg(feature="serde")]
 use crate::ident::ElementIdent;
-#[cfg(feature="serde")]
 use crate::types::ident::ValueIdent;
+use crate::Value;
+
+#[cfg(feature="compression")]
+use crate::ident::Codec;
 
 #[derive(Debug)]
 /// Error regarding a tycho process
@@ -19,15 +21,101 @@ pub enum TychoError {
         expecting: String
     },
     /// An error occurred while parsing a UTF-8 String from bytes.
-    StringError(std::string::FromUtf8Error),
+    Utf8(std::string::FromUtf8Error),
+
+    /// The input ended part way through an element.
+    UnexpectedEof,
+
+    /// A length prefix was longer than 64 bits.
+    LengthOverflow,
+
+    /// An element extended past the end of the container holding it.
+    ContainerOverflow,
+
+    /// Bytes remained in the input after the element was read.
+    TrailingData,
+
+    /// An element was not of the element type expected, such as a list where a struct was expected.
+    ElementMismatch {
+        /// The type of element expected.
+        expected: ElementIdent,
+        /// The type of element found.
+        found: ElementIdent
+    },
+
+    /// A value was not of the value type expected, such as within an array or as a map key.
+    ValueMismatch {
+        /// The type of value expected.
+        expected: ValueIdent,
+        /// The type of value found.
+        found: ValueIdent
+    },
+
+    #[cfg(feature="compression")]
+    /// Compressed data could not be compressed or decompressed.
+    Compression(std::io::Error),
+
+    #[cfg(feature="compression")]
+    /// A compression codec was used without the feature which enables it.
+    UnsupportedCodec(Codec),
+
+    /// A query could not be parsed.
+    InvalidQuery {
+        /// The byte offset within the query where parsing failed.
+        position: usize,
+        /// The reason the query is invalid.
+        reason: String
+    },
 
     /// An unspecified error.
     Other(String),
 
+    /// An error encountered while reading, with the location it was encountered at.
+    ///
+    /// Use [`inner`](TychoError::inner) to get the error without its location.
+    Located {
+        /// The error encountered.
+        error: Box<TychoError>,
+        /// The byte offset, from the start of the input, of the element which failed to read.
+        offset: u64,
+        /// The path to the element which failed to read.
+        path: ElementPath,
+    },
+
+    /// A limit set within [`ReadOptions`](crate::ReadOptions) was exceeded while reading.
+    LimitExceeded {
+        /// The limit which was exceeded.
+        limit: Limit,
+        /// The maximum allowed by the limit.
+        max: usize,
+    },
+
     #[cfg(feature="partial_state")]
     /// A pointer was referenced, but is no-longer valid as the data may have changed.
     OutdatedPointer,
 
+    #[cfg(feature="partial")]
+    /// A pointer did not refer to an element or container within the reader.
+    PointerNotFound,
+
+    /// An index was past the end of a container.
+    OutOfBounds {
+        /// The index given.
+        index: usize,
+        /// The amount of items within the container.
+        length: usize
+    },
+
+    /// A struct field or map key was found more than once, where keys must be unique.
+    DuplicateKey,
+
+    /// An element has no canonical form, such as a compression element.
+    NonCanonical,
+
+    #[cfg(feature="hash")]
+    /// A path did not match the structure of the element it was followed within.
+    PathMismatch,
+
     #[cfg(feature="serde")]
     /// A key was mismatched when handling serde.
     InvalidKeyType {
@@ -35,19 +123,127 @@ pub enum TychoError {
         found: ElementIdent
     },
     #[cfg(feature="serde")]
-    /// A type was mismatched when handling serde.
-    MismatchedType {
-        /// The type of element found.
-        found: ValueIdent,
-        /// The type of element expected.
-        expected: ValueIdent
-    },
+    /// An error raised by a type while serializing or deserializing it.
+    Serde(String),
 
 }
 
+impl TychoError {
+    /// Get the underlying error, without the location it was encountered at.
+    pub fn inner(&self) -> &TychoError {
+        match self {
+            TychoError::Located { error, .. } => error,
+            x => x
+        }
+    }
+
+    /// Get the byte offset of the element which failed to read, if known.
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            TychoError::Located { offset, .. } => Some(*offset),
+            _ => None
+        }
+    }
+
+    /// Get the path to the element which failed to read, if known.
+    pub fn path(&self) -> Option<&ElementPath> {
+        match self {
+            TychoError::Located { path, .. } => Some(path),
+            _ => None
+        }
+    }
+
+    /// Locate an error at a byte offset, if it has not been located already.
+    pub(crate) fn located(self, offset: u64) -> Self {
+        match self {
+            TychoError::Located { .. } => self,
+            error => TychoError::Located { error: Box::new(error), offset, path: ElementPath::default() }
+        }
+    }
+
+    /// Prefix the path of a located error with the segment of its parent.
+    pub(crate) fn within(mut self, segment: PathSegment) -> Self {
+        if let TychoError::Located { path, .. } = &mut self {
+            path.0.insert(0, segment);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A path to an element from the root element, such as `.users[42].address.city`.
+pub struct ElementPath(pub Vec<PathSegment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single step within an element path.
+pub enum PathSegment {
+    /// The field of a struct, displayed as `.field`.
+    Field(String),
+    /// The index of a list or array item, displayed as `[index]`.
+    Index(usize),
+    /// The key of a map entry, displayed as `[key]`.
+    Key(Value),
+    /// The contents of a variant, displayed as `::Name`.
+    Variant(String),
+}
+
+impl fmt::Display for ElementPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str(".");
+        }
+        for segment in &self.0 {
+            fmt::Display::fmt(segment, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(x) => write!(f, ".{}", x),
+            PathSegment::Index(x) => write!(f, "[{}]", x),
+            PathSegment::Key(Value::String(x)) => write!(f, "[{:?}]", x),
+            PathSegment::Key(x) => write!(f, "[{}]", x),
+            PathSegment::Variant(x) => write!(f, "::{}", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A limit on the resources used while reading.
+pub enum Limit {
+    /// The depth of nested elements.
+    Depth,
+    /// The size of a single container.
+    ContainerBytes,
+    /// The length of a single string or bytes value.
+    StringLength,
+    /// The total size of all data read.
+    TotalBytes,
+    /// The size of a single frame within a codec.
+    FrameSize,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Depth => f.write_str("depth"),
+            Limit::ContainerBytes => f.write_str("container size"),
+            Limit::StringLength => f.write_str("string length"),
+            Limit::TotalBytes => f.write_str("total size"),
+            Limit::FrameSize => f.write_str("frame size"),
+        }
+    }
+}
+
 impl From<std::io::Error> for TychoError {
     fn from(e: std::io::Error) -> Self {
-        Self::Io(e)
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => Self::UnexpectedEof,
+            _ => Self::Io(e)
+        }
     }
 }
 
@@ -57,18 +253,12 @@ pub(crate) trait FromResult<T> {
 
 impl<T> FromResult<T> for TychoResult<T> {
     fn from(r: Result<T,std::io::Error>) -> Self {
-        match r {
-            Ok(x) => Ok(x),
-            Err(e) => Err(TychoError::Io(e))
-        }
+        r.map_err(TychoError::from)
     }
 }
 
 pub(crate) fn parse_io<T>(r: Result<T,std::io::Error>) -> TychoResult<T> {
-    match r {
-        Ok(x) => Ok(x),
-        Err(e) => Err(TychoError::Io(e))
-    }
+    r.map_err(TychoError::from)
 }
 
 
@@ -77,31 +267,80 @@ impl fmt::Display for TychoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TychoError::Io(x) => x.fmt(f),
-            TychoError::StringError(x) => x.fmt(f),
+            TychoError::Utf8(x) => x.fmt(f),
+            TychoError::UnexpectedEof =>
+                f.write_str("Unexpected end of input while reading element"),
+            TychoError::LengthOverflow =>
+                f.write_str("Length prefix overflowed 64 bits"),
+            TychoError::ContainerOverflow =>
+                f.write_str("Element exceeded the size of its container"),
+            TychoError::TrailingData =>
+                f.write_str("Found trailing data after element"),
+            TychoError::ElementMismatch { expected, found } =>
+                f.write_str(&format!("Expected element of type {:?}, found {:?}", expected, found)),
+            TychoError::ValueMismatch { expected, found } =>
+                f.write_str(&format!("Expected value of type {:?}, found {:?}", expected, found)),
+
+            #[cfg(feature="compression")]
+            TychoError::Compression(x) =>
+                f.write_str(&format!("Failed to process compressed data: {}", x)),
+            #[cfg(feature="compression")]
+            TychoError::UnsupportedCodec(x) =>
+                f.write_str(&format!("The {:?} compression codec is not enabled", x)),
             TychoError::InvalidIdent { found, expecting }
                 => f.write_str(&format!("Found invalid ident byte '{}' when reading {}",
                                         found, expecting)),
+            TychoError::InvalidQuery { position, reason } =>
+                f.write_str(&format!("Invalid query at position {}: {}", position, reason)),
             TychoError::Other(x) =>
                 f.write_str(x),
+            TychoError::Located { error, offset, path } =>
+                f.write_str(&format!("{} (at byte {}, {})", error, offset, path)),
+            TychoError::LimitExceeded { limit, max } =>
+                f.write_str(&format!("Exceeded the maximum {} of {} while reading", limit, max)),
 
             #[cfg(feature="partial_state")]
             TychoError::OutdatedPointer =>
                 f.write_str("Failed to reference partial pointer, outdated in respect \
                 to reader."),
 
+            #[cfg(feature="partial")]
+            TychoError::PointerNotFound =>
+                f.write_str("Failed to find an element or container at the partial pointer."),
+            TychoError::OutOfBounds { index, length } =>
+                f.write_str(&format!("Index {} is out of bounds for a container of {} items", index, length)),
+            TychoError::DuplicateKey =>
+                f.write_str("Found a duplicate key where keys must be unique"),
+            TychoError::NonCanonical =>
+                f.write_str("Element has no canonical form"),
+
+            #[cfg(feature="hash")]
+            TychoError::PathMismatch =>
+                f.write_str("Path does not match the structure of the element"),
+
             #[cfg(feature="serde")]
             TychoError::InvalidKeyType { found } =>
                 f.write_str(&format!("Invalid key type while serializing structure: found type\
                  {:?}", found)),
 
             #[cfg(feature="serde")]
-            TychoError::MismatchedType { found, expected } =>
-                f.write_str(&format!("Mismatched type while serializing structure of type\
-                 {:?}: found type {:?}", expected, found)),
+            TychoError::Serde(x) =>
+                f.write_str(x),
+        }
+    }
+}
+impl std::error::Error for TychoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TychoError::Io(x) => Some(x),
+            TychoError::Utf8(x) => Some(x),
+            #[cfg(feature="compression")]
+            TychoError::Compression(x) => Some(x),
+            TychoError::Located { error, .. } => Some(error.as_ref()),
+            _ => None
         }
     }
 }
-impl std::error::Error for TychoError {}
 
 /// A result which errors with `TychoError` (`Result<T, TychoError>`)
 pub type TychoResult<T> = Result<T, TychoError>;
@@ -114,7 +353,7 @@ pub type TychoStatus = TychoResult<()>;
 impl serde::ser::Error for TychoError {
     fn custom<T>(msg: T) -> Self where
         T: std::fmt::Display {
-        Self::Other(msg.to_string())
+        Self::Serde(msg.to_string())
     }
 }
 
@@ -122,7 +361,7 @@ impl serde::ser::Error for TychoError {
 impl serde::de::Error for TychoError {
     fn custom<T>(msg: T) -> Self where
         T: std::fmt::Display {
-        Self::Other(msg.to_string())
+        Self::Serde(msg.to_string())
     }
 }
 
@@ -0,0 +1,225 @@
+use digest::{Digest, Output};
+
+use crate::{Element, Value};
+use crate::error::{TychoError, TychoResult};
+use crate::into::ident::Ident;
+use crate::types::ident::ValueIdent;
+use crate::write::canonical::{write_element_canonical, write_value_canonical};
+use crate::write::string::write_tstring;
+use crate::write::value::write_value_ident;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A key to a child within a merkle tree.
+pub enum MerkleKey<'a> {
+    /// The field of a struct.
+    Field(&'a str),
+    /// The index of a list item.
+    Index(usize),
+    /// The key of a map entry.
+    Key(&'a Value),
+}
+
+#[derive(Debug, Clone)]
+enum MerkleNode<H: Digest> {
+    Leaf,
+    Struct(Vec<(Vec<u8>, MerkleTree<H>)>),
+    List(Vec<MerkleTree<H>>),
+    Map(ValueIdent, Vec<(Vec<u8>, MerkleTree<H>)>),
+}
+
+#[derive(Debug, Clone)]
+/// The merkle digests of an element and all of its children.
+///
+/// ### Example
+/// ```
+/// use tycho::collections::Struct;
+/// use tycho::{Element, Value};
+/// # #[cfg(feature="hash_sha2")] {
+/// use tycho::hash::{MerkleKey, MerkleTree, Sha256};
+///
+/// let mut data = Struct::new();
+/// data.insert("foo", 10u8);
+/// data.insert("bar", true);
+/// let mut data: Element = data.into();
+///
+/// let mut tree = MerkleTree::<Sha256>::new(&data).unwrap();
+///
+/// // change a single field, only rehashing its path.
+/// let value = Element::Value(Value::Boolean(false));
+/// tree.update(&[MerkleKey::Field("bar")], &value).unwrap();
+///
+/// if let Element::Struct(fields) = &mut data {
+///     fields.insert("bar".to_string(), value);
+/// }
+///
+/// assert_eq!(tree.digest(), &data.merkle_digest::<Sha256>().unwrap());
+/// # }
+/// ```
+pub struct MerkleTree<H: Digest> {
+    digest: Output<H>,
+    node: MerkleNode<H>,
+}
+
+impl<H: Digest> MerkleTree<H> {
+    /// Hash an element and all of its children.
+    pub fn new(element: &Element) -> TychoResult<Self> {
+        let node = match element {
+            Element::Struct(data) => {
+                let mut items = Vec::with_capacity(data.len());
+                for (key, value) in data {
+                    items.push((field_bytes(key)?, Self::new(value)?));
+                }
+                items.sort_by(|a, b| a.0.cmp(&b.0));
+                MerkleNode::Struct(items)
+            }
+            Element::List(data) => {
+                let mut items = Vec::with_capacity(data.len());
+                for item in data {
+                    items.push(Self::new(item)?);
+                }
+                MerkleNode::List(items)
+            }
+            Element::Map(key_type, data) => {
+                let mut items = Vec::with_capacity(data.len());
+                for (key, value) in data {
+                    items.push((key_bytes(key)?, Self::new(value)?));
+                }
+                items.sort_by(|a, b| a.0.cmp(&b.0));
+
+                let key_type = if data.is_empty() { ValueIdent::Null } else { key_type.clone() };
+                MerkleNode::Map(key_type, items)
+            }
+            _ => {
+                let mut hasher = H::new();
+                hasher.update([0x00]);
+                let mut buffer = Vec::new();
+                write_element_canonical(&mut buffer, element)?;
+                hasher.update(&buffer);
+                return Ok(MerkleTree { digest: hasher.finalize(), node: MerkleNode::Leaf });
+            }
+        };
+
+        let mut tree = MerkleTree { digest: Output::<H>::default(), node };
+        tree.rehash()?;
+        Ok(tree)
+    }
+
+    /// The merkle digest of the element.
+    pub fn digest(&self) -> &Output<H> {
+        &self.digest
+    }
+
+    /// Get the tree of a direct child.
+    pub fn get(&self, key: &MerkleKey) -> Option<&MerkleTree<H>> {
+        match (&self.node, key) {
+            (MerkleNode::Struct(items), MerkleKey::Field(field)) => {
+                let bytes = field_bytes(field).ok()?;
+                items.iter().find(|(k, _)| k == &bytes).map(|(_, v)| v)
+            }
+            (MerkleNode::List(items), MerkleKey::Index(index)) => items.get(*index),
+            (MerkleNode::Map(_, items), MerkleKey::Key(key)) => {
+                let bytes = key_bytes(key).ok()?;
+                items.iter().find(|(k, _)| k == &bytes).map(|(_, v)| v)
+            }
+            _ => None
+        }
+    }
+
+    /// Replace the element at a path, rehashing only the nodes along the path.
+    ///
+    /// Struct fields and map entries that do not exist are inserted.
+    pub fn update(&mut self, path: &[MerkleKey], element: &Element) -> TychoResult<()> {
+        let (key, rest) = match path.split_first() {
+            Some(x) => x,
+            None => {
+                *self = Self::new(element)?;
+                return Ok(());
+            }
+        };
+
+        match (&mut self.node, key) {
+            (MerkleNode::Struct(items), MerkleKey::Field(field)) =>
+                update_sorted(items, field_bytes(field)?, rest, element)?,
+
+            (MerkleNode::List(items), MerkleKey::Index(index)) => {
+                let length = items.len();
+                match items.get_mut(*index) {
+                    Some(item) => item.update(rest, element)?,
+                    None => return Err(TychoError::OutOfBounds { index: *index, length })
+                }
+            }
+
+            (MerkleNode::Map(key_type, items), MerkleKey::Key(key)) => {
+                if key_type == &ValueIdent::Null {
+                    *key_type = key.ident();
+                } else if key_type != &key.ident() {
+                    return Err(TychoError::ValueMismatch { expected: key_type.clone(), found: key.ident() });
+                }
+                update_sorted(items, key_bytes(key)?, rest, element)?
+            }
+
+            _ => return Err(TychoError::PathMismatch)
+        }
+
+        self.rehash()
+    }
+
+    /// Recompute the digest of this node from the digests of its children.
+    fn rehash(&mut self) -> TychoResult<()> {
+        let mut hasher = H::new();
+
+        match &self.node {
+            MerkleNode::Leaf => return Ok(()),
+            MerkleNode::Struct(items) => {
+                hasher.update([0x05]);
+                for (key, value) in items {
+                    hasher.update(key);
+                    hasher.update(&value.digest);
+                }
+            }
+            MerkleNode::List(items) => {
+                hasher.update([0x06]);
+                for item in items {
+                    hasher.update(&item.digest);
+                }
+            }
+            MerkleNode::Map(key_type, items) => {
+                let mut ident = vec![0x08];
+                write_value_ident(&mut ident, key_type)?;
+                hasher.update(&ident);
+                for (key, value) in items {
+                    hasher.update(key);
+                    hasher.update(&value.digest);
+                }
+            }
+        }
+
+        self.digest = hasher.finalize();
+        Ok(())
+    }
+}
+
+fn update_sorted<H: Digest>(items: &mut Vec<(Vec<u8>, MerkleTree<H>)>, key: Vec<u8>, path: &[MerkleKey], element: &Element) -> TychoResult<()> {
+    match items.binary_search_by(|(k, _)| k.cmp(&key)) {
+        Ok(index) => items[index].1.update(path, element),
+        Err(index) => {
+            if !path.is_empty() {
+                return Err(TychoError::PathMismatch);
+            }
+            items.insert(index, (key, MerkleTree::new(element)?));
+            Ok(())
+        }
+    }
+}
+
+fn field_bytes(field: &str) -> TychoResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    write_tstring(&mut buffer, field)?;
+    Ok(buffer)
+}
+
+fn key_bytes(key: &Value) -> TychoResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    write_value_canonical(&mut buffer, key)?;
+    Ok(buffer)
+}
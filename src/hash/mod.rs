@@ -0,0 +1,96 @@
+//! Content hashing and merkle digests of elements. (requires `hash` feature)
+//!
+//! ## Applications
+//! Digests identify an element by its content, for caching, deduplication or signing.
+//!
+//! An element's digest is the hash of its [canonical](crate::marshall_canonical) encoding,
+//! so two equal elements always have the same digest, regardless of their field order.
+//!
+//! A merkle digest hashes each child of a struct, list or map independently,
+//! and combines their digests.
+//! A [`MerkleTree`](MerkleTree) keeps the digest of every child,
+//! so when a single field is changed, only the digests along its path are recomputed.
+//!
+//! Any hasher implementing [`Digest`](Digest) can be used. The following are re-exported:
+//! - `Sha256` (requires `hash_sha2` feature)
+//! - `Blake3` (requires `hash_blake3` feature)
+//!
+//! ## Quick Example
+//! ```
+//! use tycho::collections::Struct;
+//! use tycho::Element;
+//! # #[cfg(feature="hash_sha2")] {
+//! use tycho::hash::Sha256;
+//!
+//! let mut a = Struct::new();
+//! a.insert("foo", 10u8);
+//! a.insert("bar", true);
+//!
+//! let mut b = Struct::new();
+//! b.insert("bar", true);
+//! b.insert("foo", 10u8);
+//!
+//! let a: Element = a.into();
+//! let b: Element = b.into();
+//!
+//! assert_eq!(a.digest::<Sha256>().unwrap(), b.digest::<Sha256>().unwrap());
+//! # }
+//! ```
+//!
+//! ### Merkle Digests
+//! The merkle digest of an element is defined as:
+//! - Struct: `H(0x05, key_0, digest_0, key_1, digest_1, ...)`,
+//!   with fields sorted by their encoded keys.
+//! - List: `H(0x06, digest_0, digest_1, ...)`.
+//! - Map: `H(0x08, key_ident, key_0, digest_0, key_1, digest_1, ...)`,
+//!   with entries sorted by their encoded keys.
+//! - Any other element: `H(0x00, canonical_bytes)`.
+//!
+//! Options and variants are hashed as a whole, and are not split into children.
+
+pub use digest::{Digest, Output};
+#[cfg(feature="hash_sha2")]
+pub use sha2::Sha256;
+#[cfg(feature="hash_blake3")]
+pub use blake3::Hasher as Blake3;
+
+pub use merkle::{MerkleKey, MerkleTree};
+
+use crate::Element;
+use crate::error::TychoResult;
+use crate::write::canonical::write_element_canonical;
+
+pub(crate) mod merkle;
+
+impl Element {
+    /// Compute the digest of the element's canonical encoding.
+    ///
+    /// (requires `hash` feature)
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element, Value};
+    /// # #[cfg(feature="hash_sha2")] {
+    /// use tycho::hash::{Digest, Sha256};
+    ///
+    /// let element = Element::Value(Value::Boolean(true));
+    ///
+    /// // the digest of the canonical bytes `[1, 1, 1]`
+    /// assert_eq!(element.digest::<Sha256>().unwrap(), Sha256::digest(&[1, 1, 1]));
+    /// # }
+    /// ```
+    pub fn digest<H: Digest>(&self) -> TychoResult<Output<H>> {
+        let mut buffer = Vec::new();
+        write_element_canonical(&mut buffer, self)?;
+        Ok(H::digest(&buffer))
+    }
+
+    /// Compute the merkle digest of the element.
+    ///
+    /// To recompute the digest after changing a single child, use a [`MerkleTree`](MerkleTree).
+    ///
+    /// (requires `hash` feature)
+    pub fn merkle_digest<H: Digest>(&self) -> TychoResult<Output<H>> {
+        Ok(MerkleTree::<H>::new(self)?.digest().clone())
+    }
+}
@@ -1,12 +1,78 @@
-//! Utility traits for element compression/decompression. (requires `compression` feature)
+//! Codecs and utility traits for element compression/decompression. (requires `compression` feature)
 
 use crate::error::{TychoResult, TychoError};
 use crate::Element;
+use crate::into::ident::Ident;
+use crate::types::ident::ElementIdent;
+
+pub use crate::types::ident::Codec;
+
+#[derive(Debug, Clone, Copy, Default)]
+/// The codec and level used to write a compression element.
+///
+/// Without a level, the default level of the codec is used. (gzip 6, zstd 3, brotli 11)
+/// Levels are clamped to the maximum of the codec, and are ignored by lz4.
+///
+/// The level is not stored within the compressed data, so it is not read back,
+/// and configs with different levels are equal.
+///
+/// ### Example
+/// ```
+/// use tycho::Element;
+/// use tycho::compression::{Codec, Compression, CompressElement};
+///
+/// let element = Element::Unit.compress_with(Codec::Gzip, 9);
+/// assert_eq!(element, Element::Compression(Compression::new(Codec::Gzip), Box::new(Element::Unit)));
+/// ```
+pub struct Compression {
+    codec: Codec,
+    level: Option<u32>,
+}
+
+impl Compression {
+    /// Create a config for a codec, at the default level of the codec.
+    pub fn new(codec: Codec) -> Self {
+        Compression { codec, level: None }
+    }
+
+    /// Set the level of compression.
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Get the codec used to compress the element.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Get the level of compression, if one was set.
+    pub fn level(&self) -> Option<u32> {
+        self.level
+    }
+}
+
+impl PartialEq for Compression {
+    fn eq(&self, other: &Self) -> bool {
+        self.codec == other.codec
+    }
+}
+
+impl From<Codec> for Compression {
+    fn from(codec: Codec) -> Self {
+        Compression::new(codec)
+    }
+}
 
 /// Utility for element compression
 pub trait CompressElement {
     /// Mark an element for compression by placing it within a compression element.
+    ///
+    /// The element is compressed with gzip, at the default level.
     fn compress(self) -> Self;
+
+    /// Mark an element for compression with a codec and level.
+    fn compress_with(self, codec: Codec, level: u32) -> Self;
 }
 
 /// Utility for element decompression
@@ -43,16 +109,21 @@ pub trait DecompressElement: Sized {
 
 impl CompressElement for Element {
     fn compress(self) -> Self {
-        Element::Compression(Box::new(self))
+        Element::Compression(Compression::default(), Box::new(self))
+    }
+
+    fn compress_with(self, codec: Codec, level: u32) -> Self {
+        Element::Compression(Compression::new(codec).with_level(level), Box::new(self))
     }
 }
 
 impl DecompressElement for Element {
     fn _impl_decompress(self) -> Result<Self, (Self, TychoError)> {
-        if let Element::Compression(e) = self {
+        if let Element::Compression(_, e) = self {
             Ok(*e)
         } else {
-            Err((self, TychoError::Other("Element cannot be decompressed".to_string())))
+            let found = self.ident();
+            Err((self, TychoError::ElementMismatch { expected: ElementIdent::Compression, found }))
         }
     }
 }
\ No newline at end of file
@@ -1,29 +1,48 @@
 use std::convert::TryFrom;
 
 use crate::{Element, Number, Value};
+use crate::error::TychoError;
+use crate::into::ident::Ident;
+use crate::into::value::ValueType;
+use crate::types::ident::{ElementIdent, ValueIdent};
+
+/// The error for a value which is not of the expected type.
+pub(crate) fn value_mismatch(expected: ValueIdent, found: &Value) -> TychoError {
+    TychoError::ValueMismatch { expected, found: found.ident() }
+}
+
+/// The error for an element which is not a value of the expected type.
+pub(crate) fn element_mismatch(expected: ValueIdent, found: &Element) -> TychoError {
+    match found {
+        Element::Value(value) => value_mismatch(expected, value),
+        _ => TychoError::ElementMismatch { expected: ElementIdent::Value, found: found.ident() }
+    }
+}
 
 macro_rules! number_to {
     ($id: ident, $type: ty) => {
         impl TryFrom<Number> for $type {
-            type Error = ();
+            type Error = TychoError;
 
             fn try_from(value: Number) -> Result<Self, Self::Error> {
-                if let Number::$id(x) = value { return Ok(x) } else { Err(()) }
+                if let Number::$id(x) = value { return Ok(x) }
+                else { Err(value_mismatch(<$type>::IDENT, &Value::Number(value))) }
             }
         }
         impl TryFrom<Value> for $type {
-            type Error = ();
+            type Error = TychoError;
 
             fn try_from(value: Value) -> Result<Self, Self::Error> {
-                if let Value::Number(Number::$id(x)) = value { return Ok(x) } else { Err(()) }
+                if let Value::Number(Number::$id(x)) = value { return Ok(x) }
+                else { Err(value_mismatch(<$type>::IDENT, &value)) }
             }
         }
         impl TryFrom<Element> for $type {
-            type Error = ();
+            type Error = TychoError;
 
             fn try_from(value: Element) -> Result<Self, Self::Error> {
                 if let Element::Value(Value::Number(Number::$id(x))) = value { return Ok(x) }
-                 else { Err(()) }
+                else { Err(element_mismatch(<$type>::IDENT, &value)) }
             }
         }
     };
@@ -46,15 +65,17 @@ number_to!(Float64, f64);
 macro_rules! value_to {
     ($id: ident, $type: ty) => {
         impl TryFrom<Value> for $type {
-            type Error = ();
+            type Error = TychoError;
             fn try_from(value: Value) -> Result<Self, Self::Error> {
-                if let Value::$id(x) = value { return Ok(x) } else { Err(()) }
+                if let Value::$id(x) = value { return Ok(x) }
+                else { Err(value_mismatch(<$type>::IDENT, &value)) }
             }
         }
         impl TryFrom<Element> for $type {
-            type Error = ();
+            type Error = TychoError;
             fn try_from(value: Element) -> Result<Self, Self::Error> {
-                if let Element::Value(Value::$id(x)) = value { return Ok(x) } else { Err(()) }
+                if let Element::Value(Value::$id(x)) = value { return Ok(x) }
+                else { Err(element_mismatch(<$type>::IDENT, &value)) }
             }
         }
     };
@@ -26,7 +26,7 @@ impl Ident for Element {
             Element::List(_) =>  ElementIdent::List,
             Element::Array(_, _) =>  ElementIdent::Array,
             Element::Map(_, _) =>  ElementIdent::Map,
-            Element::Compression(_) =>  ElementIdent::Compression
+            Element::Compression(..) =>  ElementIdent::Compression
         }
     }
 }
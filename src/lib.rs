@@ -7,8 +7,17 @@
 //! - `serde` - Serde serialisation and deserialisation
 //! - `serde_optimise` - Serde structure optimisation (default)
 //! - `serde_types` - Serde structure type serialisation (default)
-//! - `async_tokio` - Async reading support with tokio
+//! - `async_tokio` - Async reading and writing support with tokio, and `tokio_util` codecs
 //! - `compression` - Compression (gzip)
+//! - `compression_zstd` - Zstandard compression
+//! - `compression_lz4` - LZ4 compression
+//! - `compression_brotli` - Brotli compression
+//! - `ordered` - Struct and map elements keep insertion order (`ElementMap` keeps the same api)
+//! - `uuid` - Conversions between `uuid::Uuid` and tycho uuids
+//! - `hash` - Content hashing and merkle digests
+//! - `hash_sha2` - SHA-256 hashing
+//! - `hash_blake3` - BLAKE3 hashing
+//! - `mmap` - Partial reading from memory-mapped files
 
 #![allow(unused_imports)]
 
@@ -29,7 +38,15 @@ pub mod partial;
 #[cfg(feature="serde")]
 mod serde;
 
+#[cfg(feature="async_tokio")]
+pub mod codec;
+
 pub mod collections;
+pub mod view;
+pub mod query;
+
+#[cfg(feature="hash")]
+pub mod hash;
 pub mod error;
 
 #[cfg(test)]
@@ -37,3 +54,5 @@ mod tests;
 
 pub(crate) mod uuid;
 pub use crate::uuid::Uuid;
+#[cfg(all(feature="uuid", feature="serde"))]
+pub use crate::uuid::uuid_serde;
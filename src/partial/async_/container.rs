@@ -3,8 +3,9 @@
 use crate::partial::container::{PartialContainerType, PartialContainer};
 use tokio::io::{AsyncRead, AsyncSeek};
 use crate::partial::PartialReader;
-use crate::error::{TychoResult, TychoError};
+use crate::error::{TychoError, TychoResult};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
 
 #[async_trait]
 pub trait PartialContainerTypeAsync: PartialContainerType {
@@ -14,13 +15,10 @@ pub trait PartialContainerTypeAsync: PartialContainerType {
 impl<T: PartialContainerTypeAsync> PartialContainer<T> {
 
     pub(crate) async fn next_item_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Option<T::ItemType>> {
-        #[cfg(feature="partial_state")]
-        if self.pointer.ident != reader.ident {
-            return Err(TychoError::OutdatedPointer)
-        }
+        reader.check_pointer(&self.pointer)?;
 
         // Check that the list is not finished
-        if self.head == self.pointer.size {
+        if self.head >= self.pointer.size {
             return Ok(None);
         }
 
@@ -31,14 +29,22 @@ impl<T: PartialContainerTypeAsync> PartialContainer<T> {
         reader.jump_async(&(self.pointer.pos + self.head)).await?;
         let head_start = reader.pointer;
 
-        let item = T::read_item_async(reader, &self.param).await?;
-
-        // increment head
-        self.head += reader.pointer - head_start;
+        let item = T::read_item_async(reader, &self.param).await
+            .map_err(|e| T::item_error(e.located(head_start), self.index))?;
 
         // reset pointer
+        let head = self.head + (reader.pointer - head_start);
         reader.jump_async(&top).await?;
 
+        // the item must end within the container
+        if head > self.pointer.size {
+            return Err(T::item_error(TychoError::ContainerOverflow.located(head_start), self.index));
+        }
+
+        // increment head
+        self.head = head;
+        self.index += 1;
+
         // return item
         Ok(Some(item))
     }
@@ -47,9 +53,43 @@ impl<T: PartialContainerTypeAsync> PartialContainer<T> {
         self.next_item_async(reader).await
     }
 
-    /*pub fn iter_async<'x, R: AsyncRead + AsyncSeek + Unpin + Send>(&'x mut self, reader: &'x mut PartialReader<R>) -> PartialContainerAsyncIterator<'x, T, R> {
-        PartialContainerAsyncIterator::new(self, reader)
-    }*/
+    /// Get an async stream of the items within the container.
+    ///
+    /// Unlike `iter`, errors are yielded rather than ending the stream early.
+    /// After any error, the stream ends.
+    ///
+    /// ### Example
+    /// ```
+    /// use std::io::Cursor;
+    /// use futures::StreamExt;
+    /// use tycho::{Element, marshall_vec, Value};
+    /// use tycho::partial::{PartialElement, PartialReader};
+    /// # let _ = tokio_test::block_on( async {
+    ///
+    /// let bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Value(Value::Boolean(true))])).unwrap();
+    /// let mut reader = PartialReader::from(Cursor::new(bytes));
+    ///
+    /// if let PartialElement::List(mut list) = reader.element_async().await.unwrap() {
+    ///     let mut stream = list.stream_async(&mut reader);
+    ///
+    ///     while let Some(item) = stream.next().await {
+    ///         println!("{:?}", item.unwrap());
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn stream_async<'x, R: AsyncRead + AsyncSeek + Unpin + Send>(&'x mut self, reader: &'x mut PartialReader<R>) -> BoxStream<'x, TychoResult<T::ItemType>>
+        where T: Send, T::ItemType: Send, T::ItemParam: Send + Sync
+    {
+        stream::unfold(Some((self, reader)), |state| async move {
+            let (container, reader) = state?;
+            match container.next_item_async(reader).await {
+                Ok(Some(item)) => Some((Ok(item), Some((container, reader)))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None))
+            }
+        }).boxed()
+    }
 
     pub async fn collect_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Vec<T::ItemType>> {
         let mut items = Vec::new();
@@ -60,35 +100,3 @@ impl<T: PartialContainerTypeAsync> PartialContainer<T> {
     }
 }
 
-
-/*
-pub struct PartialContainerAsyncIterator<'x, T: PartialContainerTypeAsync, R: AsyncRead + AsyncSeek + Unpin + Send> (
-    &'x mut PartialContainer<T>,
-    &'x mut PartialReader<R>
-);
-
-impl<'x, T: PartialContainerTypeAsync, R: AsyncRead + AsyncSeek + Unpin + Send> PartialContainerAsyncIterator<'x, T, R> {
-    pub fn new(
-        container: &'x mut PartialContainer<T>,
-        reader: &'x mut PartialReader<R>
-    ) -> PartialContainerAsyncIterator<'x, T, R> {
-        Self {
-            0: container,
-            1: reader
-        }
-    }
-
-    pub(crate) async fn handle_next(&mut self) -> Option<T::ItemType> {
-        self.0.next_async(&mut self.0).await.ok()?
-    }
-}
-
-impl<'x, T: PartialContainerTypeAsync, R: AsyncRead + AsyncSeek + Unpin + Send> Iterator for PartialContainerAsyncIterator<'x, T, R> {
-    type Item = Box<dyn Future<Output=Option<T::ItemType>>>;
-
-    fn next(&mut self) -> Self::Item {
-        Box::new(self.handle_next())
-    }
-}
-
-*/
\ No newline at end of file
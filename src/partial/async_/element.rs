@@ -1,85 +1,182 @@
 use tokio::io::{AsyncRead, AsyncSeek};
 
-use crate::error::TychoResult;
+use crate::error::{PathSegment, TychoResult};
 use crate::ident::ElementIdent;
 use crate::partial::{PartialElement, PartialReader};
-use crate::read::async_::element::read_element_ident_async;
+use crate::read::async_::func::read_byte_async;
 use crate::read::async_::length::read_length_async;
-use crate::read::async_::string::read_tstring_async;
-use crate::read::async_::value::{read_value_async, read_value_ident_async};
+use crate::read::async_::value::read_value_ident_async;
 use crate::types::ident::ValueIdent;
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use crate::partial::types::{PartialStruct, PartialList, PartialArray, PartialMap, PartialCompression};
+use crate::partial::types::{PartialStruct, PartialList, PartialArray, PartialMap, PartialCompression, PartialCompressionAsync};
+use crate::read::element::{parse_codec, parse_element_ident, read_compressed};
+use crate::{Element, ElementMap};
+
+#[cfg(feature="serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature="serde")]
+use crate::from_element;
+
+impl PartialElement {
+    /// Read the whole of the element asynchronously, including the contents of any containers within it.
+    ///
+    /// (requires `async_tokio` feature)
+    pub async fn read_full_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<Element> {
+        read_full_element_async(reader, self).await
+    }
+
+    #[cfg(feature="serde")]
+    /// Read the whole of the element asynchronously, and deserialize it into a type.
+    ///
+    /// (requires `serde` feature and `async_tokio` feature)
+    pub async fn deserialize_async<D: DeserializeOwned, R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<D> {
+        from_element(self.read_full_async(reader).await?)
+    }
+}
+
+fn read_full_element_async<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &'a mut PartialReader<R>, element: &'a PartialElement) -> BoxFuture<'a, TychoResult<Element>> {
+    async move {
+        Ok(match element {
+            PartialElement::Unit => Element::Unit,
+            PartialElement::Value(value) => Element::Value(value.clone()),
+            PartialElement::Option(None) => Element::Option(None),
+            PartialElement::Option(Some(x)) => Element::Option(Some(Box::new(read_full_element_async(reader, x).await?))),
+            PartialElement::Variant(name, x) => Element::Variant(name.clone(), Box::new(read_full_element_async(reader, x).await?)),
+            PartialElement::Struct(s) => {
+                let mut s = s.clone();
+                s.top();
+                let mut items = ElementMap::new();
+                while let Some((key, value)) = s.next_async(reader).await? {
+                    let value = read_full_element_async(reader, &value).await.map_err(|e| e.within(PathSegment::Field(key.clone())))?;
+                    items.insert(key, value);
+                }
+                Element::Struct(items)
+            }
+            PartialElement::List(l) => {
+                let mut l = l.clone();
+                l.top();
+                let mut items = Vec::new();
+                while let Some(item) = l.next_async(reader).await? {
+                    let index = items.len();
+                    items.push(read_full_element_async(reader, &item).await.map_err(|e| e.within(PathSegment::Index(index)))?);
+                }
+                Element::List(items)
+            }
+            PartialElement::Map(m) => {
+                let mut m = m.clone();
+                m.top();
+                let mut items = ElementMap::new();
+                while let Some((key, value)) = m.next_async(reader).await? {
+                    let value = read_full_element_async(reader, &value).await.map_err(|e| e.within(PathSegment::Key(key.clone())))?;
+                    items.insert(key, value);
+                }
+                Element::Map(m.param.clone(), items)
+            }
+            PartialElement::Array(a) => {
+                let mut a = a.clone();
+                a.top();
+                Element::Array(a.param.clone(), a.collect_async(reader).await?)
+            }
+            PartialElement::Compression(c) => {
+                let bytes = c.bytes_async(reader).await?;
+                reader.limited(|_, limiter| read_compressed(bytes, c.codec, c.pointer.pos, limiter))?
+            }
+        })
+    }.boxed()
+}
 
 pub fn read_partial_element_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>) -> BoxFuture<TychoResult<PartialElement>> {
     async move {
-        let ident = read_element_ident_async(reader).await?;
+        let start = reader.pointer;
+        read_partial_element_at_async(reader).await.map_err(|e| e.located(start))
+    }.boxed()
+}
+
+fn read_partial_element_at_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>) -> BoxFuture<'_, TychoResult<PartialElement>> {
+    async move {
+        let byte = read_byte_async(reader).await?;
+        let ident = parse_element_ident(byte)?;
 
-        match ident {
-            ElementIdent::Unit => Ok(PartialElement::Unit),
-            ElementIdent::Value => {
-                let prefix = read_value_ident_async(reader).await?;
-                let value = read_value_async(reader, &prefix).await?;
-                Ok(PartialElement::Value(value))
-            },
+        reader.limiter.enter()?;
+        let element = read_partial_element_body_async(reader, ident, byte).await;
+        reader.limiter.exit();
 
-            ElementIdent::None => Ok(PartialElement::Option(None)),
-            ElementIdent::Some => read_partial_element_async(reader).await,
+        element
+    }.boxed()
+}
 
-            ElementIdent::Variant => {
-                let name = read_tstring_async(reader).await?;
-                let value = read_partial_element_async(reader).await?;
+async fn read_partial_element_body_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>, ident: ElementIdent, byte: u8) -> TychoResult<PartialElement> {
+    match ident {
+        ElementIdent::Unit => Ok(PartialElement::Unit),
+        ElementIdent::Value => {
+            let prefix = read_value_ident_async(reader).await?;
+            let value = reader.value_async(&prefix).await?;
+            Ok(PartialElement::Value(value))
+        },
 
-                Ok(PartialElement::Variant(name, Box::new(value)))
+        ElementIdent::None => Ok(PartialElement::Option(None)),
+        ElementIdent::Some => Ok(PartialElement::Option(Some(Box::new(read_partial_element_async(reader).await?)))),
+
+        ElementIdent::Variant => {
+            let name = reader.tstring_async().await?;
+            match read_partial_element_async(reader).await {
+                Ok(value) => Ok(PartialElement::Variant(name, Box::new(value))),
+                Err(e) => Err(e.within(PathSegment::Variant(name)))
             }
+        }
 
-            ElementIdent::Struct => {
-                let size = read_length_async(reader).await? as u64;
-                let pos = reader.pointer.clone();
-                reader.jump_async(&size).await?;
-                Ok(PartialElement::Struct(PartialStruct::new(reader.pointer(pos, size), 0, ())))
-            },
-
-            ElementIdent::List => {
-                let size = read_length_async(reader).await? as u64;
-                let pos = reader.pointer.clone();
-                reader.jump_async(&size).await?;
-                Ok(PartialElement::List(PartialList::new(reader.pointer(pos, size), 0, ())))
-            },
-
-            ElementIdent::Array => {
-                let array_type = read_value_ident_async(reader).await?;
-
-                if array_type == ValueIdent::Null {
-                    return Ok(PartialElement::Array(PartialArray::empty(reader.empty_pointer(), array_type)))
-                }
+        ElementIdent::Struct => {
+            let size = read_length_async(reader).await? as u64;
+            reader.limiter.container(size as usize)?;
+            let pos = reader.pointer.clone();
+            reader.jump_async(&(pos + size)).await?;
+            Ok(PartialElement::Struct(PartialStruct::new(reader.pointer(pos, size), 0, ())))
+        },
 
-                let size = read_length_async(reader).await? as u64;
-                let pos = reader.pointer.clone();
-                reader.jump_async(&size).await?;
-                Ok(PartialElement::Array(PartialArray::new(reader.pointer(pos, size), 0, array_type)))
-            },
+        ElementIdent::List => {
+            let size = read_length_async(reader).await? as u64;
+            reader.limiter.container(size as usize)?;
+            let pos = reader.pointer.clone();
+            reader.jump_async(&(pos + size)).await?;
+            Ok(PartialElement::List(PartialList::new(reader.pointer(pos, size), 0, ())))
+        },
 
-            ElementIdent::Map => {
-                let key_type = read_value_ident_async(reader).await?;
+        ElementIdent::Array => {
+            let array_type = read_value_ident_async(reader).await?;
 
-                if key_type == ValueIdent::Null {
-                    return Ok(PartialElement::Map(PartialMap::empty(reader.empty_pointer(), key_type)))
-                }
+            if array_type == ValueIdent::Null {
+                return Ok(PartialElement::Array(PartialArray::empty(reader.empty_pointer(), array_type)))
+            }
+
+            let size = read_length_async(reader).await? as u64;
+            reader.limiter.container(size as usize)?;
+            let pos = reader.pointer.clone();
+            reader.jump_async(&(pos + size)).await?;
+            Ok(PartialElement::Array(PartialArray::new(reader.pointer(pos, size), 0, array_type)))
+        },
+
+        ElementIdent::Map => {
+            let key_type = read_value_ident_async(reader).await?;
 
-                let size = read_length_async(reader).await? as u64;
-                let pos = reader.pointer.clone();
-                reader.jump_async(&size).await?;
-                Ok(PartialElement::Map(PartialMap::new(reader.pointer(pos, size), 0, key_type)))
-            },
-
-            ElementIdent::Compression => {
-                let size = read_length_async(reader).await? as u64;
-                let pos = reader.pointer.clone();
-                reader.jump_async(&size).await?;
-                Ok(PartialElement::Compression(PartialCompression::new(reader.pointer(pos, size))))
+            if key_type == ValueIdent::Null {
+                return Ok(PartialElement::Map(PartialMap::empty(reader.empty_pointer(), key_type)))
             }
+
+            let size = read_length_async(reader).await? as u64;
+            reader.limiter.container(size as usize)?;
+            let pos = reader.pointer.clone();
+            reader.jump_async(&(pos + size)).await?;
+            Ok(PartialElement::Map(PartialMap::new(reader.pointer(pos, size), 0, key_type)))
+        },
+
+        ElementIdent::Compression => {
+            let codec = parse_codec(byte)?;
+            let size = read_length_async(reader).await? as u64;
+            reader.limiter.container(size as usize)?;
+            let pos = reader.pointer.clone();
+            reader.jump_async(&(pos + size)).await?;
+            Ok(PartialElement::Compression(PartialCompression::new(reader.pointer(pos, size), codec)))
         }
-    }.boxed()
+    }
 }
\ No newline at end of file
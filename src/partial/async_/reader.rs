@@ -2,9 +2,13 @@ use std::task::{Context, Poll};
 
 use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, ReadBuf, SeekFrom};
 
+use crate::Value;
 use crate::error::{parse_io, TychoResult, TychoStatus};
-use crate::partial::{PartialElement, PartialReader};
+use crate::ident::ValueIdent;
+use crate::partial::{PartialElement, PartialPointer, PartialReader};
 use crate::partial::async_::element::read_partial_element_async;
+use crate::read::async_::string::read_tstring_async;
+use crate::read::async_::value::read_value_async;
 use std::pin::Pin;
 
 #[allow(type_alias_bounds)]
@@ -16,7 +20,10 @@ impl<R: AsyncSeek + AsyncRead + Unpin + Send> PartialReader<R> {
     ///
     /// (requires  `async_tokio` feature)
     pub async fn jump_async(&mut self, to: &u64) -> TychoStatus {
-        parse_io(self.reader.seek(SeekFrom::Current((*to as i64) - (self.pointer as i64))).await)?;
+        if !self.buffered(*to) && self.physical != *to {
+            parse_io(self.reader.seek(SeekFrom::Current((*to as i64) - (self.physical as i64))).await)?;
+            self.physical = *to;
+        }
         self.pointer = *to;
         Ok(())
     }
@@ -27,14 +34,64 @@ impl<R: AsyncSeek + AsyncRead + Unpin + Send> PartialReader<R> {
     pub async fn element_async(&mut self) -> TychoResult<PartialElement> {
         read_partial_element_async(self).await
     }
+
+    /// Read the element at a pointer on an asynchronous reader.
+    ///
+    /// See `element_at` for more details.
+    ///
+    /// (requires  `async_tokio` feature)
+    pub async fn element_at_async(&mut self, pointer: &PartialPointer) -> TychoResult<PartialElement> {
+        self.check_pointer(pointer)?;
+        let previous = self.pointer;
+        self.jump_async(&pointer.pos).await?;
+
+        let result = match read_partial_element_async(self).await {
+            Ok(element) => self.check_end(pointer, element),
+            Err(e) => Err(e)
+        };
+        self.jump_async(&previous).await?;
+        result
+    }
+
+    /// Read a value asynchronously with the reader's limiter.
+    pub(crate) async fn value_async(&mut self, ident: &ValueIdent) -> TychoResult<Value> {
+        let mut limiter = self.limiter;
+        let value = read_value_async(self, ident, &mut limiter).await;
+        self.limiter = limiter;
+        value
+    }
+
+    /// Read a terminated string asynchronously with the reader's limiter.
+    pub(crate) async fn tstring_async(&mut self) -> TychoResult<String> {
+        let mut limiter = self.limiter;
+        let string = read_tstring_async(self, &mut limiter).await;
+        self.limiter = limiter;
+        string
+    }
 }
 
 impl<R: AsyncRead + AsyncSeek + Unpin> AsyncRead for PartialReader<R> {
     fn poll_read(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
-        match AsyncRead::poll_read(Pin::new(&mut self), cx, buf) {
+        // bytes buffered by a synchronous read are given out first.
+        if self.buffered(self.pointer) {
+            let offset = (self.pointer - self.start) as usize;
+            let size = (self.filled - offset).min(buf.remaining());
+            buf.put_slice(&self.buffer[offset..offset + size]);
+            self.pointer += size as u64;
+            return Poll::Ready(Ok(()));
+        }
+        if self.physical != self.pointer {
+            return Poll::Ready(Err(std::io::Error::other(
+                "the reader was moved by a synchronous read, and must be moved with jump_async")));
+        }
+
+        let filled = buf.filled().len();
+        match AsyncRead::poll_read(Pin::new(&mut self.reader), cx, buf) {
             Poll::Ready(x) => match x {
                 Ok(_) => {
-                    self.pointer += buf.filled().len() as u64;
+                    let size = (buf.filled().len() - filled) as u64;
+                    self.pointer += size;
+                    self.physical += size;
                     Poll::Ready(Ok(()))
                 }
                 Err(e) => Poll::Ready(Err(e))
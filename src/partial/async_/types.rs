@@ -1,21 +1,29 @@
-use crate::error::TychoResult;
+use crate::error::{PathSegment, TychoResult};
 use crate::partial::reader::PartialReader;
 use async_trait::async_trait;
 use tokio::io::{AsyncSeek, AsyncRead};
 use crate::partial::async_::container::PartialContainerTypeAsync;
-use crate::read::async_::string::read_tstring_async;
 use crate::partial::async_::element::read_partial_element_async;
 use crate::partial::types::{PartialStructInner, PartialListInner, PartialMapInner, PartialArrayInner, PartialCompression};
-use crate::read::async_::value::read_value_async;
 use crate::read::async_::func::read_bytes_async;
-use crate::partial::{PartialElement};
+
+#[cfg(feature="compression")]
+use std::io::Cursor;
+#[cfg(feature="compression")]
+use crate::Element;
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
+#[cfg(feature="compression")]
+use crate::read::element::read_decompressed;
 
 #[async_trait]
 impl PartialContainerTypeAsync for PartialStructInner {
     async fn read_item_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>, _params: &Self::ItemParam) -> TychoResult<Self::ItemType> {
-        let key = read_tstring_async(reader).await?;
-        let value = read_partial_element_async(reader).await?;
-        Ok((key, value))
+        let key = reader.tstring_async().await?;
+        match read_partial_element_async(reader).await {
+            Ok(value) => Ok((key, value)),
+            Err(e) => Err(e.within(PathSegment::Field(key)))
+        }
     }
 }
 
@@ -29,16 +37,18 @@ impl PartialContainerTypeAsync for PartialListInner {
 #[async_trait]
 impl PartialContainerTypeAsync for PartialMapInner {
     async fn read_item_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>, params: &Self::ItemParam) -> TychoResult<Self::ItemType> {
-        let key = read_value_async(reader, &params).await?;
-        let value = read_partial_element_async(reader).await?;
-        Ok((key, value))
+        let key = reader.value_async(params).await?;
+        match read_partial_element_async(reader).await {
+            Ok(value) => Ok((key, value)),
+            Err(e) => Err(e.within(PathSegment::Key(key)))
+        }
     }
 }
 
 #[async_trait]
 impl PartialContainerTypeAsync for PartialArrayInner {
     async fn read_item_async<R: AsyncRead + AsyncSeek + Unpin + Send>(reader: &mut PartialReader<R>, params: &Self::ItemParam) -> TychoResult<Self::ItemType> {
-        read_value_async(reader, &params).await
+        reader.value_async(params).await
     }
 }
 
@@ -47,7 +57,9 @@ impl PartialContainerTypeAsync for PartialArrayInner {
 pub trait PartialCompressionAsync {
     async fn bytes_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<Vec<u8>>;
     #[cfg(feature="compression")]
-    async fn element_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<PartialElement>;
+    async fn element_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<Element>;
+    #[cfg(feature="compression")]
+    async fn reader_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<PartialReader<Cursor<Vec<u8>>>>;
 }
 
 #[async_trait]
@@ -64,14 +76,22 @@ impl PartialCompressionAsync for PartialCompression {
     }
 
     #[cfg(feature="compression")]
-    /// Get the element within the compression object asynchronously.
+    /// Decompress and read the element within the compression object asynchronously.
     ///
     /// (requires `compression` feature and `async_tokio` feature)
-    async fn element_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<PartialElement> {
-        let top = reader.pointer.clone();
-        reader.jump_async(&self.pointer.pos).await?;
-        let element = read_partial_element_async(reader).await?;
-        reader.jump_async(&top).await?;
-        Ok(element)
+    async fn element_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<Element> {
+        let bytes = self.bytes_async(reader).await?;
+        reader.limited(|_, limiter| read_decompressed(&bytes, self.codec, self.pointer.pos, limiter))
+    }
+
+    #[cfg(feature="compression")]
+    /// Decompress the bytes within the compression object into a new partial reader asynchronously,
+    /// with the same options as the reader.
+    ///
+    /// (requires `compression` feature and `async_tokio` feature)
+    async fn reader_async<R: AsyncRead + AsyncSeek + Unpin + Send>(&self, reader: &mut PartialReader<R>) -> TychoResult<PartialReader<Cursor<Vec<u8>>>> {
+        let bytes = self.bytes_async(reader).await?;
+        let data = reader.limited(|_, limiter| decompress_limited(&bytes, self.codec, limiter))?;
+        Ok(PartialReader::from_vec(data).with_options(reader.limiter.options))
     }
 }
@@ -1,9 +1,12 @@
 //! The common container structure used within container types.
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, Write};
 use std::marker::PhantomData;
 
-use crate::error::{TychoError, TychoResult};
+#[cfg(feature="hash")]
+use digest::{Digest, Output};
+
+use crate::error::{parse_io, TychoError, TychoResult, TychoStatus};
 use crate::partial::reader::{PartialPointer, PartialReader};
 
 pub trait PartialContainerType {
@@ -12,6 +15,14 @@ pub trait PartialContainerType {
     //type ItemStandard;
 
     fn read_item<R: Read + Seek>(reader: &mut PartialReader<R>, param: &Self::ItemParam) -> TychoResult<Self::ItemType>;
+
+    /// Add the position of an item within the container to an error raised while reading it.
+    fn item_error(error: TychoError, _index: usize) -> TychoError {
+        error
+    }
+
+    /// Write the prefix of the container, which comes before its bytes.
+    fn write_prefix<W: Write>(writer: &mut W, param: &Self::ItemParam, size: u64) -> TychoStatus;
     //fn standardise(items: Vec<T::ItemType>) -> TychoResult<Self::ItemStandard>;
 }
 
@@ -23,29 +34,28 @@ pub struct PartialContainer<T: PartialContainerType> {
     pub head: u64,
     /// Parameters for the container.
     pub param: T::ItemParam,
+    /// The index of the next item.
+    pub(crate) index: usize,
     _phantom: PhantomData<T>
 }
 
 impl<T: PartialContainerType> PartialContainer<T> {
     /// Create a new partial container
     pub(crate) fn new(pointer: PartialPointer, head: u64, param: T::ItemParam) -> Self {
-        PartialContainer { pointer, head, _phantom: Default::default(), param }
+        PartialContainer { pointer, head, index: 0, _phantom: Default::default(), param }
     }
 
     /// Create an empty partial container
     pub(crate) fn empty(pointer: PartialPointer, param: T::ItemParam) -> Self {
-        PartialContainer { pointer, head: 0, _phantom: Default::default(), param }
+        PartialContainer { pointer, head: 0, index: 0, _phantom: Default::default(), param }
     }
 
     /// Read next item
     pub(crate) fn next_item<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Option<T::ItemType>> {
-        #[cfg(feature="partial_state")]
-        if self.pointer.ident != reader.ident {
-            return Err(TychoError::OutdatedPointer)
-        }
+        reader.check_pointer(&self.pointer)?;
 
         // Check that the list is not finished
-        if self.head == self.pointer.size {
+        if self.head >= self.pointer.size {
             return Ok(None);
         }
 
@@ -56,20 +66,61 @@ impl<T: PartialContainerType> PartialContainer<T> {
         reader.jump(&(self.pointer.pos + self.head))?;
         let head_start = reader.pointer;
 
-        let item = T::read_item(reader, &self.param)?;
-
-        // increment head
-        self.head += reader.pointer - head_start;
+        let item = T::read_item(reader, &self.param)
+            .map_err(|e| T::item_error(e.located(head_start), self.index))?;
 
         // reset pointer
+        let head = self.head + (reader.pointer - head_start);
         reader.jump(&top)?;
 
+        // the item must end within the container
+        if head > self.pointer.size {
+            return Err(T::item_error(TychoError::ContainerOverflow.located(head_start), self.index));
+        }
+
+        // increment head
+        self.head = head;
+        self.index += 1;
+
         // return item
         Ok(Some(item))
     }
 
+    /// Visit each item from the start of the container, until `visit` finds a result.
+    ///
+    /// The head of the container and the position of the reader are left unchanged.
+    pub(crate) fn find_item<R: Read + Seek, X>(
+        &self,
+        reader: &mut PartialReader<R>,
+        mut visit: impl FnMut(&mut PartialReader<R>, usize) -> TychoResult<Option<X>>
+    ) -> TychoResult<Option<X>> {
+        reader.check_pointer(&self.pointer)?;
+
+        let top = reader.pointer;
+        let end = self.pointer.pos + self.pointer.size;
+        reader.jump(&self.pointer.pos)?;
+
+        let mut index = 0;
+        let result = loop {
+            if reader.pointer >= end {
+                break Ok(None);
+            }
+
+            let item_start = reader.pointer;
+            match visit(reader, index) {
+                Ok(Some(item)) => break Ok(Some(item)),
+                Ok(None) if reader.pointer > end => break Err(T::item_error(TychoError::ContainerOverflow.located(item_start), index)),
+                Ok(None) => index += 1,
+                Err(e) => break Err(T::item_error(e.located(item_start), index))
+            }
+        };
+
+        reader.jump(&top)?;
+        result
+    }
+
     /// Returns if the local pointer head has reached the end of the container.
-    pub fn finished(&self) -> bool { self.head == self.pointer.size }
+    pub fn finished(&self) -> bool { self.head >= self.pointer.size }
 
     /// Get the next item in the container
     pub fn next<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Option<T::ItemType>> {
@@ -77,10 +128,35 @@ impl<T: PartialContainerType> PartialContainer<T> {
     }
 
     /// Get an iterator of the container.
+    ///
+    /// This iterator is lossy: an error while reading an item ends the iteration,
+    /// which looks the same as reaching the end of the container.
+    /// Use `try_iter` to detect errors.
     pub fn iter<'x, R: Read + Seek>(&'x mut self, reader: &'x mut PartialReader<R>) -> PartialContainerIterator<'x, T, R> {
         PartialContainerIterator::new(self, reader)
     }
 
+    /// Get an iterator of the container, which yields errors.
+    ///
+    /// After any error, the iterator ends.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element, marshall_vec};
+    /// use tycho::partial::{PartialElement, PartialReader};
+    ///
+    /// let bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Unit])).unwrap();
+    /// let mut reader = PartialReader::from_vec(bytes);
+    ///
+    /// if let PartialElement::List(mut list) = reader.element().unwrap() {
+    ///     let items = list.try_iter(&mut reader).collect::<Result<Vec<_>, _>>().unwrap();
+    ///     assert_eq!(items.len(), 2);
+    /// }
+    /// ```
+    pub fn try_iter<'x, R: Read + Seek>(&'x mut self, reader: &'x mut PartialReader<R>) -> PartialContainerTryIterator<'x, T, R> {
+        PartialContainerTryIterator::new(self, reader)
+    }
+
     /// Collect all items within the container.
     pub fn collect<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Vec<T::ItemType>> {
         let mut items = Vec::new();
@@ -93,25 +169,41 @@ impl<T: PartialContainerType> PartialContainer<T> {
     /// Move the head to the top/start
     pub fn top(&mut self) {
         self.head = 0;
+        self.index = 0;
     }
 
-    /*pub fn standardise<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<T::ItemStandard> {
-        let head = self.head;
-        self.head = 0;
+    #[cfg(feature="hash")]
+    /// Compute the digest of the container from the bytes it points to, without decoding its items.
+    ///
+    /// When the container was written canonically,
+    /// this is equal to the digest of the decoded element.
+    ///
+    /// (requires `hash` feature)
+    pub fn digest<H: Digest, R: Read + Seek>(&self, reader: &mut PartialReader<R>) -> TychoResult<Output<H>> {
+        reader.check_pointer(&self.pointer)?;
 
-        let items = self.collect(reader)?;
+        let mut hasher = H::new();
 
-        let standard = T::standardise(items)?;
+        let mut prefix = Vec::new();
+        T::write_prefix(&mut prefix, &self.param, self.pointer.size)?;
+        hasher.update(&prefix);
 
-        self.head = head;
+        let top = reader.pointer;
+        reader.jump(&self.pointer.pos)?;
+
+        let mut buffer = [0u8; 4096];
+        let mut remaining = self.pointer.size;
+        while remaining > 0 {
+            let size = remaining.min(buffer.len() as u64) as usize;
+            parse_io(reader.read_exact(&mut buffer[..size]))?;
+            hasher.update(&buffer[..size]);
+            remaining -= size as u64;
+        }
 
-        Ok(standard)
+        reader.jump(&top)?;
+        Ok(hasher.finalize())
     }
 
-    pub fn replace<R: Read + Seek + Write>(&mut self, reader: &mut PartialReader<R>, element: T::) {
-        reader.jump(&self.pointer.pos);
-        reader.reader.write_all(&element)
-    }*/
 }
 
 pub struct PartialContainerIterator<'x, T: PartialContainerType, R: Read + Seek>(
@@ -137,4 +229,38 @@ impl<'x, T: PartialContainerType, R: Read + Seek> Iterator for PartialContainerI
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next_item(self.1).ok()?
     }
-}
\ No newline at end of file
+}
+
+/// An iterator over the items of a container, which yields errors rather than ending early.
+pub struct PartialContainerTryIterator<'x, T: PartialContainerType, R: Read + Seek> {
+    container: &'x mut PartialContainer<T>,
+    reader: &'x mut PartialReader<R>,
+    failed: bool,
+}
+
+impl<'x, T: PartialContainerType, R: Read + Seek> PartialContainerTryIterator<'x, T, R> {
+    pub fn new(
+        container: &'x mut PartialContainer<T>,
+        reader: &'x mut PartialReader<R>
+    ) -> PartialContainerTryIterator<'x, T, R> {
+        Self { container, reader, failed: false }
+    }
+}
+
+impl<'x, T: PartialContainerType, R: Read + Seek> Iterator for PartialContainerTryIterator<'x, T, R> {
+    type Item = TychoResult<T::ItemType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        match self.container.next_item(self.reader) {
+            Ok(item) => item.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! In-place editing of tycho data through a partial reader.
+
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+use crate::Element;
+use crate::error::{parse_io, TychoError, TychoResult, TychoStatus};
+use crate::partial::element::skip_partial_element;
+use crate::partial::reader::{BUFFER_SIZE, PartialPointer, PartialReader};
+use crate::read::element::read_element_ident;
+use crate::read::length::read_length;
+use crate::read::string::read_tstring;
+use crate::read::value::{read_value, read_value_ident};
+use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::write::element::write_element;
+use crate::write::length::write_length;
+use crate::write::string::write_tstring;
+
+/// A writable object which can be shortened, such as a file.
+///
+/// Edits which remove bytes shorten the data to its new length.
+pub trait SetLen {
+    /// Set the length of the data, removing any bytes after it.
+    fn set_len(&mut self, len: u64) -> std::io::Result<()>;
+}
+
+impl SetLen for File {
+    fn set_len(&mut self, len: u64) -> std::io::Result<()> {
+        File::set_len(self, len)
+    }
+}
+
+impl SetLen for Cursor<Vec<u8>> {
+    fn set_len(&mut self, len: u64) -> std::io::Result<()> {
+        self.get_mut().truncate(len as usize);
+        Ok(())
+    }
+}
+
+impl SetLen for Cursor<&mut Vec<u8>> {
+    fn set_len(&mut self, len: u64) -> std::io::Result<()> {
+        self.get_mut().truncate(len as usize);
+        Ok(())
+    }
+}
+
+/// The location of a container element, and its size prefix.
+#[derive(Debug, Clone)]
+struct Frame {
+    ident: ElementIdent,
+    /// The start of the container element.
+    start: u64,
+    /// The start of the size prefix.
+    length_pos: u64,
+    /// The start of the container's contents, after the size prefix.
+    contents_pos: u64,
+    /// The size of the container's contents.
+    size: u64,
+}
+
+impl Frame {
+    fn end(&self) -> u64 {
+        self.contents_pos + self.size
+    }
+}
+
+/// An editor, which modifies tycho data in place through a partial reader.
+///
+/// Containers are referred to by their `PartialPointer`, found by reading with `reader`.
+/// When an edit changes the size of an element,
+/// the size prefixes of every container around it are rewritten, and the bytes after it are moved.
+///
+/// Every edit invalidates pointers issued before it,
+/// returning `TychoError::OutdatedPointer` when used (requires `partial_state` feature).
+/// Without `partial_state`, old pointers are not detected, and must not be reused.
+///
+/// After an edit, the reader is left at the start of the data.
+///
+/// ### Example
+/// ```
+/// use std::io::Cursor;
+/// use tycho::{Element, ElementMap, marshall_vec, unmarshall_vec, Value};
+/// use tycho::partial::{PartialEditor, PartialElement, PartialReader};
+///
+/// let mut data = ElementMap::new();
+/// data.insert("foo".to_string(), Element::Unit);
+/// let bytes = marshall_vec(Element::Struct(data)).unwrap();
+///
+/// let mut editor = PartialEditor::new(PartialReader::from(Cursor::new(bytes)));
+///
+/// if let PartialElement::Struct(s) = editor.reader().element().unwrap() {
+///     editor.set_field(&s.pointer, "bar", Value::Boolean(true)).unwrap();
+/// }
+///
+/// let bytes = editor.into_inner().into_inner().into_inner();
+/// let mut expected = ElementMap::new();
+/// expected.insert("foo".to_string(), Element::Unit);
+/// expected.insert("bar".to_string(), Element::Value(Value::Boolean(true)));
+/// assert_eq!(unmarshall_vec(bytes).unwrap(), Element::Struct(expected));
+/// ```
+pub struct PartialEditor<R> {
+    reader: PartialReader<R>,
+}
+
+impl<R: Read + Write + Seek> PartialEditor<R> {
+    /// Create an editor over a reader, with the root element at the start.
+    pub fn new(reader: PartialReader<R>) -> Self {
+        PartialEditor { reader }
+    }
+
+    /// Get the reader, to find pointers to the containers to edit.
+    pub fn reader(&mut self) -> &mut PartialReader<R> {
+        &mut self.reader
+    }
+
+    /// Get the underlying partial reader.
+    pub fn into_inner(self) -> PartialReader<R> {
+        self.reader
+    }
+}
+
+impl<R: Read + Write + Seek + SetLen> PartialEditor<R> {
+    /// Replace the container element at a pointer with another element.
+    pub fn replace<E: Into<Element>>(&mut self, pointer: &PartialPointer, element: E) -> TychoStatus {
+        let mut chain = self.locate(pointer)?;
+        let target = chain.pop().ok_or(TychoError::PointerNotFound)?;
+
+        let bytes = encode(&element.into())?;
+        self.rewrite(&chain, target.start, target.end(), bytes)
+    }
+
+    /// Set the value of a field within the struct at a pointer,
+    /// adding the field if it does not exist.
+    pub fn set_field<E: Into<Element>>(&mut self, pointer: &PartialPointer, key: &str, element: E) -> TychoStatus {
+        let chain = self.locate_type(pointer, ElementIdent::Struct)?;
+        let bytes = encode(&element.into())?;
+
+        match self.find_field(chain.last().unwrap(), key)? {
+            Some((_, value_start, end)) => self.rewrite(&chain, value_start, end, bytes),
+            None => {
+                let mut item = Vec::new();
+                write_tstring(&mut item, key)?;
+                item.extend(bytes);
+
+                let end = chain.last().unwrap().end();
+                self.rewrite(&chain, end, end, item)
+            }
+        }
+    }
+
+    /// Remove a field from the struct at a pointer, returning if it existed.
+    pub fn remove_field(&mut self, pointer: &PartialPointer, key: &str) -> TychoResult<bool> {
+        let chain = self.locate_type(pointer, ElementIdent::Struct)?;
+
+        match self.find_field(chain.last().unwrap(), key)? {
+            Some((start, _, end)) => self.rewrite(&chain, start, end, Vec::new()).map(|_| true),
+            None => self.top().map(|_| false)
+        }
+    }
+
+    /// Insert an item into the list at a pointer, before the item at an index.
+    ///
+    /// Returns `TychoError::OutOfBounds` if the index is greater than the length of the list.
+    pub fn insert_item<E: Into<Element>>(&mut self, pointer: &PartialPointer, index: usize, element: E) -> TychoStatus {
+        let chain = self.locate_type(pointer, ElementIdent::List)?;
+        let bytes = encode(&element.into())?;
+
+        match self.find_item(chain.last().unwrap(), index)? {
+            Ok((start, _)) => self.rewrite(&chain, start, start, bytes),
+            Err(length) if length == index => {
+                let end = chain.last().unwrap().end();
+                self.rewrite(&chain, end, end, bytes)
+            }
+            Err(length) => {
+                self.top()?;
+                Err(TychoError::OutOfBounds { index, length })
+            }
+        }
+    }
+
+    /// Remove the item at an index from the list at a pointer, returning if it existed.
+    pub fn remove_item(&mut self, pointer: &PartialPointer, index: usize) -> TychoResult<bool> {
+        let chain = self.locate_type(pointer, ElementIdent::List)?;
+
+        match self.find_item(chain.last().unwrap(), index)? {
+            Ok((start, end)) => self.rewrite(&chain, start, end, Vec::new()).map(|_| true),
+            Err(_) => self.top().map(|_| false)
+        }
+    }
+
+    /// Find the containers from the root to the container at a pointer.
+    fn locate(&mut self, pointer: &PartialPointer) -> TychoResult<Vec<Frame>> {
+        self.reader.check_pointer(pointer)?;
+
+        let mut chain = Vec::new();
+        let mut start = 0;
+
+        loop {
+            let frame = self.read_frame(start)?.ok_or(TychoError::PointerNotFound)?;
+            chain.push(frame.clone());
+
+            if frame.contents_pos == pointer.pos && frame.size == pointer.size {
+                self.top()?;
+                return Ok(chain);
+            }
+            if pointer.pos < frame.contents_pos || pointer.pos > frame.end() || frame.ident == ElementIdent::Compression {
+                return Err(TychoError::PointerNotFound);
+            }
+
+            start = self.find_child(&frame, pointer.pos)?;
+        }
+    }
+
+    /// Find the containers from the root to the container at a pointer, which must be of a type.
+    fn locate_type(&mut self, pointer: &PartialPointer, ident: ElementIdent) -> TychoResult<Vec<Frame>> {
+        let chain = self.locate(pointer)?;
+        let found = chain.last().unwrap().ident.clone();
+        if found != ident {
+            return Err(TychoError::ElementMismatch { expected: ident, found });
+        }
+        Ok(chain)
+    }
+
+    /// Read the container element at a position, passing through options and variants.
+    fn read_frame(&mut self, start: u64) -> TychoResult<Option<Frame>> {
+        self.reader.jump(&start)?;
+
+        loop {
+            let start = self.reader.pointer;
+            let ident = read_element_ident(&mut self.reader)?;
+
+            match ident {
+                ElementIdent::Some => continue,
+                ElementIdent::Variant => {
+                    self.reader.limited(read_tstring)?;
+                    continue;
+                }
+                ElementIdent::Array | ElementIdent::Map => {
+                    if read_value_ident(&mut self.reader)? == ValueIdent::Null {
+                        return Ok(None);
+                    }
+                }
+                ElementIdent::Struct | ElementIdent::List | ElementIdent::Compression => (),
+                _ => return Ok(None),
+            }
+
+            let length_pos = self.reader.pointer;
+            let size = read_length(&mut self.reader)? as u64;
+            let contents_pos = self.reader.pointer;
+            return Ok(Some(Frame { ident, start, length_pos, contents_pos, size }));
+        }
+    }
+
+    /// Find the start of the item within a container which holds a position.
+    fn find_child(&mut self, frame: &Frame, pos: u64) -> TychoResult<u64> {
+        self.reader.jump(&frame.contents_pos)?;
+
+        while self.reader.pointer < frame.end() {
+            self.skip_key(frame)?;
+
+            let start = self.reader.pointer;
+            skip_partial_element(&mut self.reader)?;
+
+            // empty containers end at the start of their contents.
+            if pos <= self.reader.pointer {
+                return Ok(start);
+            }
+        }
+
+        Err(TychoError::PointerNotFound)
+    }
+
+    /// Move past the key of an item, if the container has keys.
+    fn skip_key(&mut self, frame: &Frame) -> TychoStatus {
+        match frame.ident {
+            ElementIdent::Struct => {
+                self.reader.limited(read_tstring)?;
+            }
+            ElementIdent::Map => {
+                let ident = read_value_ident(&mut self.reader)?;
+                self.reader.limited(|reader, limiter| read_value(reader, &ident, limiter))?;
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Find the start, value start and end of a field within a struct.
+    fn find_field(&mut self, frame: &Frame, key: &str) -> TychoResult<Option<(u64, u64, u64)>> {
+        self.reader.jump(&frame.contents_pos)?;
+
+        while self.reader.pointer < frame.end() {
+            let start = self.reader.pointer;
+            let found = self.reader.limited(read_tstring)?;
+            let value_start = self.reader.pointer;
+            skip_partial_element(&mut self.reader)?;
+
+            if found == key {
+                return Ok(Some((start, value_start, self.reader.pointer)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Find the start and end of an item within a list, or the length of the list if past the end.
+    fn find_item(&mut self, frame: &Frame, index: usize) -> TychoResult<Result<(u64, u64), usize>> {
+        self.reader.jump(&frame.contents_pos)?;
+
+        let mut current = 0;
+        while self.reader.pointer < frame.end() {
+            let start = self.reader.pointer;
+            skip_partial_element(&mut self.reader)?;
+
+            if current == index {
+                return Ok(Ok((start, self.reader.pointer)));
+            }
+            current += 1;
+        }
+
+        Ok(Err(current))
+    }
+
+    /// Replace the bytes between two positions,
+    /// and resize each container around them, from the innermost outwards.
+    fn rewrite(&mut self, chain: &[Frame], start: u64, end: u64, bytes: Vec<u8>) -> TychoStatus {
+        // the size prefix of each container and the edited span, as replacements in order of position.
+        let mut delta = bytes.len() as i64 - (end - start) as i64;
+        let mut edits = vec![(start, end, bytes)];
+
+        for frame in chain.iter().rev() {
+            let mut prefix = Vec::new();
+            write_length(&mut prefix, (frame.size as i64 + delta) as usize)?;
+
+            delta += prefix.len() as i64 - (frame.contents_pos - frame.length_pos) as i64;
+            edits.push((frame.length_pos, frame.contents_pos, prefix));
+        }
+        edits.reverse();
+
+        let inner = &mut self.reader.reader;
+        let length = parse_io(inner.seek(SeekFrom::End(0)))?;
+
+        // where each replacement is written, and how far the bytes up to the next one move.
+        let mut offset = 0;
+        let mut moves = Vec::with_capacity(edits.len());
+        for (i, (start, end, bytes)) in edits.iter().enumerate() {
+            let to = (*start as i64 + offset) as u64;
+            offset += bytes.len() as i64 - (end - start) as i64;
+            let next = edits.get(i + 1).map_or(length, |edit| edit.0);
+            moves.push((to, bytes, *end, next, offset));
+        }
+
+        // every edit grows or every edit shrinks, so the bytes are moved
+        // from the end when growing, and from the start when shrinking.
+        if delta > 0 {
+            for (to, bytes, from, next, offset) in moves.into_iter().rev() {
+                shift(inner, from, next, offset)?;
+                parse_io(inner.seek(SeekFrom::Start(to)))?;
+                parse_io(inner.write_all(bytes))?;
+            }
+        } else {
+            for (to, bytes, from, next, offset) in moves {
+                parse_io(inner.seek(SeekFrom::Start(to)))?;
+                parse_io(inner.write_all(bytes))?;
+                shift(inner, from, next, offset)?;
+            }
+        }
+
+        if delta < 0 {
+            parse_io(inner.set_len((length as i64 + delta) as u64))?;
+        }
+
+        // pointers issued before the edit no longer refer to the same data.
+        #[cfg(feature="partial_state")]
+        {
+            self.reader.ident = self.reader.ident.wrapping_add(1);
+        }
+
+        self.top()
+    }
+
+    /// Move the reader to the start of the data.
+    fn top(&mut self) -> TychoStatus {
+        self.reader.rewind()
+    }
+}
+
+fn encode(element: &Element) -> TychoResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    write_element(&mut bytes, element)?;
+    Ok(bytes)
+}
+
+/// Move the bytes between two positions by an offset, a chunk at a time.
+///
+/// Chunks are moved from the end when moving forwards, and from the start when moving backwards,
+/// so no byte is overwritten before it is moved.
+fn shift<R: Read + Write + Seek>(inner: &mut R, start: u64, end: u64, offset: i64) -> TychoStatus {
+    if offset == 0 || start >= end {
+        return Ok(());
+    }
+
+    let mut buffer = vec![0u8; BUFFER_SIZE.min((end - start) as usize)];
+    let mut moved = 0;
+
+    while moved < end - start {
+        let len = (buffer.len() as u64).min(end - start - moved);
+        let pos = if offset > 0 { end - moved - len } else { start + moved };
+        let chunk = &mut buffer[..len as usize];
+
+        parse_io(inner.seek(SeekFrom::Start(pos)))?;
+        parse_io(inner.read_exact(chunk))?;
+        parse_io(inner.seek(SeekFrom::Start((pos as i64 + offset) as u64)))?;
+        parse_io(inner.write_all(chunk))?;
+
+        moved += len;
+    }
+
+    Ok(())
+}
@@ -1,14 +1,20 @@
 use std::io::{Read, Seek};
 
-use crate::error::TychoResult;
+use crate::error::{PathSegment, TychoResult, TychoStatus};
 use crate::partial::reader::PartialReader;
 use crate::partial::types::{PartialStruct, PartialList, PartialMap, PartialArray, PartialCompression};
-use crate::read::element::read_element_ident;
+use crate::read::element::{parse_codec, parse_element_ident, read_compressed, read_element_ident};
+use crate::read::func::read_byte;
 use crate::read::length::read_length;
-use crate::read::string::read_tstring;
-use crate::read::value::{read_value, read_value_ident};
+use crate::read::string::{read_char, read_tstring};
+use crate::read::value::{read_value, read_value_ident, value_width};
 use crate::types::ident::{ElementIdent, ValueIdent};
-use crate::Value;
+use crate::{Element, ElementMap, Value};
+
+#[cfg(feature="serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature="serde")]
+use crate::from_element;
 
 #[derive(Debug, Clone)]
 /// A partial, unread, element with a pointer.
@@ -24,38 +30,112 @@ pub enum PartialElement {
     Compression(PartialCompression),
 }
 
+impl PartialElement {
+    /// Read the whole of the element, including the contents of any containers within it.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element, marshall_vec, Value};
+    /// use tycho::partial::PartialReader;
+    ///
+    /// let element = Element::List(vec![Element::Unit, Element::Value(Value::Boolean(true))]);
+    /// let mut reader = PartialReader::from_vec(marshall_vec(element.clone()).unwrap());
+    ///
+    /// let partial = reader.element().unwrap();
+    /// assert_eq!(partial.read_full(&mut reader).unwrap(), element);
+    /// ```
+    pub fn read_full<R: Read + Seek>(&self, reader: &mut PartialReader<R>) -> TychoResult<Element> {
+        read_full_element(reader, self)
+    }
+
+    #[cfg(feature="serde")]
+    /// Read the whole of the element, and deserialize it into a type.
+    ///
+    /// (requires `serde` feature)
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{marshall_vec, to_element};
+    /// use tycho::partial::{PartialElement, PartialReader};
+    ///
+    /// let bytes = marshall_vec(to_element(vec![vec![1u8, 2], vec![3]]).unwrap()).unwrap();
+    /// let mut reader = PartialReader::from_vec(bytes);
+    ///
+    /// if let PartialElement::List(list) = reader.element().unwrap() {
+    ///     let second = list.nth(&mut reader, 1).unwrap().unwrap();
+    ///     assert_eq!(second.deserialize::<Vec<u8>, _>(&mut reader).unwrap(), vec![3]);
+    /// }
+    /// ```
+    pub fn deserialize<D: DeserializeOwned, R: Read + Seek>(&self, reader: &mut PartialReader<R>) -> TychoResult<D> {
+        from_element(self.read_full(reader)?)
+    }
+}
+
+macro_rules! partial_element_from {
+    ($type: ty, $variant: ident) => {
+        impl From<$type> for PartialElement {
+            fn from(x: $type) -> Self {
+                PartialElement::$variant(x)
+            }
+        }
+    };
+}
+
+partial_element_from!(PartialStruct, Struct);
+partial_element_from!(PartialList, List);
+partial_element_from!(PartialMap, Map);
+partial_element_from!(PartialArray, Array);
+partial_element_from!(PartialCompression, Compression);
+
 pub fn read_partial_element<R: Read + Seek>(reader: &mut PartialReader<R>) -> TychoResult<PartialElement> {
-    let ident = read_element_ident(reader)?;
+    let start = reader.pointer;
+    read_partial_element_at(reader).map_err(|e| e.located(start))
+}
+
+fn read_partial_element_at<R: Read + Seek>(reader: &mut PartialReader<R>) -> TychoResult<PartialElement> {
+    let byte = read_byte(reader)?;
+    let ident = parse_element_ident(byte)?;
 
+    reader.limiter.enter()?;
+    let element = read_partial_element_body(reader, ident, byte);
+    reader.limiter.exit();
+
+    element
+}
+
+fn read_partial_element_body<R: Read + Seek>(reader: &mut PartialReader<R>, ident: ElementIdent, byte: u8) -> TychoResult<PartialElement> {
     match ident {
         ElementIdent::Unit => Ok(PartialElement::Unit),
         ElementIdent::Value => {
             let prefix = read_value_ident(reader)?;
-            let value = read_value(reader, &prefix)?;
+            let value = reader.limited(|reader, limiter| read_value(reader, &prefix, limiter))?;
             Ok(PartialElement::Value(value))
         },
 
         ElementIdent::None => Ok(PartialElement::Option(None)),
-        ElementIdent::Some => read_partial_element(reader),
+        ElementIdent::Some => Ok(PartialElement::Option(Some(Box::new(read_partial_element(reader)?)))),
 
         ElementIdent::Variant => {
-            let name = read_tstring(reader)?;
-            let value =read_partial_element(reader)?;
-
-            Ok(PartialElement::Variant(name, Box::new(value)))
+            let name = reader.limited(read_tstring)?;
+            match read_partial_element(reader) {
+                Ok(value) => Ok(PartialElement::Variant(name, Box::new(value))),
+                Err(e) => Err(e.within(PathSegment::Variant(name)))
+            }
         }
 
         ElementIdent::Struct => {
             let size = read_length(reader)? as u64;
+            reader.limiter.container(size as usize)?;
             let pos = reader.pointer.clone();
-            reader.jump(&size)?;
+            reader.jump(&(pos + size))?;
             Ok(PartialElement::Struct(PartialStruct::new(reader.pointer(pos, size), 0, ())))
         },
 
         ElementIdent::List => {
             let size = read_length(reader)? as u64;
+            reader.limiter.container(size as usize)?;
             let pos = reader.pointer.clone();
-            reader.jump(&size)?;
+            reader.jump(&(pos + size))?;
             Ok(PartialElement::List(PartialList::new(reader.pointer(pos, size), 0, ())))
         },
 
@@ -67,8 +147,9 @@ pub fn read_partial_element<R: Read + Seek>(reader: &mut PartialReader<R>) -> Ty
             }
 
             let size = read_length(reader)? as u64;
+            reader.limiter.container(size as usize)?;
             let pos = reader.pointer.clone();
-            reader.jump(&size)?;
+            reader.jump(&(pos + size))?;
             Ok(PartialElement::Array(PartialArray::new(reader.pointer(pos, size), 0, array_type)))
         },
 
@@ -80,16 +161,126 @@ pub fn read_partial_element<R: Read + Seek>(reader: &mut PartialReader<R>) -> Ty
             }
 
             let size = read_length(reader)? as u64;
+            reader.limiter.container(size as usize)?;
             let pos = reader.pointer.clone();
-            reader.jump(&size)?;
+            reader.jump(&(pos + size))?;
             Ok(PartialElement::Map(PartialMap::new(reader.pointer(pos, size), 0, key_type)))
         },
 
         ElementIdent::Compression => {
+            let codec = parse_codec(byte)?;
             let size = read_length(reader)? as u64;
+            reader.limiter.container(size as usize)?;
             let pos = reader.pointer.clone();
-            reader.jump(&size)?;
-            Ok(PartialElement::Compression(PartialCompression::new(reader.pointer(pos, size))))
+            reader.jump(&(pos + size))?;
+            Ok(PartialElement::Compression(PartialCompression::new(reader.pointer(pos, size), codec)))
+        }
+    }
+}
+
+/// Read the whole of a partial element, and the containers within it, into an element.
+pub(crate) fn read_full_element<R: Read + Seek>(reader: &mut PartialReader<R>, element: &PartialElement) -> TychoResult<Element> {
+    Ok(match element {
+        PartialElement::Unit => Element::Unit,
+        PartialElement::Value(value) => Element::Value(value.clone()),
+        PartialElement::Option(None) => Element::Option(None),
+        PartialElement::Option(Some(x)) => Element::Option(Some(Box::new(read_full_element(reader, x)?))),
+        PartialElement::Variant(name, x) => Element::Variant(name.clone(), Box::new(read_full_element(reader, x)?)),
+        PartialElement::Struct(s) => {
+            let mut items = ElementMap::new();
+            let mut s = s.clone();
+            s.top();
+            while let Some((key, value)) = s.next(reader)? {
+                let value = read_full_element(reader, &value).map_err(|e| e.within(PathSegment::Field(key.clone())))?;
+                items.insert(key, value);
+            }
+            Element::Struct(items)
+        }
+        PartialElement::List(l) => {
+            let mut items = Vec::new();
+            let mut l = l.clone();
+            l.top();
+            while let Some(item) = l.next(reader)? {
+                let index = items.len();
+                items.push(read_full_element(reader, &item).map_err(|e| e.within(PathSegment::Index(index)))?);
+            }
+            Element::List(items)
+        }
+        PartialElement::Map(m) => {
+            let mut items = ElementMap::new();
+            let mut m = m.clone();
+            m.top();
+            while let Some((key, value)) = m.next(reader)? {
+                let value = read_full_element(reader, &value).map_err(|e| e.within(PathSegment::Key(key.clone())))?;
+                items.insert(key, value);
+            }
+            Element::Map(m.param.clone(), items)
+        }
+        PartialElement::Array(a) => {
+            let mut a = a.clone();
+            a.top();
+            Element::Array(a.param.clone(), a.collect(reader)?)
+        }
+        PartialElement::Compression(c) => {
+            let bytes = c.clone().bytes(reader)?;
+            reader.limited(|_, limiter| read_compressed(bytes, c.codec, c.pointer.pos, limiter))?
+        }
+    })
+}
+
+/// Move past an element, reading only its prefixes and sizes.
+pub(crate) fn skip_partial_element<R: Read + Seek>(reader: &mut PartialReader<R>) -> TychoStatus {
+    let start = reader.pointer;
+    skip_partial_element_at(reader).map_err(|e| e.located(start))
+}
+
+fn skip_partial_element_at<R: Read + Seek>(reader: &mut PartialReader<R>) -> TychoStatus {
+    let ident = read_element_ident(reader)?;
+
+    reader.limiter.enter()?;
+    let result = skip_partial_element_body(reader, ident);
+    reader.limiter.exit();
+
+    result
+}
+
+fn skip_partial_element_body<R: Read + Seek>(reader: &mut PartialReader<R>, ident: ElementIdent) -> TychoStatus {
+    match ident {
+        ElementIdent::Unit | ElementIdent::None => (),
+        ElementIdent::Value => {
+            let prefix = read_value_ident(reader)?;
+            skip_partial_value(reader, &prefix)?;
+        }
+        ElementIdent::Some => skip_partial_element(reader)?,
+        ElementIdent::Variant => {
+            reader.limited(read_tstring)?;
+            skip_partial_element(reader)?;
+        }
+        ElementIdent::Struct | ElementIdent::List | ElementIdent::Compression => skip_sized(reader)?,
+        ElementIdent::Array | ElementIdent::Map => {
+            if read_value_ident(reader)? != ValueIdent::Null {
+                skip_sized(reader)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Move past a value, jumping over its bytes where its size is known.
+pub(crate) fn skip_partial_value<R: Read + Seek>(reader: &mut PartialReader<R>, ident: &ValueIdent) -> TychoStatus {
+    match ident {
+        ValueIdent::String | ValueIdent::Bytes => skip_sized(reader),
+        ValueIdent::Char => read_char(reader).map(|_| ()),
+        _ => {
+            let pos = reader.pointer + value_width(ident).unwrap_or_default();
+            reader.jump(&pos)
         }
     }
-}
\ No newline at end of file
+}
+
+fn skip_sized<R: Read + Seek>(reader: &mut PartialReader<R>) -> TychoStatus {
+    let size = read_length(reader)? as u64;
+    let pos = reader.pointer + size;
+    reader.jump(&pos)
+}
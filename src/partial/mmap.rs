@@ -0,0 +1,39 @@
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::error::{parse_io, TychoResult};
+use crate::partial::PartialReader;
+
+/// A partial reader over a memory-mapped file.
+///
+/// Reads and jumps are served from the mapping without system calls,
+/// and strings and bytes can be borrowed from it with `view` and `slice`.
+///
+/// (requires `mmap` feature)
+pub type PartialMmapReader = PartialReader<Cursor<Mmap>>;
+
+impl PartialReader<Cursor<Mmap>> {
+    /// Open a file and map it into memory for reading.
+    ///
+    /// The file must not be modified or truncated by another process while it is mapped,
+    /// doing so may change data that has already been read or cause the process to crash.
+    ///
+    /// ```no_run
+    /// use tycho::partial::PartialReader;
+    ///
+    /// let mut reader = PartialReader::mmap("path/to/file").unwrap();
+    /// let root = reader.element().unwrap();
+    /// ```
+    ///
+    /// (requires `mmap` feature)
+    pub fn mmap<P: AsRef<Path>>(path: P) -> TychoResult<Self> {
+        let file = parse_io(File::open(path))?;
+
+        // the mapping is read only, and the caller is documented to not modify the file while mapped.
+        let map = parse_io(unsafe { Mmap::map(&file) })?;
+        Ok(PartialReader::from(Cursor::new(map)))
+    }
+}
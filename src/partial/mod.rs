@@ -71,6 +71,14 @@
 //! let buf = BufReader::new(file);
 //! let mut reader = PartialReader::from(buf);
 //! ```
+//! ```no_run
+//! // From a memory-mapped file (requires `mmap` feature)
+//! # #[cfg(feature = "mmap")] {
+//! use tycho::partial::PartialReader;
+//!
+//! let mut reader = PartialReader::mmap("path/to/file").unwrap();
+//! # }
+//! ```
 //!
 //! ### Pointers
 //! Pointers map to a set of bytes within a reader.
@@ -81,6 +89,10 @@
 //! While the user may not interact with a pointer directly it may help with understanding how
 //! tycho paritally parses bytes.
 //!
+//! Pointers can also be created with `PartialPointer::new` from a position and size,
+//! and read with `reader.element_at(&pointer)`,
+//! allowing the location of an element to be kept in an external index.
+//!
 //! ### Element
 //! ParitalElements contain a proccessed value or a unproccess container with its respective pointer.
 //!
@@ -99,7 +111,8 @@
 //! Container elements allow you to iterate over thier children one at a time.
 //! See below for more infomation
 //!
-//! Compression elements allow you to get the bytes or another partial element upon request.
+//! Compression elements allow you to get their bytes upon request,
+//! or decompress them into an element or a new partial reader.
 //!
 //! ### Containers
 //! All container types (Struct, List, Map, Array) share a `PartialContainer` which takes a generic.
@@ -112,6 +125,10 @@
 #[cfg(feature = "async_tokio")]
 pub use async_::reader::PartialAsyncReader;
 
+#[cfg(feature = "mmap")]
+pub use mmap::PartialMmapReader;
+
+pub use editor::{PartialEditor, SetLen};
 pub use element::PartialElement;
 pub use reader::{PartialPointer, PartialReader};
 //pub use types::{PartialArray, PartialList, PartialMap, PartialStruct};
@@ -121,6 +138,10 @@ pub mod container;
 pub(crate) mod reader;
 pub(crate) mod element;
 pub mod types;
+pub mod editor;
+
+#[cfg(feature = "mmap")]
+pub(crate) mod mmap;
 
 //pub(crate) mod test;
 
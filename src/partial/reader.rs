@@ -1,10 +1,16 @@
-use std::io::{Cursor, Read, Result as IoResult, Seek, SeekFrom};
+use std::convert::TryFrom;
+use std::io::{BufRead, Cursor, Read, Result as IoResult, Seek, SeekFrom};
 
 #[cfg(feature="partial_state")]
 use rand;
 
-use crate::error::{parse_io, TychoResult, TychoStatus};
+use crate::error::{parse_io, TychoError, TychoResult, TychoStatus};
 use crate::partial::element::{PartialElement, read_partial_element};
+use crate::read::options::{Limiter, ReadOptions};
+use crate::view::ElementRef;
+
+#[cfg(feature="serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// A reader with an inner pointer and state management for reading tycho partially.
 ///
@@ -41,9 +47,34 @@ use crate::partial::element::{PartialElement, read_partial_element};
 ///
 /// let element = reader.element().unwrap();
 /// ```
+///
+/// ### Buffering
+/// Bytes are read ahead into a buffer, so prefixes and strings are scanned in bulk,
+/// and jumps to a position held within the buffer do not seek the underlying reader.
+/// The buffer is kept after jumping away from it, so returning to a container is cheap.
+///
+/// ### Limits
+/// When reading untrusted input, limits can be set with `with_options`.
+/// ```
+/// use tycho::partial::PartialReader;
+/// use tycho::ReadOptions;
+///
+/// let options = ReadOptions { max_string_len: Some(1024), ..ReadOptions::default() };
+/// let mut reader = PartialReader::from_vec(vec![ 0 ]).with_options(options);
+/// ```
 pub struct PartialReader<R> {
     pub(crate) reader: R,
     pub(crate) pointer: u64,
+    pub(crate) limiter: Limiter,
+
+    /// Bytes read from the underlying reader, allocated on the first read.
+    pub(crate) buffer: Vec<u8>,
+    /// The position of the first byte within the buffer.
+    pub(crate) start: u64,
+    /// The amount of bytes within the buffer which were read from the underlying reader.
+    pub(crate) filled: usize,
+    /// The position of the underlying reader, which may differ from the pointer.
+    pub(crate) physical: u64,
 
     #[cfg(feature="partial_state")]
     pub(crate) ident: u16,
@@ -51,13 +82,7 @@ pub struct PartialReader<R> {
 
 impl PartialReader<Cursor<Vec<u8>>> {
     pub fn from_vec(reader: Vec<u8>) -> Self {
-        PartialReader {
-            reader: Cursor::new(reader),
-            pointer: 0,
-
-            #[cfg(feature="partial_state")]
-            ident: rand::random()
-        }
+        PartialReader::from(Cursor::new(reader))
     }
 }
 
@@ -66,78 +91,299 @@ impl<R: Read + Seek> PartialReader<R> {
 
     /// Jump to a pointer location.
     pub fn jump(&mut self, to: &u64) -> TychoStatus {
-        parse_io(self.reader.seek(SeekFrom::Current((*to as i64) - (self.pointer as i64))))?;
+        if !self.buffered(*to) && self.physical != *to {
+            parse_io(self.reader.seek(SeekFrom::Current((*to as i64) - (self.physical as i64))))?;
+            self.physical = *to;
+        }
         self.pointer = *to;
         Ok(())
     }
 
+    /// Move the underlying reader to the pointer, if it has been left elsewhere.
+    fn seek_pointer(&mut self) -> IoResult<()> {
+        if self.physical != self.pointer {
+            self.reader.seek(SeekFrom::Current((self.pointer as i64) - (self.physical as i64)))?;
+            self.physical = self.pointer;
+        }
+        Ok(())
+    }
+
+    /// Move the reader and the underlying reader to the start of the data, emptying the buffer.
+    pub(crate) fn rewind(&mut self) -> TychoStatus {
+        parse_io(self.reader.seek(SeekFrom::Start(0)))?;
+        self.filled = 0;
+        self.physical = 0;
+        self.pointer = 0;
+        Ok(())
+    }
+
     /// Get the next element of the reader.
     pub fn element(&mut self) -> TychoResult<PartialElement> {
         read_partial_element(self)
 
     }
 
-}
+    /// Read the element at a pointer, such as one stored within an external index.
+    ///
+    /// The pointer must start at the element and cover the whole of it,
+    /// otherwise `TychoError::PointerNotFound` is returned.
+    /// The reader is returned to its previous position afterwards.
+    ///
+    /// ```
+    /// use tycho::{Element, marshall_vec, Value};
+    /// use tycho::partial::{PartialElement, PartialPointer, PartialReader};
+    ///
+    /// let mut bytes = marshall_vec(Element::Unit).unwrap();
+    /// bytes.extend(marshall_vec(Element::Value(Value::Boolean(true))).unwrap());
+    ///
+    /// let mut reader = PartialReader::from_vec(bytes);
+    /// let element = reader.element_at(&PartialPointer::new(1, 3)).unwrap();
+    ///
+    /// assert!(matches!(element, PartialElement::Value(Value::Boolean(true))));
+    /// ```
+    pub fn element_at(&mut self, pointer: &PartialPointer) -> TychoResult<PartialElement> {
+        self.check_pointer(pointer)?;
+        let previous = self.pointer;
+        self.jump(&pointer.pos)?;
 
-/*
-impl<R: Read + Seek + Write> PartialReader<R> {
-    pub fn snip(&mut self, pointer: PartialPointer) -> TychoResult<Vec<u8>> {
-        #[cfg(feature="partial_state")]
-        if self.pointer.ident != reader.ident {
-            return Err(TychoError::OutdatedPointer)
-        }
+        let result = read_partial_element(self).and_then(|element| self.check_end(pointer, element));
+        self.jump(&previous)?;
+        result
+    }
 
-        self.jump(&pointer.pos)?;
+}
 
-        self.reader.rem2
+// in-memory implementation
+impl<T: AsRef<[u8]>> PartialReader<Cursor<T>> {
+    /// Borrow the bytes of a pointer from an in-memory reader, such as a memory-mapped file.
+    ///
+    /// Returns `TychoError::PointerNotFound` if the pointer is outside of the buffer.
+    ///
+    /// ```
+    /// use tycho::partial::{PartialPointer, PartialReader};
+    ///
+    /// let reader = PartialReader::from_vec(vec![ 1, 2, 3, 4 ]);
+    /// assert_eq!(reader.slice(&PartialPointer::new(1, 2)).unwrap(), &[ 2, 3 ]);
+    /// ```
+    pub fn slice(&self, pointer: &PartialPointer) -> TychoResult<&[u8]> {
+        self.check_pointer(pointer)?;
+        let bytes = self.reader.get_ref().as_ref();
+        usize::try_from(pointer.pos).ok()
+            .zip(usize::try_from(pointer.end()).ok())
+            .and_then(|(start, end)| bytes.get(start..end))
+            .ok_or_else(|| TychoError::PointerNotFound.located(pointer.pos))
     }
-}*/
+
+    /// View the element at a pointer, borrowing its strings and bytes from the buffer.
+    ///
+    /// The pointer must start at the element, as with `element_at`,
+    /// and the element is only read within the bounds of the pointer.
+    ///
+    /// ```
+    /// use tycho::{Element, marshall_vec, Value};
+    /// use tycho::partial::{PartialPointer, PartialReader};
+    /// use tycho::view::{ElementRef, ValueRef};
+    ///
+    /// let bytes = marshall_vec(Element::Value(Value::String("foo".to_string()))).unwrap();
+    /// let size = bytes.len() as u64;
+    /// let reader = PartialReader::from_vec(bytes);
+    ///
+    /// let element = reader.view(&PartialPointer::new(0, size)).unwrap();
+    /// assert_eq!(element, ElementRef::Value(ValueRef::String("foo")));
+    /// ```
+    pub fn view(&self, pointer: &PartialPointer) -> TychoResult<ElementRef<'_>> {
+        ElementRef::with_options(self.slice(pointer)?, self.limiter.options).map_err(|e| e.located(pointer.pos))
+    }
+}
 
 impl<R> PartialReader<R> {
     pub fn from(reader: R) -> PartialReader<R> {
         PartialReader {
             reader,
             pointer: 0,
+            limiter: Limiter::default(),
+
+            buffer: Vec::new(),
+            start: 0,
+            filled: 0,
+            physical: 0,
 
             #[cfg(feature = "partial_state")]
             ident: rand::random()
         }
     }
 
+    /// Get the underlying reader.
+    ///
+    /// Bytes read ahead into the buffer are discarded,
+    /// so the underlying reader may not be positioned at `position()`.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Get the current position of the reader.
+    ///
+    /// Taken before and after reading an element with `element`,
+    /// this gives the bounds of a pointer which can later be read with `element_at`.
+    pub fn position(&self) -> u64 {
+        self.pointer
+    }
+
+    /// Set the limits used while reading.
+    ///
+    /// The total size limit applies over the life of the reader,
+    /// counting every container, string and bytes value read.
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.limiter = Limiter::new(options);
+        self
+    }
+
+    /// Run a read function with the reader's limiter.
+    pub(crate) fn limited<T, F: FnOnce(&mut Self, &mut Limiter) -> TychoResult<T>>(&mut self, f: F) -> TychoResult<T> {
+        let mut limiter = self.limiter;
+        let result = f(self, &mut limiter);
+        self.limiter = limiter;
+        result
+    }
+
+    /// Check if the byte at a position is held within the buffer.
+    pub(crate) fn buffered(&self, position: u64) -> bool {
+        self.start <= position && position < self.start + self.filled as u64
+    }
+
     pub(crate) fn pointer(&self, pos: u64, size: u64) -> PartialPointer {
         PartialPointer {
             pos,
             size,
             #[cfg(feature="partial_state")]
-            ident: self.ident.clone()
+            ident: Some(self.ident)
         }
     }
 
     pub(crate) fn empty_pointer(&self) -> PartialPointer {
-        PartialPointer {
-            pos: 0,
-            size: 0,
-            #[cfg(feature="partial_state")]
-            ident: self.ident.clone()
+        self.pointer(0, 0)
+    }
+
+    /// Check that a pointer has not been outdated by a change to the reader.
+    ///
+    /// Pointers created outside of a reader are not tied to one, and are always accepted.
+    #[allow(unused_variables)]
+    pub(crate) fn check_pointer(&self, pointer: &PartialPointer) -> TychoStatus {
+        #[cfg(feature="partial_state")]
+        if matches!(pointer.ident, Some(ident) if ident != self.ident) {
+            return Err(TychoError::OutdatedPointer)
+        }
+        Ok(())
+    }
+
+    /// Check that an element read at a pointer ended at the end of the pointer.
+    pub(crate) fn check_end(&self, pointer: &PartialPointer, element: PartialElement) -> TychoResult<PartialElement> {
+        if self.pointer == pointer.end() {
+            Ok(element)
+        } else {
+            Err(TychoError::PointerNotFound.located(pointer.pos))
         }
     }
 }
 
+/// The amount of bytes read ahead into the buffer of a partial reader at once.
+pub(crate) const BUFFER_SIZE: usize = 8 * 1024;
+
 impl<R: Read + Seek> Read for PartialReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
-        let size = self.reader.read(buf)?;
-        self.pointer += size.clone() as u64;
+        // large reads skip the buffer if they do not start within it.
+        if !self.buffered(self.pointer) && buf.len() >= BUFFER_SIZE {
+            self.seek_pointer()?;
+            let size = self.reader.read(buf)?;
+            self.physical += size as u64;
+            self.pointer += size as u64;
+            return Ok(size);
+        }
+
+        let available = self.fill_buf()?;
+        let size = available.len().min(buf.len());
+        buf[..size].copy_from_slice(&available[..size]);
+        self.consume(size);
         Ok(size)
     }
 }
 
+impl<R: Read + Seek> BufRead for PartialReader<R> {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        if !self.buffered(self.pointer) {
+            self.seek_pointer()?;
+            if self.buffer.is_empty() {
+                self.buffer = vec![0; BUFFER_SIZE];
+            }
+            self.start = self.pointer;
+            self.filled = 0;
+            self.filled = self.reader.read(&mut self.buffer)?;
+            self.physical += self.filled as u64;
+        }
+        Ok(&self.buffer[(self.pointer - self.start) as usize..self.filled])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pointer = (self.pointer + amount as u64).min(self.start + self.filled as u64);
+    }
+}
+
 
 #[derive(Debug, Clone)]
 /// A pointer, referring to a block of data in a partial reader.
+///
+/// Pointers given out by containers refer to the contents of the container,
+/// while pointers read with `PartialReader::element_at` refer to a whole element.
+///
+/// With the `serde` feature, pointers can be stored as their position and size,
+/// and read again later from the same data.
 pub struct PartialPointer {
     pub(crate) pos: u64,
     pub(crate) size: u64,
 
+    /// The reader state the pointer was given out with, if any.
     #[cfg(feature="partial_state")]
-    pub(crate) ident: u16,
+    pub(crate) ident: Option<u16>,
+}
+
+impl PartialPointer {
+    /// Create a pointer from a position and size within a reader.
+    pub fn new(position: u64, size: u64) -> Self {
+        PartialPointer {
+            pos: position,
+            size,
+            #[cfg(feature="partial_state")]
+            ident: None
+        }
+    }
+
+    /// Get the position of the start of the data.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Get the length of the data.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get the position after the end of the data.
+    pub fn end(&self) -> u64 {
+        self.pos + self.size
+    }
+}
+
+#[cfg(feature="serde")]
+impl Serialize for PartialPointer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.pos, self.size).serialize(serializer)
+    }
+}
+
+#[cfg(feature="serde")]
+impl<'de> Deserialize<'de> for PartialPointer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (position, size) = <(u64, u64)>::deserialize(deserializer)?;
+        Ok(PartialPointer::new(position, size))
+    }
 }
\ No newline at end of file
@@ -1,17 +1,30 @@
 //! Container types returned during partial parsing.
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, Write};
 
-use crate::error::TychoResult;
+use crate::error::{PathSegment, TychoError, TychoResult, TychoStatus};
 use crate::partial::container::{PartialContainer, PartialContainerType};
-use crate::partial::element::{PartialElement, read_partial_element};
+use crate::partial::element::{PartialElement, read_partial_element, skip_partial_element, skip_partial_value};
 use crate::partial::reader::PartialReader;
 use crate::read::string::read_tstring;
-use crate::read::value::read_value;
-use crate::types::ident::ValueIdent;
+use crate::read::value::{read_value, value_width};
+use crate::types::ident::{Codec, ValueIdent};
 use crate::Value;
+use crate::into::ident::Ident;
 use crate::partial::PartialPointer;
 use crate::read::func::read_bytes;
+use crate::write::func::write_byte;
+use crate::write::length::write_length;
+use crate::write::value::write_value_ident;
+
+#[cfg(feature="compression")]
+use std::io::Cursor;
+#[cfg(feature="compression")]
+use crate::Element;
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
+#[cfg(feature="compression")]
+use crate::read::element::read_decompressed;
 
 #[derive(Debug, Clone)]
 /// The inner implementation structure for a struct.
@@ -21,14 +34,54 @@ impl PartialContainerType for PartialStructInner {
     type ItemParam = ();
 
     fn read_item<R: Read + Seek>(reader: &mut PartialReader<R>, _: &()) -> TychoResult<Self::ItemType> {
-        let key = read_tstring(reader)?;
-        let value = read_partial_element(reader)?;
-        Ok((key, value))
+        let key = reader.limited(read_tstring)?;
+        match read_partial_element(reader) {
+            Ok(value) => Ok((key, value)),
+            Err(e) => Err(e.within(PathSegment::Field(key)))
+        }
+    }
+
+    fn write_prefix<W: Write>(writer: &mut W, _: &(), size: u64) -> TychoStatus {
+        write_byte(writer, &0x05)?;
+        write_length(writer, size as usize)
     }
 }
 /// A unprocessed struct object.
 pub type PartialStruct = PartialContainer<PartialStructInner>;
 
+impl PartialStruct {
+    /// Get the value of a field, skipping over the values of other fields without reading them.
+    ///
+    /// The head of the struct is left unchanged.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element, ElementMap, marshall_vec, Value};
+    /// use tycho::partial::{PartialElement, PartialReader};
+    ///
+    /// let mut data = ElementMap::new();
+    /// data.insert("foo".to_string(), Element::Value(Value::Boolean(true)));
+    /// let mut reader = PartialReader::from_vec(marshall_vec(Element::Struct(data)).unwrap());
+    ///
+    /// if let PartialElement::Struct(s) = reader.element().unwrap() {
+    ///     let value = s.get(&mut reader, "foo").unwrap();
+    ///     assert!(matches!(value, Some(PartialElement::Value(Value::Boolean(true)))));
+    /// }
+    /// ```
+    pub fn get<R: Read + Seek>(&self, reader: &mut PartialReader<R>, field: &str) -> TychoResult<Option<PartialElement>> {
+        self.find_item(reader, |reader, _| {
+            let key = reader.limited(read_tstring)?;
+            if key != field {
+                return skip_partial_element(reader).map(|_| None);
+            }
+            match read_partial_element(reader) {
+                Ok(value) => Ok(Some(value)),
+                Err(e) => Err(e.within(PathSegment::Field(key)))
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 /// The inner implementation structure for a list.
 pub struct PartialListInner;
@@ -39,10 +92,34 @@ impl PartialContainerType for PartialListInner {
     fn read_item<R: Read + Seek>(reader: &mut PartialReader<R>, _: &()) -> TychoResult<Self::ItemType> {
         read_partial_element(reader)
     }
+
+    fn item_error(error: TychoError, index: usize) -> TychoError {
+        error.within(PathSegment::Index(index))
+    }
+
+    fn write_prefix<W: Write>(writer: &mut W, _: &(), size: u64) -> TychoStatus {
+        write_byte(writer, &0x06)?;
+        write_length(writer, size as usize)
+    }
 }
 /// A unprocessed list object.
 pub type PartialList = PartialContainer<PartialListInner>;
 
+impl PartialList {
+    /// Get the item at an index, skipping over the items before it without reading them.
+    ///
+    /// The head of the list is left unchanged.
+    pub fn nth<R: Read + Seek>(&self, reader: &mut PartialReader<R>, n: usize) -> TychoResult<Option<PartialElement>> {
+        self.find_item(reader, |reader, index| {
+            if index == n {
+                read_partial_element(reader).map(Some)
+            } else {
+                skip_partial_element(reader).map(|_| None)
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 /// The inner implementation structure for a map.
 pub struct PartialMapInner;
@@ -51,15 +128,48 @@ impl PartialContainerType for PartialMapInner {
     type ItemParam = ValueIdent;
 
     fn read_item<R: Read + Seek>(reader: &mut PartialReader<R>, params: &ValueIdent) -> TychoResult<Self::ItemType> {
-        let key = read_value(reader, &params)?;
-        let value = read_partial_element(reader)?;
-        Ok((key, value))
+        let key = reader.limited(|reader, limiter| read_value(reader, params, limiter))?;
+        match read_partial_element(reader) {
+            Ok(value) => Ok((key, value)),
+            Err(e) => Err(e.within(PathSegment::Key(key)))
+        }
+    }
+
+    fn write_prefix<W: Write>(writer: &mut W, params: &ValueIdent, size: u64) -> TychoStatus {
+        write_byte(writer, &0x08)?;
+        write_value_ident(writer, params)?;
+        if params == &ValueIdent::Null {
+            return Ok(());
+        }
+        write_length(writer, size as usize)
     }
 }
 
 /// A unprocessed map object.
 pub type PartialMap = PartialContainer<PartialMapInner>;
 
+impl PartialMap {
+    /// Get the value of a key, skipping over the values of other keys without reading them.
+    ///
+    /// The head of the map is left unchanged.
+    pub fn get<R: Read + Seek>(&self, reader: &mut PartialReader<R>, key: &Value) -> TychoResult<Option<PartialElement>> {
+        if key.ident() != self.param {
+            return Ok(None);
+        }
+
+        self.find_item(reader, |reader, _| {
+            let item_key = reader.limited(|reader, limiter| read_value(reader, &self.param, limiter))?;
+            if &item_key != key {
+                return skip_partial_element(reader).map(|_| None);
+            }
+            match read_partial_element(reader) {
+                Ok(value) => Ok(Some(value)),
+                Err(e) => Err(e.within(PathSegment::Key(item_key)))
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 /// The inner implementation structure for a array.
 pub struct PartialArrayInner;
@@ -68,22 +178,77 @@ impl PartialContainerType for PartialArrayInner {
     type ItemParam = ValueIdent;
 
     fn read_item<R: Read + Seek>(reader: &mut PartialReader<R>, params: &ValueIdent) -> TychoResult<Self::ItemType> {
-        let item = read_value(reader, &params)?;
+        let item = reader.limited(|reader, limiter| read_value(reader, params, limiter))?;
         Ok(item)
     }
+
+    fn item_error(error: TychoError, index: usize) -> TychoError {
+        error.within(PathSegment::Index(index))
+    }
+
+    fn write_prefix<W: Write>(writer: &mut W, params: &ValueIdent, size: u64) -> TychoStatus {
+        write_byte(writer, &0x07)?;
+        write_value_ident(writer, params)?;
+        if params == &ValueIdent::Null {
+            return Ok(());
+        }
+        write_length(writer, size as usize)
+    }
 }
 /// A unprocessed array object.
 pub type PartialArray = PartialContainer<PartialArrayInner>;
 
+impl PartialArray {
+    /// Get the value at an index.
+    ///
+    /// Values with a fixed width, such as numbers, are found directly from the index,
+    /// otherwise the values before it are skipped over without reading them.
+    /// The head of the array is left unchanged.
+    pub fn nth<R: Read + Seek>(&self, reader: &mut PartialReader<R>, n: usize) -> TychoResult<Option<Value>> {
+        let width = match value_width(&self.param) {
+            Some(width) if width > 0 => width,
+            _ => return self.find_item(reader, |reader, index| {
+                if index == n {
+                    reader.limited(|reader, limiter| read_value(reader, &self.param, limiter)).map(Some)
+                } else {
+                    skip_partial_value(reader, &self.param).map(|_| None)
+                }
+            })
+        };
+
+        reader.check_pointer(&self.pointer)?;
+
+        let offset = match (n as u64).checked_mul(width) {
+            Some(offset) if offset < self.pointer.size => offset,
+            _ => return Ok(None)
+        };
+
+        let pos = self.pointer.pos + offset;
+        if offset + width > self.pointer.size {
+            return Err(TychoError::ContainerOverflow.located(pos).within(PathSegment::Index(n)));
+        }
+
+        let top = reader.pointer;
+        reader.jump(&pos)?;
+        let value = reader.limited(|reader, limiter| read_value(reader, &self.param, limiter))
+            .map_err(|e| e.located(pos).within(PathSegment::Index(n)));
+        reader.jump(&top)?;
+
+        value.map(Some)
+    }
+}
+
 #[derive(Debug, Clone)]
 /// A unprocessed compression object.
 pub struct PartialCompression {
     pub pointer: PartialPointer,
+    /// The codec the bytes were compressed with.
+    pub codec: Codec,
 }
 
 impl PartialCompression {
-    pub(crate) fn new(pointer: PartialPointer) -> Self {
-        PartialCompression { pointer }
+    pub(crate) fn new(pointer: PartialPointer, codec: Codec) -> Self {
+        PartialCompression { pointer, codec }
     }
 
     /// Get the bytes within the compression object.
@@ -96,15 +261,28 @@ impl PartialCompression {
     }
 
     #[cfg(feature="compression")]
-    /// Get the element within the compression object.
+    /// Decompress and read the element within the compression object.
+    ///
+    /// The decompressed bytes are not within the reader, so the whole element is read.
+    /// To read it partially, use `reader`.
     ///
     /// (requires `compression` feature)
-    pub fn element<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<PartialElement> {
-        let top = reader.pointer.clone();
-        reader.jump(&self.pointer.pos)?;
-        let element = read_partial_element(reader)?;
-        reader.jump(&top)?;
-        Ok(element)
+    pub fn element<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<Element> {
+        let bytes = self.bytes(reader)?;
+        let (codec, pos) = (self.codec, self.pointer.pos);
+        reader.limited(|_, limiter| read_decompressed(&bytes, codec, pos, limiter))
+    }
+
+    #[cfg(feature="compression")]
+    /// Decompress the bytes within the compression object into a new partial reader,
+    /// with the same options as the reader.
+    ///
+    /// (requires `compression` feature)
+    pub fn reader<R: Read + Seek>(&mut self, reader: &mut PartialReader<R>) -> TychoResult<PartialReader<Cursor<Vec<u8>>>> {
+        let bytes = self.bytes(reader)?;
+        let codec = self.codec;
+        let data = reader.limited(|_, limiter| decompress_limited(&bytes, codec, limiter))?;
+        Ok(PartialReader::from_vec(data).with_options(reader.limiter.options))
     }
 }
 
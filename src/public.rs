@@ -1,4 +1,4 @@
-use std::io::{BufWriter, Cursor, Read, Write};
+use std::io::{BufRead, BufWriter, ErrorKind, Read, Write};
 
 #[cfg(feature="async_tokio")]
 pub use async_tokio_public::*;
@@ -6,8 +6,12 @@ pub use async_tokio_public::*;
 pub use serde_public::*;
 
 use crate::Element;
-use crate::error::{TychoResult, TychoStatus};
+use crate::error::{TychoError, TychoResult, TychoStatus};
 use crate::read::element::read_element;
+use crate::read::buffer::ExactReader;
+use crate::read::options::Limiter;
+use crate::read::position::PositionReader;
+use crate::write::canonical::write_element_canonical;
 use crate::write::element::write_element;
 
 /// Marshall an element to a byte buffer or writable object.
@@ -52,8 +56,84 @@ pub fn marshall_vec<E: Into<Element>>(element: E) -> TychoResult<Vec<u8>> {
     Ok(buffer.into_inner().unwrap()) // todo: issue may occur here not sure. will catch later.
 }
 
+/// Marshall an element in its canonical form to a byte buffer or writable object.
+///
+/// The canonical form of an element is deterministic, and suitable for hashing or signing:
+/// - Struct fields and map entries are sorted by their encoded keys.
+/// - Float NaNs are written as a single quiet NaN.
+/// - Duplicate keys and compressed elements are rejected.
+///
+/// The canonical form is of an element as given, including the types of its values.
+/// Numbers keep their width, so `Unsigned8(10)` and `Unsigned32(10)` encode differently,
+/// and a list of values encodes differently to an array of the same values.
+/// Elements which are equal always have the same canonical form.
+///
+/// ### Example
+/// ```
+/// use tycho::collections::Struct;
+/// use tycho::marshall_canonical;
+///
+/// // Create two structs with fields in different orders.
+/// let mut a = Struct::new();
+/// a.insert("foo", 10u8);
+/// a.insert("bar", true);
+///
+/// let mut b = Struct::new();
+/// b.insert("bar", true);
+/// b.insert("foo", 10u8);
+///
+/// // Marshall both structs canonically.
+/// let mut a_bytes = Vec::new();
+/// marshall_canonical(&mut a_bytes, a).unwrap();
+/// let mut b_bytes = Vec::new();
+/// marshall_canonical(&mut b_bytes, b).unwrap();
+///
+/// assert_eq!(a_bytes, b_bytes);
+/// ```
+pub fn marshall_canonical<W: Write, E: Into<Element>>(writer: &mut W, element: E) -> TychoStatus {
+    write_element_canonical(writer, &element.into())
+}
+
+/// Marshall an element in its canonical form into a vec of bytes.
+///
+/// See [`marshall_canonical`](crate::marshall_canonical) for the canonical rules.
+pub fn marshall_canonical_vec<E: Into<Element>>(element: E) -> TychoResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    marshall_canonical(&mut buffer, element)?;
+    Ok(buffer)
+}
+
+/// Check if bytes contain a single element in its canonical form.
+///
+/// ### Example
+/// ```
+/// use tycho::is_canonical;
+///
+/// // A struct with fields `foo` and `bar`, in encoded order.
+/// let sorted = vec![5, 10, 98, 97, 114, 0, 0, 102, 111, 111, 0, 0];
+/// // The same struct, with fields out of order.
+/// let unsorted = vec![5, 10, 102, 111, 111, 0, 0, 98, 97, 114, 0, 0];
+///
+/// assert!(is_canonical(&sorted));
+/// assert!(!is_canonical(&unsorted));
+/// ```
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    let mut reader = PositionReader::new(bytes);
+    match read_element(&mut reader, &mut Limiter::default()) {
+        Ok(element) if reader.position() == bytes.len() as u64 => match marshall_canonical_vec(element) {
+            Ok(canonical) => canonical == bytes,
+            Err(_) => false
+        },
+        _ => false
+    }
+}
+
 /// Unmarshall an element from a readable object.
 ///
+/// Only the bytes of the element are read, so the reader can be read from again after it.
+/// Prefixes are read a byte at a time, so wrap readers such as files or sockets in a
+/// `std::io::BufReader`, or use `unmarshall_buffered`.
+///
 /// ### Example
 /// ```
 /// use std::io::{BufReader, Cursor};
@@ -71,9 +151,94 @@ pub fn marshall_vec<E: Into<Element>>(element: E) -> TychoResult<Vec<u8>> {
 /// assert_eq!(data, Value(Number(Unsigned8(10))));
 /// ```
 pub fn unmarshall<R: Read>(reader: &mut R) -> TychoResult<Element> {
-    read_element(reader)
+    read_element(&mut PositionReader::new(ExactReader::new(reader)), &mut Limiter::default())
+}
+
+/// Unmarshall an element from a readable object, within a set of limits.
+///
+/// Use this over `unmarshall` when reading untrusted input,
+/// erroring with `TychoError::LimitExceeded` when a limit is exceeded.
+///
+/// ### Example
+/// ```
+/// use std::io::Cursor;
+/// use tycho::{ReadOptions, unmarshall_with};
+///
+/// let options = ReadOptions { max_depth: Some(2), ..ReadOptions::default() };
+///
+/// // `Some(Value(10))` is within the depth limit.
+/// let mut bytes = Cursor::new(vec![3, 1, 4, 1, 10]);
+/// assert!(unmarshall_with(&mut bytes, options).is_ok());
+///
+/// // `Some(Some(Value(10)))` is not.
+/// let mut bytes = Cursor::new(vec![3, 3, 1, 4, 1, 10]);
+/// assert!(unmarshall_with(&mut bytes, options).is_err());
+/// ```
+pub fn unmarshall_with<R: Read>(reader: &mut R, options: ReadOptions) -> TychoResult<Element> {
+    read_element(&mut PositionReader::new(ExactReader::new(reader)), &mut Limiter::new(options))
+}
+
+/// Unmarshall an element from a buffered readable object, without reading past the element.
+///
+/// Only the bytes of the element are consumed, so the reader can be read from again after it,
+/// such as to read the next of a sequence of elements.
+///
+/// ### Example
+/// ```
+/// use std::io::{BufReader, Cursor};
+/// use tycho::{Element, unmarshall_buffered};
+///
+/// // A unit element, followed by a none element.
+/// let mut reader = BufReader::new(Cursor::new(vec![0, 2]));
+///
+/// assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Unit);
+/// assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Option(None));
+/// ```
+pub fn unmarshall_buffered<R: BufRead>(reader: &mut R) -> TychoResult<Element> {
+    read_element(&mut PositionReader::new(reader), &mut Limiter::default())
 }
 
+/// Unmarshall an element from a buffered readable object, without reading past the element,
+/// within a set of limits.
+///
+/// See `unmarshall_buffered` and `unmarshall_with`.
+pub fn unmarshall_buffered_with<R: BufRead>(reader: &mut R, options: ReadOptions) -> TychoResult<Element> {
+    read_element(&mut PositionReader::new(reader), &mut Limiter::new(options))
+}
+
+/// Unmarshall a single element from a readable object, erroring if any bytes remain after it.
+///
+/// Unlike `unmarshall`, which stops after the first element,
+/// this errors with `TychoError::TrailingData` if the reader has not ended.
+///
+/// ### Example
+/// ```
+/// use std::io::Cursor;
+/// use tycho::unmarshall_exact;
+/// use tycho::error::TychoError;
+///
+/// let mut bytes = Cursor::new(vec![1, 4, 1, 10]);
+/// assert!(unmarshall_exact(&mut bytes).is_ok());
+///
+/// // A unit element, followed by an extra byte.
+/// let mut bytes = Cursor::new(vec![0, 0]);
+/// assert!(matches!(unmarshall_exact(&mut bytes).unwrap_err().inner(), TychoError::TrailingData));
+/// ```
+pub fn unmarshall_exact<R: Read>(reader: &mut R) -> TychoResult<Element> {
+    let mut reader = PositionReader::new(ExactReader::new(reader));
+    let element = read_element(&mut reader, &mut Limiter::default())?;
+
+    let position = reader.position();
+    let mut byte = [0u8];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(element),
+            Ok(_) => return Err(TychoError::TrailingData.located(position)),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into())
+        }
+    }
+}
 
 /// Unmarshall an element from a vec of bytes.
 ///
@@ -93,19 +258,23 @@ pub fn unmarshall<R: Read>(reader: &mut R) -> TychoResult<Element> {
 /// assert_eq!(data, Value(Number(Unsigned16(420))))
 /// ```
 pub fn unmarshall_vec(data: Vec<u8>) -> TychoResult<Element>  {
-    let mut buffer = Cursor::new(data);
-    unmarshall(&mut buffer)
+    read_element(&mut PositionReader::new(data.as_slice()), &mut Limiter::default())
 }
 
 
 
 #[cfg(feature="async_tokio")]
 mod async_tokio_public {
-    use tokio::io::AsyncRead;
+    use tokio::io::{AsyncRead, AsyncWrite};
 
     use crate::Element;
-    use crate::error::TychoResult;
+    use crate::error::{TychoResult, TychoStatus};
     use crate::read::async_::element::read_element_async;
+    use crate::read::options::{Limiter, ReadOptions};
+    use crate::read::position::PositionReader;
+    use crate::write::async_::element::write_element_async;
+
+    pub use crate::read::async_::stream::ElementStreamAsync;
 
     /// Unmarshall an element from a async readable object.
     ///
@@ -148,7 +317,90 @@ mod async_tokio_public {
     /// ```
     ///
     pub async fn unmarshall_async<R: AsyncRead + Unpin + Send>(reader: &mut R) -> TychoResult<Element> {
-        read_element_async(reader).await
+        read_element_async(&mut PositionReader::new(reader), &mut Limiter::default()).await
+    }
+
+    /// Unmarshall an element from an async readable object, within a set of limits.
+    ///
+    /// Use this over `unmarshall_async` when reading untrusted input,
+    /// erroring with `TychoError::LimitExceeded` when a limit is exceeded.
+    ///
+    /// ### Example
+    /// ```
+    /// use std::io::Cursor;
+    /// use tycho::{ReadOptions, unmarshall_async_with};
+    /// # let _ = tokio_test::block_on( async {
+    ///
+    /// let options = ReadOptions { max_depth: Some(2), ..ReadOptions::default() };
+    ///
+    /// // `Some(Value(10))` is within the depth limit.
+    /// let mut bytes = Cursor::new(vec![3, 1, 4, 1, 10]);
+    /// assert!(unmarshall_async_with(&mut bytes, options).await.is_ok());
+    ///
+    /// // `Some(Some(Value(10)))` is not.
+    /// let mut bytes = Cursor::new(vec![3, 3, 1, 4, 1, 10]);
+    /// assert!(unmarshall_async_with(&mut bytes, options).await.is_err());
+    /// # });
+    /// ```
+    pub async fn unmarshall_async_with<R: AsyncRead + Unpin + Send>(reader: &mut R, options: ReadOptions) -> TychoResult<Element> {
+        read_element_async(&mut PositionReader::new(reader), &mut Limiter::new(options)).await
+    }
+
+    /// Marshall an element to an async writable object.
+    ///
+    /// The bytes written are the same as `marshall`,
+    /// with the element written in chunks rather than buffered in full.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element::Value, Value::Boolean, marshall_async};
+    /// # let _ = tokio_test::block_on( async {
+    ///
+    /// // Create a boolean value.
+    /// let data = Value(Boolean(true));
+    ///
+    /// // Write boolean value to buffer.
+    /// let mut buffer = Vec::new();
+    /// marshall_async(&mut buffer, data).await.unwrap();
+    ///
+    /// assert_eq!(buffer, vec![1, 1, 1]);
+    /// # });
+    /// ```
+    pub async fn marshall_async<W: AsyncWrite + Unpin + Send, E: Into<Element>>(writer: &mut W, element: E) -> TychoStatus {
+        write_element_async(writer, &element.into()).await
+    }
+
+    #[cfg(feature="serde")]
+    /// Serialize a serde serializable object as tycho bytes to an async writable object.
+    /// (requires `serde`)
+    ///
+    /// The bytes written are the same as `to_writer`.
+    /// As serde serializes synchronously, the object is serialized into a buffer before being written.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use tycho::to_writer_async;
+    /// # let _ = tokio_test::block_on( async {
+    ///
+    /// // Create a serializable serde structure.
+    /// #[derive(Serialize)]
+    /// pub struct Example {
+    ///     foo: String
+    /// }
+    ///
+    /// // Instantiate serializable object.
+    /// let data = Example { foo: "Hi".to_string() };
+    ///
+    /// // Serialize to an async writer.
+    /// let mut buffer = Vec::new();
+    /// to_writer_async(&mut buffer, data).await.unwrap();
+    ///
+    /// assert_eq!(buffer, vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105])
+    /// # });
+    /// ```
+    pub async fn to_writer_async<W: AsyncWrite + Unpin + Send, S: serde::Serialize>(writer: &mut W, o: S) -> TychoStatus {
+        let bytes = crate::to_bytes(o)?;
+        crate::error::parse_io(tokio::io::AsyncWriteExt::write_all(writer, &bytes).await)
     }
 
 }
@@ -156,13 +408,19 @@ mod async_tokio_public {
 
 #[cfg(feature="serde")]
 mod serde_public {
+    use std::io::{BufRead, Read, Write};
+
     use serde::de::DeserializeOwned;
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
 
-    use crate::{Element, marshall_vec, unmarshall_vec};
-    use crate::error::TychoResult;
+    use crate::{Element, marshall_canonical_vec, ReadOptions};
+    use crate::error::{TychoResult, TychoStatus};
+    use crate::read::buffer::ExactReader;
     use crate::serde::de::TychoDeserializer;
+    use crate::serde::read::{IoSource, SliceSource, TychoReadDeserializer};
     use crate::serde::ser::TychoSerializer;
+    use crate::serde::write::{Output, TychoWriteSerializer};
+    use crate::write::element::write_element;
 
     /// Serialize a serde serializable object into an Element. (requires `serde`)
     ///
@@ -214,7 +472,72 @@ mod serde_public {
     /// assert_eq!(bytes, vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105])
     /// ```
     pub fn to_bytes<S: Serialize>(o: S) -> TychoResult<Vec<u8>> {
-        marshall_vec(to_element(o)?)
+        let mut buffer = Vec::new();
+        to_writer(&mut buffer, o)?;
+        Ok(buffer)
+    }
+
+    /// Serialize a serde serializable object as tycho bytes to a writable object. (requires `serde`)
+    ///
+    /// Bytes are written as the object is serialized, without building an intermediate element.
+    /// The object is serialized twice, first to find the size of each container, then to write it.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use tycho::to_writer;
+    ///
+    /// // Create a serializable serde structure.
+    /// #[derive(Serialize)]
+    /// pub struct Example {
+    ///     foo: String
+    /// }
+    ///
+    /// // Instantiate serializable object.
+    /// let data = Example { foo: "Hi".to_string() };
+    ///
+    /// // Serialize to a writer.
+    /// let mut buffer = Vec::new();
+    /// to_writer(&mut buffer, data).unwrap();
+    ///
+    /// assert_eq!(buffer, vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105])
+    /// ```
+    pub fn to_writer<W: Write, S: Serialize>(writer: &mut W, o: S) -> TychoStatus {
+        let mut sized = Output::sizing();
+        o.serialize(TychoWriteSerializer::new(&mut sized))?;
+
+        // repeated keys are replaced within an element, so are written the same way.
+        if sized.duplicate {
+            return write_element(writer, &o.serialize(TychoSerializer)?);
+        }
+
+        o.serialize(TychoWriteSerializer::new(&mut Output::writing(writer, sized)))
+    }
+
+    /// Serialize a serde serializable object into canonical tycho bytes. (requires `serde`)
+    ///
+    /// See [`marshall_canonical`](crate::marshall_canonical) for the canonical rules.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use tycho::{is_canonical, to_bytes_canonical};
+    ///
+    /// // Create a serializable serde structure.
+    /// #[derive(Serialize)]
+    /// pub struct Example {
+    ///     foo: String,
+    ///     bar: bool
+    /// }
+    ///
+    /// // Instantiate serializable object.
+    /// let data = Example { foo: "Hi".to_string(), bar: true };
+    ///
+    /// // Convert serializable serde structure to canonical bytes.
+    /// let bytes = to_bytes_canonical(data).unwrap();
+    ///
+    /// assert!(is_canonical(&bytes));
+    /// ```
+    pub fn to_bytes_canonical<S: Serialize>(o: S) -> TychoResult<Vec<u8>> {
+        marshall_canonical_vec(to_element(o)?)
     }
 
     /// Deserialize an element into a serde deserializable object. (requires `serde`)
@@ -265,11 +588,137 @@ mod serde_public {
     ///
     /// ```
     pub fn from_bytes<D: DeserializeOwned>(b: &[u8]) -> TychoResult<D> {
-        from_element(unmarshall_vec(b.to_vec())?)
+        from_slice(b)
+    }
+
+    /// Deserialize tycho bytes into a serde deserializable object, within a set of limits.
+    /// (requires `serde`)
+    ///
+    /// Use this over `from_bytes` when reading untrusted input,
+    /// erroring with `TychoError::LimitExceeded` when a limit is exceeded.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use tycho::{from_bytes_with, ReadOptions};
+    ///
+    /// #[derive(Deserialize, PartialEq, Debug)]
+    /// pub struct Example {
+    ///     foo: String
+    /// }
+    ///
+    /// let bytes = vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105];
+    ///
+    /// // Strings and keys of up to 3 bytes are allowed.
+    /// let options = ReadOptions { max_string_len: Some(3), ..ReadOptions::default() };
+    /// let data: Example = from_bytes_with(&bytes, options).unwrap();
+    /// assert_eq!(data, Example { foo: "Hi".to_string() });
+    ///
+    /// // Strings and keys of up to 1 byte are not.
+    /// let options = ReadOptions { max_string_len: Some(1), ..ReadOptions::default() };
+    /// assert!(from_bytes_with::<Example>(&bytes, options).is_err());
+    /// ```
+    pub fn from_bytes_with<D: DeserializeOwned>(b: &[u8], options: ReadOptions) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut SliceSource::with_options(b, options)))
+    }
+
+    /// Deserialize a slice of tycho bytes into a serde deserializable object. (requires `serde`)
+    ///
+    /// Bytes are parsed as the object is deserialized, without building an intermediate element.
+    /// Strings and bytes can be borrowed from the slice, avoiding a copy.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use tycho::from_slice;
+    ///
+    /// // Create a serializable serde structure, borrowing from the input.
+    /// #[derive(Deserialize, PartialEq, Debug)]
+    /// pub struct Example<'a> {
+    ///     foo: &'a str
+    /// }
+    ///
+    /// // Example bytes
+    /// let bytes = vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105];
+    ///
+    /// // Deserialize bytes
+    /// let data: Example = from_slice(&bytes).unwrap();
+    ///
+    /// assert_eq!(data, Example { foo: "Hi" })
+    /// ```
+    pub fn from_slice<'de, D: Deserialize<'de>>(b: &'de [u8]) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut SliceSource::new(b)))
+    }
+
+    /// Deserialize tycho bytes from a readable object into a serde deserializable object.
+    /// (requires `serde`)
+    ///
+    /// Bytes are read as the object is deserialized, without building an intermediate element.
+    ///
+    /// Only the bytes of the element are read, so the reader can be read from again after it.
+    /// Prefixes are read a byte at a time, so wrap readers such as files or sockets in a
+    /// `std::io::BufReader`, or use `from_reader_buffered`.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use serde::Deserialize;
+    /// use tycho::from_reader;
+    ///
+    /// // Create a serializable serde structure.
+    /// #[derive(Deserialize, PartialEq, Debug)]
+    /// pub struct Example {
+    ///     foo: String
+    /// }
+    ///
+    /// // Create a cursor with example bytes
+    /// let mut bytes = Cursor::new(vec![5, 9, 102, 111, 111, 0, 1, 2, 2, 72, 105]);
+    ///
+    /// // Deserialize from reader
+    /// let data: Example = from_reader(&mut bytes).unwrap();
+    ///
+    /// assert_eq!(data, Example { foo: "Hi".to_string() })
+    /// ```
+    pub fn from_reader<R: Read, D: DeserializeOwned>(reader: &mut R) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut IoSource::new(ExactReader::new(reader))))
+    }
+
+    /// Deserialize tycho bytes from a readable object into a serde deserializable object,
+    /// within a set of limits. (requires `serde`)
+    ///
+    /// Use this over `from_reader` when reading untrusted input.
+    pub fn from_reader_with<R: Read, D: DeserializeOwned>(reader: &mut R, options: ReadOptions) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut IoSource::with_options(ExactReader::new(reader), options)))
+    }
+
+    /// Deserialize tycho bytes from a buffered readable object into a serde deserializable object,
+    /// without reading past the element. (requires `serde`)
+    ///
+    /// Only the bytes of the element are consumed, so the reader can be read from again after it.
+    ///
+    /// ```
+    /// use std::io::{BufReader, Cursor};
+    /// use tycho::from_reader_buffered;
+    ///
+    /// // Two strings, one after the other.
+    /// let mut reader = BufReader::new(Cursor::new(vec![1, 2, 2, 72, 105, 1, 2, 2, 121, 111]));
+    ///
+    /// assert_eq!(from_reader_buffered::<_, String>(&mut reader).unwrap(), "Hi");
+    /// assert_eq!(from_reader_buffered::<_, String>(&mut reader).unwrap(), "yo");
+    /// ```
+    pub fn from_reader_buffered<R: BufRead, D: DeserializeOwned>(reader: &mut R) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut IoSource::new(reader)))
+    }
+
+    /// Deserialize tycho bytes from a buffered readable object into a serde deserializable object,
+    /// without reading past the element, within a set of limits. (requires `serde`)
+    ///
+    /// See `from_reader_buffered` and `from_reader_with`.
+    pub fn from_reader_buffered_with<R: BufRead, D: DeserializeOwned>(reader: &mut R, options: ReadOptions) -> TychoResult<D> {
+        D::deserialize(TychoReadDeserializer::new(&mut IoSource::with_options(reader, options)))
     }
 }
 
 pub use crate::into::ident::Ident;
+pub use crate::read::options::ReadOptions;
+pub use crate::read::stream::ElementStream;
 pub use crate::into::value::ValueType;
 
 
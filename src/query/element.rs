@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+
+use crate::{Element, Value};
+use crate::query::{Query, Selector};
+
+impl Query {
+    /// Evaluate the query against an element, returning each match.
+    pub fn select(&self, element: &Element) -> Vec<Element> {
+        let mut matches = Vec::new();
+        select_element(&self.selectors, element, &mut matches);
+        matches
+    }
+}
+
+pub(crate) fn select_element(selectors: &[Selector], element: &Element, matches: &mut Vec<Element>) {
+    let (selector, rest) = match selectors.split_first() {
+        Some(x) => x,
+        None => {
+            matches.push(element.clone());
+            return;
+        }
+    };
+
+    for item in select_items(selector, element) {
+        select_element(rest, &item, matches);
+    }
+}
+
+/// Apply a single selector to an element, returning the items selected within it.
+fn select_items<'a>(selector: &Selector, element: &'a Element) -> Vec<Cow<'a, Element>> {
+    match (selector, element) {
+        (_, Element::Option(Some(x))) | (_, Element::Variant(_, x)) => select_items(selector, x),
+
+        #[cfg(feature="compression")]
+        (_, Element::Compression(_, x)) => select_items(selector, x),
+
+        (Selector::Field(name), Element::Struct(data)) => data.get(name).map(Cow::Borrowed).into_iter().collect(),
+        (Selector::Field(name), Element::Map(_, data)) => data.get(&Value::String(name.clone())).map(Cow::Borrowed).into_iter().collect(),
+        (Selector::All, Element::Struct(data)) => data.values().map(Cow::Borrowed).collect(),
+        (Selector::All, Element::Map(_, data)) => data.values().map(Cow::Borrowed).collect(),
+
+        (Selector::Field(_), _) => Vec::new(),
+
+        (_, Element::List(data)) => data.iter().enumerate()
+            .filter(|(i, _)| selector.selects_index(*i))
+            .map(|(_, x)| Cow::Borrowed(x))
+            .collect(),
+        (_, Element::Array(_, data)) => data.iter().enumerate()
+            .filter(|(i, _)| selector.selects_index(*i))
+            .map(|(_, x)| Cow::Owned(Element::Value(x.clone())))
+            .collect(),
+
+        _ => Vec::new(),
+    }
+}
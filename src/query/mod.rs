@@ -0,0 +1,110 @@
+//! Path queries over elements and partial readers.
+//!
+//! A query is compiled once from a path, and can then be evaluated against an in-memory
+//! `Element`, or against a `PartialReader` without reading unrelated data.
+//!
+//! ## Syntax
+//! A query is a sequence of selectors, each applied to the matches of the one before it:
+//! - `name` or `.name` - a struct field, or a map value with a string key.
+//! - `["name"]` - the same as `.name`, for names which are not alphanumeric.
+//! - `[3]` - the item at an index within a list or array.
+//! - `[3..10]`, `[3..]`, `[..10]` - the items within a range of a list or array.
+//! - `[*]` or `.*` - every item within a list or array, or every value within a struct or map.
+//!
+//! An empty query matches the element it is evaluated against.
+//!
+//! Option and variant elements are passed through when a selector is applied to them,
+//! so `address.city` matches within `Some({"city": ...})`.
+//!
+//! ## Example
+//! ```
+//! use tycho::{Element, ElementMap, Value};
+//! use tycho::query::Query;
+//!
+//! let mut user = ElementMap::new();
+//! user.insert("name".to_string(), Element::Value(Value::String("Dan".to_string())));
+//! let users = Element::List(vec![Element::Struct(user)]);
+//!
+//! let query = Query::parse("[*].name").unwrap();
+//!
+//! assert_eq!(query.select(&users), vec![Element::Value(Value::String("Dan".to_string()))]);
+//! ```
+
+use std::str::FromStr;
+
+use crate::error::{TychoError, TychoResult};
+
+pub(crate) mod element;
+pub(crate) mod parse;
+
+#[cfg(feature="partial")]
+pub(crate) mod partial;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single step within a query.
+pub enum Selector {
+    /// A struct field, or a map value with a string key.
+    Field(String),
+    /// The item at an index within a list or array.
+    Index(usize),
+    /// The items within a range of a list or array, from the start to the end (exclusive).
+    Range(Option<usize>, Option<usize>),
+    /// Every item within a list or array, or every value within a struct or map.
+    All,
+}
+
+impl Selector {
+    /// Check if an index within a list or array is selected.
+    pub(crate) fn selects_index(&self, index: usize) -> bool {
+        match self {
+            Selector::Index(n) => index == *n,
+            Selector::Range(start, end) => !matches!(start, Some(x) if index < *x) && !matches!(end, Some(x) if index >= *x),
+            Selector::All => true,
+            Selector::Field(_) => false,
+        }
+    }
+
+    #[cfg(feature="partial")]
+    /// The index after the last index which can be selected, if limited.
+    pub(crate) fn index_end(&self) -> Option<usize> {
+        match self {
+            Selector::Index(n) => Some(n.saturating_add(1)),
+            Selector::Range(_, end) => *end,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A compiled path query.
+pub struct Query {
+    selectors: Vec<Selector>,
+}
+
+impl Query {
+    /// Compile a query from its path.
+    ///
+    /// Returns `TychoError::InvalidQuery` if the path is not valid.
+    pub fn parse(query: &str) -> TychoResult<Self> {
+        Ok(Query { selectors: parse::parse_query(query)? })
+    }
+
+    /// Get the selectors within the query.
+    pub fn selectors(&self) -> &[Selector] {
+        &self.selectors
+    }
+}
+
+impl From<Vec<Selector>> for Query {
+    fn from(selectors: Vec<Selector>) -> Self {
+        Query { selectors }
+    }
+}
+
+impl FromStr for Query {
+    type Err = TychoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Query::parse(s)
+    }
+}
@@ -0,0 +1,152 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::error::{TychoError, TychoResult};
+use crate::query::Selector;
+
+struct QueryParser<'a> {
+    query: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> QueryParser<'a> {
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.query.len(), |(i, _)| *i)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn error<T>(&mut self, reason: &str) -> TychoResult<T> {
+        Err(TychoError::InvalidQuery { position: self.position(), reason: reason.to_string() })
+    }
+
+    fn expect(&mut self, expected: char) -> TychoResult<()> {
+        if self.peek() == Some(expected) {
+            self.chars.next();
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", expected))
+        }
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.chars.next();
+        }
+        found
+    }
+
+    /// Parse a field name or `*`, following a `.` or at the start of the query.
+    fn name(&mut self) -> TychoResult<Selector> {
+        if self.consume('*') {
+            return Ok(Selector::All);
+        }
+
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+            self.chars.next();
+        }
+
+        if name.is_empty() {
+            return self.error("expected a field name");
+        }
+        Ok(Selector::Field(name))
+    }
+
+    /// Parse the contents of a `[...]` selector, after the opening bracket.
+    fn bracket(&mut self) -> TychoResult<Selector> {
+        let selector = match self.peek() {
+            Some('*') => {
+                self.chars.next();
+                Selector::All
+            }
+            Some('"') => Selector::Field(self.string()?),
+            Some('.') => {
+                self.expect('.')?;
+                self.expect('.')?;
+                Selector::Range(None, self.number()?)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.number()?;
+                if self.consume('.') {
+                    self.expect('.')?;
+                    Selector::Range(start, self.number()?)
+                } else {
+                    Selector::Index(start.unwrap_or_default())
+                }
+            }
+            _ => return self.error("expected '*', a string, an index or a range"),
+        };
+
+        self.expect(']')?;
+        Ok(selector)
+    }
+
+    /// Parse a decimal index, if one is present.
+    fn number(&mut self) -> TychoResult<Option<usize>> {
+        let position = self.position();
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            digits.push(c);
+            self.chars.next();
+        }
+
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        digits.parse().map(Some).map_err(|_| TychoError::InvalidQuery {
+            position,
+            reason: "index is too large".to_string()
+        })
+    }
+
+    /// Parse a quoted string, with `\"` and `\\` escapes.
+    fn string(&mut self) -> TychoResult<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(string),
+                Some((_, '\\')) => match self.peek() {
+                    Some(c) if c == '"' || c == '\\' => {
+                        string.push(c);
+                        self.chars.next();
+                    }
+                    _ => return self.error("expected '\"' or '\\' after escape"),
+                },
+                Some((_, c)) => string.push(c),
+                None => return self.error("unterminated string"),
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_query(query: &str) -> TychoResult<Vec<Selector>> {
+    let mut parser = QueryParser { query, chars: query.char_indices().peekable() };
+    let mut selectors = Vec::new();
+
+    // the first field may be given without a leading `.`
+    if matches!(parser.peek(), Some(c) if c != '.' && c != '[') {
+        selectors.push(parser.name()?);
+    }
+
+    while let Some(c) = parser.peek() {
+        parser.chars.next();
+        match c {
+            '.' => selectors.push(parser.name()?),
+            '[' => selectors.push(parser.bracket()?),
+            _ => {
+                return Err(TychoError::InvalidQuery {
+                    position: parser.position() - c.len_utf8(),
+                    reason: "expected '.' or '['".to_string()
+                });
+            }
+        }
+    }
+
+    Ok(selectors)
+}
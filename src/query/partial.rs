@@ -0,0 +1,142 @@
+use std::io::{Read, Seek};
+
+use crate::{Element, Value};
+use crate::error::{TychoResult, TychoStatus};
+use crate::ident::ValueIdent;
+use crate::partial::{PartialElement, PartialReader};
+use crate::partial::element::read_full_element;
+use crate::query::{Query, Selector};
+
+#[cfg(feature="compression")]
+use crate::query::element::select_element;
+
+/// A match found within a partial reader.
+enum Found {
+    /// A match which can be read further from the reader.
+    Partial(PartialElement),
+    /// A match within a compressed element, which has already been read.
+    #[cfg_attr(not(feature="compression"), allow(dead_code))]
+    Element(Element),
+}
+
+impl Query {
+    /// Evaluate the query against a partial element, returning each match.
+    ///
+    /// Fields and indexes are found with `get` and `nth`,
+    /// so items which are not selected are skipped over without being read.
+    ///
+    /// Matches within compressed elements can not be pointed to within the reader,
+    /// and are only returned by `select_partial_elements`.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::{Element, marshall_vec, Value};
+    /// use tycho::partial::{PartialElement, PartialReader};
+    /// use tycho::query::Query;
+    ///
+    /// let bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Value(Value::Boolean(true))])).unwrap();
+    /// let mut reader = PartialReader::from_vec(bytes);
+    /// let root = reader.element().unwrap();
+    ///
+    /// let matches = Query::parse("[1]").unwrap().select_partial(&mut reader, &root).unwrap();
+    /// assert!(matches!(matches[..], [PartialElement::Value(Value::Boolean(true))]));
+    /// ```
+    pub fn select_partial<R: Read + Seek>(&self, reader: &mut PartialReader<R>, element: &PartialElement) -> TychoResult<Vec<PartialElement>> {
+        let mut matches = Vec::new();
+        select_partial(&self.selectors, reader, element.clone(), &mut matches)?;
+
+        Ok(matches.into_iter()
+            .filter_map(|x| match x {
+                Found::Partial(element) => Some(element),
+                Found::Element(_) => None,
+            })
+            .collect())
+    }
+
+    /// Evaluate the query against a partial element, reading the whole of each match.
+    pub fn select_partial_elements<R: Read + Seek>(&self, reader: &mut PartialReader<R>, element: &PartialElement) -> TychoResult<Vec<Element>> {
+        let mut matches = Vec::new();
+        select_partial(&self.selectors, reader, element.clone(), &mut matches)?;
+
+        matches.into_iter()
+            .map(|x| match x {
+                Found::Partial(element) => read_full_element(reader, &element),
+                Found::Element(element) => Ok(element),
+            })
+            .collect()
+    }
+}
+
+fn select_partial<R: Read + Seek>(selectors: &[Selector], reader: &mut PartialReader<R>, element: PartialElement, matches: &mut Vec<Found>) -> TychoStatus {
+    let (selector, rest) = match selectors.split_first() {
+        Some(x) => x,
+        None => {
+            matches.push(Found::Partial(element));
+            return Ok(());
+        }
+    };
+
+    let items = match (selector, element) {
+        (_, PartialElement::Option(Some(x))) | (_, PartialElement::Variant(_, x)) =>
+            return select_partial(selectors, reader, *x, matches),
+
+        #[cfg(feature="compression")]
+        (_, element @ PartialElement::Compression(_)) => {
+            // compressed elements are read whole, and the rest of the query is evaluated in memory.
+            let mut found = Vec::new();
+            select_element(selectors, &read_full_element(reader, &element)?, &mut found);
+            matches.extend(found.into_iter().map(Found::Element));
+            return Ok(());
+        }
+
+        (Selector::Field(name), PartialElement::Struct(s)) => s.get(reader, name)?.into_iter().collect(),
+        (Selector::Field(name), PartialElement::Map(m)) if m.param == ValueIdent::String =>
+            m.get(reader, &Value::String(name.clone()))?.into_iter().collect(),
+
+        (Selector::All, PartialElement::Struct(mut s)) => {
+            s.top();
+            s.collect(reader)?.into_iter().map(|(_, value)| value).collect()
+        }
+        (Selector::All, PartialElement::Map(mut m)) => {
+            m.top();
+            m.collect(reader)?.into_iter().map(|(_, value)| value).collect()
+        }
+
+        (Selector::Index(n), PartialElement::List(l)) => l.nth(reader, *n)?.into_iter().collect(),
+        (Selector::Index(n), PartialElement::Array(a)) => a.nth(reader, *n)?.into_iter().map(PartialElement::Value).collect(),
+
+        (Selector::Range(..) | Selector::All, PartialElement::List(mut l)) => {
+            l.top();
+            let mut items = Vec::new();
+            while !matches!(selector.index_end(), Some(end) if l.index >= end) {
+                let index = l.index;
+                match l.next(reader)? {
+                    Some(item) if selector.selects_index(index) => items.push(item),
+                    Some(_) => (),
+                    None => break,
+                }
+            }
+            items
+        }
+        (Selector::Range(..) | Selector::All, PartialElement::Array(mut a)) => {
+            a.top();
+            let mut items = Vec::new();
+            while !matches!(selector.index_end(), Some(end) if a.index >= end) {
+                let index = a.index;
+                match a.next(reader)? {
+                    Some(item) if selector.selects_index(index) => items.push(PartialElement::Value(item)),
+                    Some(_) => (),
+                    None => break,
+                }
+            }
+            items
+        }
+
+        _ => Vec::new(),
+    };
+
+    for item in items {
+        select_partial(rest, reader, item, matches)?;
+    }
+    Ok(())
+}
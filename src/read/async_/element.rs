@@ -1,112 +1,172 @@
-use std::collections::HashMap;
-use std::io::Cursor;
-
 use futures::future::{BoxFuture, FutureExt};
 use tokio::io::AsyncRead;
 
-use crate::Element;
-use crate::error::TychoResult;
+use crate::{Element, ElementMap};
+use crate::error::{PathSegment, TychoResult};
 use crate::read::async_::func::{read_byte_async, read_bytes_async};
 use crate::read::async_::length::read_length_async;
 use crate::read::async_::string::read_tstring_async;
 use crate::read::async_::value::{read_value_async, read_value_ident_async};
-use crate::read::element::parse_element_ident;
-use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::read::element::{check_container_end, parse_codec, parse_element_ident};
+use crate::read::options::Limiter;
+use crate::read::position::PositionReader;
+use crate::types::ident::{Codec, ElementIdent, ValueIdent};
 
-pub(crate) async fn read_element_ident_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<ElementIdent> {
-   parse_element_ident(read_byte_async(reader).await?)
+#[cfg(feature="compression")]
+use std::io::Cursor;
+#[cfg(feature="compression")]
+use crate::error::TychoError;
+#[cfg(feature="compression")]
+use crate::into::compression::Compression;
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
+
+pub(crate) fn read_element_async<'a, R: AsyncRead + Unpin + Send>(reader: &'a mut PositionReader<R>, limiter: &'a mut Limiter) -> BoxFuture<'a, TychoResult<Element>> {
+    async move {
+        let start = reader.position();
+        let result = match read_byte_async(reader).await {
+            Ok(prefix) => read_element_at_async(reader, prefix, start, limiter).await,
+            Err(e) => Err(e)
+        };
+        result.map_err(|e| e.located(start))
+    }.boxed()
 }
 
-pub(crate) fn read_element_async<'a, R: AsyncRead + Unpin + Send>(reader: &'a mut R) -> BoxFuture<'a, TychoResult<Element>> {
-    async move {
-        let ident = read_element_ident_async(reader).await?;
-
-        match ident {
-            ElementIdent::Unit => Ok(Element::Unit),
-            ElementIdent::Value => {
-                let ident = read_value_ident_async(reader).await?;
-                Ok(Element::Value(read_value_async(reader, &ident).await?))
-            },
-            ElementIdent::Some => Ok(Element::Option(Some(Box::new(read_element_async(reader).await?)))),
-            ElementIdent::None => Ok(Element::Option(None)),
-            ElementIdent::Variant => Ok(Element::Variant(
-                read_tstring_async(reader).await?,
-                Box::new(read_element_async(reader).await?)
-            )),
-            ElementIdent::Struct => {
-                let size = read_length_async(reader).await?;
-                let mut items = HashMap::new();
-                let mut buffer = Cursor::new(read_bytes_async(reader, size).await?);
-
-                loop {
-                    if buffer.position() == size as u64 { break; }
-
-                    let key = read_tstring_async(&mut buffer).await?;
-                    let value = read_element_async(&mut buffer).await?;
-
-                    items.insert(key, value);
-                }
+/// Read an element whose ident byte has already been read.
+pub(crate) async fn read_element_after_async<R: AsyncRead + Unpin + Send>(reader: &mut PositionReader<R>, byte: u8, limiter: &mut Limiter) -> TychoResult<Element> {
+    let start = reader.position() - 1;
+    read_element_at_async(reader, byte, start, limiter).await
+        .map_err(|e| e.located(start))
+}
 
-                Ok(Element::Struct(items))
-            }
-            ElementIdent::List => {
-                let size = read_length_async(reader).await?;
-                let mut items = Vec::new();
-                let mut buffer = Cursor::new(read_bytes_async(reader, size).await?);
+async fn read_element_at_async<R: AsyncRead + Unpin + Send>(reader: &mut PositionReader<R>, prefix: u8, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    let ident = parse_element_ident(prefix)?;
+    limiter.enter()?;
+    let element = read_element_body_async(reader, ident, prefix, start, limiter).await;
+    limiter.exit();
 
-                loop {
-                    if buffer.position() == size as u64 { break; }
-                    items.push(read_element_async(&mut buffer).await?);
-                }
+    element
+}
 
-                Ok(Element::List(items))
-            },
-            ElementIdent::Array => {
-                let array_type = read_value_ident_async(reader).await?;
-
-                if let ValueIdent::Null = &array_type {
-                    Ok(Element::Array(ValueIdent::Null, Vec::new()))
-                } else {
-                    let size = read_length_async(reader).await?;
-                    let mut items = Vec::new();
-                    let mut buffer = Cursor::new(read_bytes_async(reader, size).await?);
-
-                    loop {
-                        if buffer.position() == size as u64 { break; }
-                        items.push(read_value_async(&mut buffer, &array_type).await?);
-                    }
+/// Read a container size prefix, returning the position at which the container ends.
+async fn read_container_end_async<R: AsyncRead + Unpin>(reader: &mut PositionReader<R>, limiter: &mut Limiter) -> TychoResult<u64> {
+    let size = read_length_async(reader).await?;
+    limiter.container(size)?;
+    Ok(reader.position() + size as u64)
+}
 
-                    Ok(Element::Array(array_type, items))
-                }
-            },
-            ElementIdent::Map => {
-                let key_type = read_value_ident_async(reader).await?;
+async fn read_element_body_async<R: AsyncRead + Unpin + Send>(reader: &mut PositionReader<R>, ident: ElementIdent, prefix: u8, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    match ident {
+        ElementIdent::Unit => Ok(Element::Unit),
+        ElementIdent::Value => {
+            let ident = read_value_ident_async(reader).await?;
+            Ok(Element::Value(read_value_async(reader, &ident, limiter).await?))
+        },
+        ElementIdent::Some => Ok(Element::Option(Some(Box::new(read_element_async(reader, limiter).await?)))),
+        ElementIdent::None => Ok(Element::Option(None)),
+        ElementIdent::Variant => {
+            let name = read_tstring_async(reader, limiter).await?;
+            match read_element_async(reader, limiter).await {
+                Ok(value) => Ok(Element::Variant(name, Box::new(value))),
+                Err(e) => Err(e.within(PathSegment::Variant(name)))
+            }
+        },
+        ElementIdent::Struct => {
+            let end = read_container_end_async(reader, limiter).await?;
+            let mut items = ElementMap::new();
+
+            while reader.position() < end {
+                let key_start = reader.position();
+                let key = read_tstring_async(reader, limiter).await.map_err(|e| e.located(key_start))?;
+                match read_element_async(reader, limiter).await {
+                    Ok(value) => items.insert(key, value),
+                    Err(e) => return Err(e.within(PathSegment::Field(key)))
+                };
+            }
 
-                if let ValueIdent::Null = &key_type {
-                    Ok(Element::Map(ValueIdent::Null, HashMap::new()))
-                } else {
-                    let size = read_length_async(reader).await?;
-                    let mut items = HashMap::new();
-                    let mut buffer = Cursor::new(read_bytes_async(reader, size).await?);
+            check_container_end(reader.position(), end)?;
+            Ok(Element::Struct(items))
+        }
+        ElementIdent::List => {
+            let end = read_container_end_async(reader, limiter).await?;
+            let mut items = Vec::new();
+
+            while reader.position() < end {
+                match read_element_async(reader, limiter).await {
+                    Ok(value) => items.push(value),
+                    Err(e) => return Err(e.within(PathSegment::Index(items.len())))
+                }
+            }
 
-                    loop {
-                        if buffer.position() == size as u64 { break; }
+            check_container_end(reader.position(), end)?;
+            Ok(Element::List(items))
+        },
+        ElementIdent::Array => {
+            let array_type = read_value_ident_async(reader).await?;
 
-                        let key = read_value_async(&mut buffer, &key_type).await?;
-                        let value = read_element_async(&mut buffer).await?;
+            if let ValueIdent::Null = &array_type {
+                Ok(Element::Array(ValueIdent::Null, Vec::new()))
+            } else {
+                let end = read_container_end_async(reader, limiter).await?;
+                let mut items = Vec::new();
 
-                        items.insert(key, value);
+                while reader.position() < end {
+                    let item_start = reader.position();
+                    match read_value_async(reader, &array_type, limiter).await {
+                        Ok(value) => items.push(value),
+                        Err(e) => return Err(e.located(item_start).within(PathSegment::Index(items.len())))
                     }
+                }
 
-                    Ok(Element::Map(key_type, items))
+                check_container_end(reader.position(), end)?;
+                Ok(Element::Array(array_type, items))
+            }
+        },
+        ElementIdent::Map => {
+            let key_type = read_value_ident_async(reader).await?;
+
+            if let ValueIdent::Null = &key_type {
+                Ok(Element::Map(ValueIdent::Null, ElementMap::new()))
+            } else {
+                let end = read_container_end_async(reader, limiter).await?;
+                let mut items = ElementMap::new();
+
+                while reader.position() < end {
+                    let key_start = reader.position();
+                    let key = read_value_async(reader, &key_type, limiter).await.map_err(|e| e.located(key_start))?;
+                    match read_element_async(reader, limiter).await {
+                        Ok(value) => items.insert(key, value),
+                        Err(e) => return Err(e.within(PathSegment::Key(key)))
+                    };
                 }
-            },
-            ElementIdent::Compression => {
-                let size = read_length_async(reader).await?;
-                let mut buffer = Cursor::new(read_bytes_async(reader, size).await?);
-                Ok(Element::Compression(Box::new(read_element_async(&mut buffer).await?)))
+
+                check_container_end(reader.position(), end)?;
+                Ok(Element::Map(key_type, items))
             }
+        },
+        ElementIdent::Compression => {
+            let codec = parse_codec(prefix)?;
+            let size = read_length_async(reader).await?;
+            limiter.container(size)?;
+            let bytes = read_bytes_async(reader, size).await?;
+            read_compressed_async(bytes, codec, start, limiter).await
         }
-    }.boxed()
+    }
 }
 
+#[cfg(feature="compression")]
+async fn read_compressed_async(bytes: Vec<u8>, codec: Codec, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    let mut buffer = PositionReader::new(Cursor::new(decompress_limited(&bytes, codec, limiter)?));
+
+    // errors within the compressed data are located at the compression element.
+    match read_element_async(&mut buffer, limiter).await {
+        Ok(element) => Ok(Element::Compression(Compression::new(codec), Box::new(element))),
+        Err(TychoError::Located { error, path, .. }) => Err(TychoError::Located { error, offset: start, path }),
+        Err(e) => Err(e)
+    }
+}
+
+#[cfg(not(feature="compression"))]
+async fn read_compressed_async(bytes: Vec<u8>, codec: Codec, _start: u64, _limiter: &mut Limiter) -> TychoResult<Element> {
+    Ok(Element::Compression(codec, bytes))
+}
@@ -1,16 +1,20 @@
 use tokio::io::AsyncRead;
 use tokio_byteorder::AsyncReadBytesExt;
 
-use crate::error::{parse_io, TychoResult};
+use crate::error::{parse_io, TychoError, TychoResult};
 
 pub(crate) async fn read_byte_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<u8> {
     parse_io(reader.read_u8().await)
 }
 
 pub(crate) async fn read_bytes_async<R: AsyncRead + Unpin>(reader: &mut R, size: usize) -> TychoResult<Vec<u8>> {
-    let mut buffer = Vec::with_capacity(size);
-    for _ in 0..size {
-        buffer.push(read_byte_async(reader).await?);
+    // the buffer grows as bytes are read, so a large size can not allocate without the data.
+    let mut buffer = Vec::with_capacity(size.min(4096));
+    let mut limited = tokio::io::AsyncReadExt::take(reader, size as u64);
+    parse_io(tokio::io::AsyncReadExt::read_to_end(&mut limited, &mut buffer).await)?;
+
+    if buffer.len() != size {
+        return Err(TychoError::UnexpectedEof);
     }
     Ok(buffer)
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use tokio::io::AsyncRead;
 
-use crate::error::TychoResult;
+use crate::error::{TychoError, TychoResult};
 use crate::read::async_::func::read_byte_async;
 
 pub(crate) async fn read_length_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<usize> {
@@ -10,6 +10,10 @@ pub(crate) async fn read_length_async<R: AsyncRead + Unpin>(reader: &mut R) -> T
     loop {
         let byte = read_byte_async(reader).await?;
 
+        if count == 9 && byte > 0x01 {
+            return Err(TychoError::LengthOverflow);
+        }
+
         number |= ((byte & 0x7F) as u64) << (7 * count);
 
         if byte & 0x80 == 0 {
@@ -3,4 +3,5 @@ pub(crate) mod number;
 pub(crate) mod length;
 pub(crate) mod string;
 pub(crate) mod value;
-pub(crate) mod element;
\ No newline at end of file
+pub(crate) mod element;
+pub(crate) mod stream;
@@ -14,7 +14,7 @@ pub(crate) async fn read_number_ident_async<R: AsyncRead + Unpin>(reader: &mut R
 
 pub(crate) async fn read_number_async<R: AsyncRead + Unpin>(reader: &mut R, ident: &NumberIdent) -> TychoResult<Number> {
     match ident {
-        NumberIdent::Bit => Ok(Number::Bit(read_byte_async(reader).await? == 0x00)),
+        NumberIdent::Bit => Ok(Number::Bit(read_byte_async(reader).await? == 0x01)),
         NumberIdent::Unsigned8 => Ok(Number::Unsigned8(parse_io(reader.read_u8().await)?)),
         NumberIdent::Signed8 => Ok(Number::Signed8(parse_io(reader.read_i8().await)?)),
         NumberIdent::Unsigned16 => Ok(Number::Unsigned16(parse_io(reader.read_u16::<BE>().await)?)),
@@ -0,0 +1,96 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::future::{BoxFuture, FutureExt};
+use futures::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::Element;
+use crate::error::TychoResult;
+use crate::read::async_::element::read_element_after_async;
+use crate::read::options::{Limiter, ReadOptions};
+use crate::read::position::PositionReader;
+
+type NextElement<R> = BoxFuture<'static, (PositionReader<R>, Option<TychoResult<Element>>)>;
+
+/// An async stream over a sequence of concatenated elements within a readable object.
+///
+/// Elements are yielded until the reader ends cleanly between two elements.
+/// If the reader ends part way through an element, `TychoError::UnexpectedEof` is yielded.
+/// After any error, the stream ends.
+///
+/// ### Example
+/// ```
+/// use std::io::Cursor;
+/// use futures::StreamExt;
+/// use tycho::{Element, ElementStreamAsync, marshall};
+/// # let _ = tokio_test::block_on( async {
+///
+/// // Write two elements, one after the other.
+/// let mut bytes = Vec::new();
+/// marshall(&mut bytes, Element::Unit).unwrap();
+/// marshall(&mut bytes, Element::Option(None)).unwrap();
+///
+/// let elements: Vec<_> = ElementStreamAsync::new(Cursor::new(bytes)).collect().await;
+///
+/// assert_eq!(elements.len(), 2);
+/// assert_eq!(elements[1].as_ref().unwrap(), &Element::Option(None));
+/// # });
+/// ```
+pub struct ElementStreamAsync<R> {
+    reader: Option<PositionReader<R>>,
+    options: ReadOptions,
+    next: Option<NextElement<R>>,
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> ElementStreamAsync<R> {
+    /// Create a stream of the elements within a reader.
+    pub fn new(reader: R) -> Self {
+        ElementStreamAsync { reader: Some(PositionReader::new(reader)), options: ReadOptions::default(), next: None }
+    }
+
+    /// Set the limits used while reading each element.
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+async fn next_element<R: AsyncRead + Unpin + Send>(reader: &mut PositionReader<R>, options: ReadOptions) -> Option<TychoResult<Element>> {
+    // a clean end is only found before the first byte of an element.
+    let mut byte = [0u8];
+    match reader.read(&mut byte).await {
+        Ok(0) => None,
+        Ok(_) => Some(read_element_after_async(reader, byte[0], &mut Limiter::new(options)).await),
+        Err(e) => Some(Err(e.into()))
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> Stream for ElementStreamAsync<R> {
+    type Item = TychoResult<Element>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.next.is_none() {
+            let mut reader = match self.reader.take() {
+                Some(reader) => reader,
+                None => return Poll::Ready(None)
+            };
+            let options = self.options;
+            self.next = Some(async move {
+                let result = next_element(&mut reader, options).await;
+                (reader, result)
+            }.boxed());
+        }
+
+        match self.next.as_mut().unwrap().poll_unpin(cx) {
+            Poll::Ready((reader, result)) => {
+                self.next = None;
+                if let Some(Ok(_)) = &result {
+                    self.reader = Some(reader);
+                }
+                Poll::Ready(result)
+            }
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
@@ -1,18 +1,21 @@
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
-use crate::error::{TychoError, TychoResult};
+use crate::error::{parse_io, TychoError, TychoResult};
 use crate::read::async_::func::{read_byte_async, read_bytes_async};
 use crate::read::async_::length::read_length_async;
+use crate::read::options::Limiter;
+use crate::read::string::{char_width, parse_char};
 
-pub(crate) async fn read_string_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<String> {
+pub(crate) async fn read_string_async<R: AsyncRead + Unpin>(reader: &mut R, limiter: &mut Limiter) -> TychoResult<String> {
     let length = read_length_async(reader).await?;
+    limiter.string(length)?;
     match String::from_utf8(read_bytes_async(reader, length).await?) {
         Ok(s) => Ok(s),
-        Err(e) => Err(TychoError::StringError(e))
+        Err(e) => Err(TychoError::Utf8(e))
     }
 }
 
-pub(crate) async fn read_tstring_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<String> {
+pub(crate) async fn read_tstring_async<R: AsyncRead + Unpin>(reader: &mut R, limiter: &mut Limiter) -> TychoResult<String> {
     let mut buffer = Vec::new();
     loop {
         let byte = read_byte_async(reader).await?;
@@ -20,33 +23,24 @@ pub(crate) async fn read_tstring_async<R: AsyncRead + Unpin>(reader: &mut R) ->
             break;
         }
         buffer.push(byte);
+
+        // stop at the limit, rather than reading an unterminated string to the end.
+        if limiter.options.max_string_len.is_some_and(|max| buffer.len() > max) {
+            limiter.string(buffer.len())?;
+        }
     }
+    limiter.string(buffer.len())?;
     match String::from_utf8(buffer) {
         Ok(s) => Ok(s),
-        Err(e) => Err(TychoError::StringError(e))
+        Err(e) => Err(TychoError::Utf8(e))
     }
 }
 
 pub(crate) async fn read_char_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<char> {
-    let mut buffer = Vec::new();
-
-    let byte = read_byte_async(reader).await?;
-    if byte >> 7 == 0 {
-        buffer.push(byte);
-    } else {
-        let count = if byte & 0b01000000 == 0x00 { 1 }
-        else if byte & 0b00100000 == 0x00 { 2 }
-        else if byte & 0b00010000 == 0x00 { 3 }
-        else if byte & 0b00001000 == 0x00 { 4 }
-        else if byte & 0b00000100 == 0x00 { 5 }
-        else if byte & 0b00000010 == 0x00 { 6 }
-        else { 0 };
+    let mut buffer = [0u8; 4];
+    buffer[0] = read_byte_async(reader).await?;
 
-        buffer.extend_from_slice(&read_bytes_async(reader, count).await?);
-    }
-
-    match String::from_utf8(buffer) {
-        Ok(s) => Ok(s.chars().nth(0).unwrap().clone()), //todo: unwrap :(
-        Err(e) => Err(TychoError::StringError(e))
-    }
-}
\ No newline at end of file
+    let width = char_width(buffer[0]);
+    parse_io(reader.read_exact(&mut buffer[1..width]).await)?;
+    parse_char(&buffer[..width])
+}
@@ -1,11 +1,12 @@
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
-use crate::error::{TychoError, TychoResult};
+use crate::error::{parse_io, TychoError, TychoResult};
 use crate::ident::ValueIdent;
 use crate::read::async_::func::{read_byte_async, read_bytes_async};
 use crate::read::async_::length::read_length_async;
 use crate::read::async_::number::{read_number_async, read_number_ident_async};
 use crate::read::async_::string::{read_char_async, read_string_async};
+use crate::read::options::Limiter;
 use crate::{Value, Uuid};
 
 pub(crate) async fn read_value_ident_async<R: AsyncRead + Unpin>(reader: &mut R) -> TychoResult<ValueIdent> {
@@ -24,29 +25,21 @@ pub(crate) async fn read_value_ident_async<R: AsyncRead + Unpin>(reader: &mut R)
     }
 }
 
-pub(crate) async fn read_value_async<R: AsyncRead + Unpin>(reader: &mut R, ident: &ValueIdent) -> TychoResult<Value> {
+pub(crate) async fn read_value_async<R: AsyncRead + Unpin>(reader: &mut R, ident: &ValueIdent, limiter: &mut Limiter) -> TychoResult<Value> {
     match ident {
         ValueIdent::Null => Ok(Value::Null),
         ValueIdent::Boolean => Ok(Value::Boolean(read_byte_async(reader).await? == 0x01)),
-        ValueIdent::String => Ok(Value::String(read_string_async(reader).await?)),
+        ValueIdent::String => Ok(Value::String(read_string_async(reader, limiter).await?)),
         ValueIdent::Char => Ok(Value::Char(read_char_async(reader).await?)),
         ValueIdent::Number(n) => Ok(Value::Number(read_number_async(reader, n).await?)),
         ValueIdent::Bytes => {
             let length = read_length_async(reader).await?;
+            limiter.string(length)?;
             Ok(Value::Bytes(read_bytes_async(reader, length).await?))
         }
         ValueIdent::UUID => {
-            // suffering
-            let bytes = [
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-                read_byte_async(reader).await?, read_byte_async(reader).await?,
-            ];
+            let mut bytes = [0u8; 16];
+            parse_io(reader.read_exact(&mut bytes).await)?;
             Ok(Value::UUID(Uuid::from_slice(bytes)))
         }
     }
@@ -0,0 +1,96 @@
+use std::io::{self, BufRead, ErrorKind, Read};
+
+use crate::error::{TychoError, TychoResult};
+use crate::read::options::Limiter;
+
+/// A buffered reader over a readable object, which never reads past the bytes it is asked for.
+///
+/// Readers given to `unmarshall` may be read from again after the element,
+/// so no more than a single byte is held ahead of the underlying reader.
+/// Prefixes, lengths and terminated strings are read a byte per call,
+/// while the contents of sized strings, bytes and containers are read directly.
+pub(crate) struct ExactReader<R> {
+    reader: R,
+    byte: [u8; 1],
+    held: bool,
+}
+
+impl<R> ExactReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        ExactReader { reader, byte: [0], held: false }
+    }
+}
+
+impl<R: Read> Read for ExactReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.held && !buf.is_empty() {
+            buf[0] = self.byte[0];
+            self.held = false;
+            return Ok(1);
+        }
+        self.reader.read(buf)
+    }
+}
+
+impl<R: Read> BufRead for ExactReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.held {
+            match self.reader.read(&mut self.byte)? {
+                0 => return Ok(&[]),
+                _ => self.held = true
+            }
+        }
+        Ok(&self.byte)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if amount > 0 {
+            self.held = false;
+        }
+    }
+}
+
+/// Get the buffered bytes of a reader, reading more if none are buffered.
+///
+/// Interrupted reads are retried, and `TychoError::UnexpectedEof` is returned if the reader has ended.
+/// The bytes are given to a function, which returns the amount of them to consume.
+pub(crate) fn scan<R: BufRead, T, F: FnMut(&[u8]) -> (usize, T)>(reader: &mut R, mut f: F) -> TychoResult<T> {
+    loop {
+        let (used, result) = match reader.fill_buf() {
+            Ok([]) => return Err(TychoError::UnexpectedEof),
+            Ok(available) => f(available),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into())
+        };
+        reader.consume(used);
+        return Ok(result);
+    }
+}
+
+/// Read bytes up to a null terminator, consuming the terminator without including it.
+///
+/// Each buffered block is scanned for the terminator at once, rather than a byte at a time.
+pub(crate) fn read_terminated<R: BufRead>(reader: &mut R, limiter: &mut Limiter) -> TychoResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    loop {
+        let found = scan(reader, |available| match available.iter().position(|x| *x == 0x00) {
+            Some(end) => {
+                buffer.extend_from_slice(&available[..end]);
+                (end + 1, true)
+            }
+            None => {
+                buffer.extend_from_slice(available);
+                (available.len(), false)
+            }
+        })?;
+
+        if found {
+            break;
+        }
+        if limiter.options.max_string_len.is_some_and(|max| buffer.len() > max) {
+            limiter.string(buffer.len())?;
+        }
+    }
+    limiter.string(buffer.len())?;
+    Ok(buffer)
+}
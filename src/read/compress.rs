@@ -1,12 +1,47 @@
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
 use crate::error::{TychoResult, TychoError};
-use flate2::write::GzDecoder;
-use std::io::Write;
-
-pub(crate) fn decompress(bytes: Vec<u8>) -> TychoResult<Vec<u8>> {
-    let mut decoder = GzDecoder::new(Vec::new());
-    decoder.write_all(&bytes)?;
-    match decoder.finish() {
-        Ok(x) => Ok(x),
-        Err(e) => Err(TychoError::Io(e))
+use crate::read::options::Limiter;
+use crate::types::ident::Codec;
+
+#[cfg(feature="compression_brotli")]
+/// The size of the buffer used by the brotli decoder.
+const BROTLI_BUFFER: usize = 4096;
+
+/// Create a reader over the decompressed bytes.
+fn decoder<'a>(bytes: &'a [u8], codec: Codec) -> TychoResult<Box<dyn Read + 'a>> {
+    match codec {
+        Codec::Gzip => Ok(Box::new(GzDecoder::new(bytes))),
+        #[cfg(feature="compression_zstd")]
+        Codec::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(bytes).map_err(TychoError::Compression)?)),
+        #[cfg(feature="compression_lz4")]
+        Codec::Lz4 => Ok(Box::new(lz4_flex::frame::FrameDecoder::new(bytes))),
+        #[cfg(feature="compression_brotli")]
+        Codec::Brotli => Ok(Box::new(brotli::Decompressor::new(bytes, BROTLI_BUFFER))),
+        #[allow(unreachable_patterns)]
+        codec => Err(TychoError::UnsupportedCodec(codec))
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn decompress(bytes: &[u8], codec: Codec) -> TychoResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    decoder(bytes, codec)?.read_to_end(&mut buffer).map_err(TychoError::Compression)?;
+    Ok(buffer)
+}
+
+/// Decompress bytes, stopping once the decompressed data exceeds a limit.
+pub(crate) fn decompress_limited(bytes: &[u8], codec: Codec, limiter: &mut Limiter) -> TychoResult<Vec<u8>> {
+    let max = match limiter.remaining() {
+        Some(max) => max,
+        None => return decompress(bytes, codec)
+    };
+
+    let mut buffer = Vec::new();
+    decoder(bytes, codec)?.take(max as u64 + 1).read_to_end(&mut buffer)
+        .map_err(TychoError::Compression)?;
+
+    limiter.container(buffer.len())?;
+    Ok(buffer)
+}
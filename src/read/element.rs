@@ -1,18 +1,25 @@
-use std::collections::HashMap;
-use std::io::{Cursor, Read};
+use std::io::BufRead;
 
-use crate::Element;
-use crate::error::{TychoError, TychoResult};
+#[cfg(feature="compression")]
+use std::io::Cursor;
+
+use crate::{Element, ElementMap};
+use crate::error::{PathSegment, TychoError, TychoResult, TychoStatus};
 use crate::read::func::{read_byte, read_bytes};
 use crate::read::length::read_length;
+use crate::read::options::Limiter;
+use crate::read::position::PositionReader;
 use crate::read::string::read_tstring;
 use crate::read::value::{read_value, read_value_ident};
-use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::types::ident::{Codec, ElementIdent, ValueIdent};
 
 #[cfg(feature="compression")]
-use crate::read::compress::decompress;
+use crate::into::compression::Compression;
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
 
-pub(crate) fn read_element_ident<R: Read>(reader: &mut R) -> TychoResult<ElementIdent> {
+#[cfg_attr(not(any(feature="partial", feature="async_tokio")), allow(dead_code))]
+pub(crate) fn read_element_ident<R: BufRead>(reader: &mut R) -> TychoResult<ElementIdent> {
      parse_element_ident(read_byte(reader)?)
 }
 
@@ -28,53 +35,112 @@ pub(crate) fn parse_element_ident(byte: u8) -> TychoResult<ElementIdent> {
         0x07 => Ok(ElementIdent::Array),
         0x08 => Ok(ElementIdent::Map),
 
-        0xF0 => Ok(ElementIdent::Compression),
+        0xF0..=0xF3 => Ok(ElementIdent::Compression),
 
         _ => Err(TychoError::InvalidIdent { found: byte, expecting: "element ident".to_string() })
     }
 }
 
-pub(crate) fn read_element<R: Read>(reader: &mut R) -> TychoResult<Element> {
-    let ident = read_element_ident(reader)?;
+/// Get the codec of a compression element from its prefix.
+pub(crate) fn parse_codec(byte: u8) -> TychoResult<Codec> {
+    match byte {
+        0xF0 => Ok(Codec::Gzip),
+        0xF1 => Ok(Codec::Zstd),
+        0xF2 => Ok(Codec::Lz4),
+        0xF3 => Ok(Codec::Brotli),
+
+        _ => Err(TychoError::InvalidIdent { found: byte, expecting: "compression codec".to_string() })
+    }
+}
+
+pub(crate) fn read_element<R: BufRead>(reader: &mut PositionReader<R>, limiter: &mut Limiter) -> TychoResult<Element> {
+    let start = reader.position();
+    read_byte(reader)
+        .and_then(|prefix| read_element_at(reader, prefix, start, limiter))
+        .map_err(|e| e.located(start))
+}
+
+/// Read an element whose ident byte has already been read.
+pub(crate) fn read_element_after<R: BufRead>(reader: &mut PositionReader<R>, byte: u8, limiter: &mut Limiter) -> TychoResult<Element> {
+    let start = reader.position() - 1;
+    read_element_at(reader, byte, start, limiter)
+        .map_err(|e| e.located(start))
+}
+
+fn read_element_at<R: BufRead>(reader: &mut PositionReader<R>, prefix: u8, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    let ident = parse_element_ident(prefix)?;
+    limiter.enter()?;
+    let element = read_element_body(reader, ident, prefix, start, limiter);
+    limiter.exit();
+
+    element
+}
+
+/// Read a container size prefix, returning the position at which the container ends.
+fn read_container_end<R: BufRead>(reader: &mut PositionReader<R>, limiter: &mut Limiter) -> TychoResult<u64> {
+    let size = read_length(reader)?;
+    limiter.container(size)?;
+    Ok(reader.position() + size as u64)
+}
+
+/// Check that the items of a container ended at the end of the container.
+pub(crate) fn check_container_end(position: u64, end: u64) -> TychoStatus {
+    if position == end {
+        Ok(())
+    } else {
+        Err(TychoError::ContainerOverflow)
+    }
+}
+
+/// Read an item which is not an element, locating any error at its start.
+fn read_item<R: BufRead, T, F: FnOnce(&mut PositionReader<R>) -> TychoResult<T>>(reader: &mut PositionReader<R>, f: F) -> TychoResult<T> {
+    let start = reader.position();
+    f(reader).map_err(|e| e.located(start))
+}
 
+fn read_element_body<R: BufRead>(reader: &mut PositionReader<R>, ident: ElementIdent, prefix: u8, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
     match ident {
         ElementIdent::Unit => Ok(Element::Unit),
         ElementIdent::Value => {
             let ident = read_value_ident(reader)?;
-            Ok(Element::Value(read_value(reader, &ident)?))
+            Ok(Element::Value(read_value(reader, &ident, limiter)?))
         },
-        ElementIdent::Some => Ok(Element::Option(Some(Box::new(read_element(reader)?)))),
+        ElementIdent::Some => Ok(Element::Option(Some(Box::new(read_element(reader, limiter)?)))),
         ElementIdent::None => Ok(Element::Option(None)),
-        ElementIdent::Variant => Ok(Element::Variant(
-            read_tstring(reader)?,
-            Box::new(read_element(reader)?)
-        )),
+        ElementIdent::Variant => {
+            let name = read_tstring(reader, limiter)?;
+            match read_element(reader, limiter) {
+                Ok(value) => Ok(Element::Variant(name, Box::new(value))),
+                Err(e) => Err(e.within(PathSegment::Variant(name)))
+            }
+        },
         ElementIdent::Struct => {
-            let size = read_length(reader)?;
-            let mut items = HashMap::new();
-            let mut buffer = Cursor::new(read_bytes(reader, size)?);
-
-            loop {
-                if buffer.position() == size as u64 { break; }
-
-                let key = read_tstring(&mut buffer)?;
-                let value = read_element(&mut buffer)?;
-
-                items.insert(key, value);
+            let end = read_container_end(reader, limiter)?;
+            let mut items = ElementMap::new();
+
+            while reader.position() < end {
+                let key = read_item(reader, |reader| read_tstring(reader, limiter))?;
+                match read_element(reader, limiter) {
+                    Ok(value) => items.insert(key, value),
+                    Err(e) => return Err(e.within(PathSegment::Field(key)))
+                };
             }
 
+            check_container_end(reader.position(), end)?;
             Ok(Element::Struct(items))
         }
         ElementIdent::List => {
-            let size = read_length(reader)?;
+            let end = read_container_end(reader, limiter)?;
             let mut items = Vec::new();
-            let mut buffer = Cursor::new(read_bytes(reader, size)?);
 
-            loop {
-                if buffer.position() == size as u64 { break; }
-                items.push(read_element(&mut buffer)?);
+            while reader.position() < end {
+                match read_element(reader, limiter) {
+                    Ok(value) => items.push(value),
+                    Err(e) => return Err(e.within(PathSegment::Index(items.len())))
+                }
             }
 
+            check_container_end(reader.position(), end)?;
             Ok(Element::List(items))
         },
         ElementIdent::Array => {
@@ -83,15 +149,17 @@ pub(crate) fn read_element<R: Read>(reader: &mut R) -> TychoResult<Element> {
             if let ValueIdent::Null = &array_type {
                 Ok(Element::Array(ValueIdent::Null, Vec::new()))
             } else {
-                let size = read_length(reader)?;
+                let end = read_container_end(reader, limiter)?;
                 let mut items = Vec::new();
-                let mut buffer = Cursor::new(read_bytes(reader, size)?);
 
-                loop {
-                    if buffer.position() == size as u64 { break; }
-                    items.push(read_value(&mut buffer, &array_type)?);
+                while reader.position() < end {
+                    match read_item(reader, |reader| read_value(reader, &array_type, limiter)) {
+                        Ok(value) => items.push(value),
+                        Err(e) => return Err(e.within(PathSegment::Index(items.len())))
+                    }
                 }
 
+                check_container_end(reader.position(), end)?;
                 Ok(Element::Array(array_type, items))
             }
         },
@@ -99,36 +167,52 @@ pub(crate) fn read_element<R: Read>(reader: &mut R) -> TychoResult<Element> {
             let key_type = read_value_ident(reader)?;
 
             if let ValueIdent::Null = &key_type {
-                Ok(Element::Map(ValueIdent::Null, HashMap::new()))
+                Ok(Element::Map(ValueIdent::Null, ElementMap::new()))
             } else {
-                let size = read_length(reader)?;
-                let mut items = HashMap::new();
-                let mut buffer = Cursor::new(read_bytes(reader, size)?);
-
-                loop {
-                    if buffer.position() == size as u64 { break; }
-
-                    let key = read_value(&mut buffer, &key_type)?;
-                    let value = read_element(&mut buffer)?;
-
-                    items.insert(key, value);
+                let end = read_container_end(reader, limiter)?;
+                let mut items = ElementMap::new();
+
+                while reader.position() < end {
+                    let key = read_item(reader, |reader| read_value(reader, &key_type, limiter))?;
+                    match read_element(reader, limiter) {
+                        Ok(value) => items.insert(key, value),
+                        Err(e) => return Err(e.within(PathSegment::Key(key)))
+                    };
                 }
 
+                check_container_end(reader.position(), end)?;
                 Ok(Element::Map(key_type, items))
             }
         },
         ElementIdent::Compression => {
+            let codec = parse_codec(prefix)?;
             let size = read_length(reader)?;
+            limiter.container(size)?;
             let bytes = read_bytes(reader, size)?;
-
-            #[cfg(not(feature="compression"))]
-            return Ok(Element::Compression(bytes));
-
-            #[cfg(feature="compression")]
-            let mut buffer = Cursor::new(decompress(bytes)?);
-            #[cfg(feature="compression")]
-            return Ok(Element::Compression(Box::new(read_element(&mut buffer)?)))
+            read_compressed(bytes, codec, start, limiter)
         }
     }
 }
 
+#[cfg(feature="compression")]
+pub(crate) fn read_compressed(bytes: Vec<u8>, codec: Codec, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    let element = read_decompressed(&bytes, codec, start, limiter)?;
+    Ok(Element::Compression(Compression::new(codec), Box::new(element)))
+}
+
+#[cfg(not(feature="compression"))]
+pub(crate) fn read_compressed(bytes: Vec<u8>, codec: Codec, _start: u64, _limiter: &mut Limiter) -> TychoResult<Element> {
+    Ok(Element::Compression(codec, bytes))
+}
+
+#[cfg(feature="compression")]
+/// Decompress and read the element within a compression element.
+pub(crate) fn read_decompressed(bytes: &[u8], codec: Codec, start: u64, limiter: &mut Limiter) -> TychoResult<Element> {
+    let mut buffer = PositionReader::new(Cursor::new(decompress_limited(bytes, codec, limiter)?));
+
+    // errors within the compressed data are located at the compression element.
+    match read_element(&mut buffer, limiter) {
+        Err(TychoError::Located { error, path, .. }) => Err(TychoError::Located { error, offset: start, path }),
+        result => result
+    }
+}
@@ -1,17 +1,19 @@
-use std::io::Read;
+use std::io::{BufRead, Read};
 
-use byteorder::ReadBytesExt;
+use crate::error::{parse_io, TychoError, TychoResult};
+use crate::read::buffer::scan;
 
-use crate::error::{parse_io, TychoResult};
-
-pub(crate) fn read_byte<R: Read>(reader: &mut R) -> TychoResult<u8> {
-    parse_io(reader.read_u8())
+pub(crate) fn read_byte<R: BufRead>(reader: &mut R) -> TychoResult<u8> {
+    scan(reader, |available| (1, available[0]))
 }
 
 pub(crate) fn read_bytes<R: Read>(reader: &mut R, size: usize) -> TychoResult<Vec<u8>> {
-    let mut buffer = Vec::with_capacity(size);
-    for _ in 0..size {
-        buffer.push(read_byte(reader)?);
+    // the buffer grows as bytes are read, so a large size can not allocate without the data.
+    let mut buffer = Vec::with_capacity(size.min(4096));
+    parse_io(reader.take(size as u64).read_to_end(&mut buffer))?;
+
+    if buffer.len() != size {
+        return Err(TychoError::UnexpectedEof);
     }
     Ok(buffer)
 }
\ No newline at end of file
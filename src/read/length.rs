@@ -1,21 +1,35 @@
-use std::io::Read;
+use std::io::BufRead;
 
-use crate::error::TychoResult;
-use crate::read::func::read_byte;
+use crate::error::{TychoError, TychoResult};
+use crate::read::buffer::scan;
 
-pub(crate) fn read_length<R: Read>(reader: &mut R) -> TychoResult<usize> {
+pub(crate) fn read_length<R: BufRead>(reader: &mut R) -> TychoResult<usize> {
     let mut number: u64 = 0;
     let mut count = 0;
 
+    // the continuation bits are scanned over each buffered block at once.
     loop {
-        let byte = read_byte(reader)?;
+        let end = scan(reader, |available| {
+            let mut used = 0;
+            for byte in available {
+                if count == 9 && *byte > 0x01 {
+                    return (used, Err(TychoError::LengthOverflow));
+                }
 
-        number |= ((byte & 0x7F) as u64) << (7 * count);
+                number |= ((byte & 0x7F) as u64) << (7 * count);
+                used += 1;
 
-        if byte & 0x80 == 0 {
+                if byte & 0x80 == 0 {
+                    return (used, Ok(true));
+                }
+
+                count += 1;
+            }
+            (used, Ok(false))
+        })??;
+
+        if end {
             return Ok(number as usize);
         }
-
-        count += 1;
     }
-}
\ No newline at end of file
+}
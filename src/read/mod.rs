@@ -1,9 +1,14 @@
 pub(crate) mod length;
 pub(crate) mod func;
+pub(crate) mod buffer;
 pub(crate) mod number;
 pub(crate) mod value;
 pub(crate) mod string;
 pub(crate) mod element;
+pub(crate) mod slice;
+pub(crate) mod options;
+pub(crate) mod position;
+pub(crate) mod stream;
 
 #[cfg(feature="async_tokio")]
 pub(crate) mod async_;
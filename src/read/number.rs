@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::BufRead;
 
 use byteorder::{BE, ReadBytesExt};
 
@@ -7,7 +7,7 @@ use crate::Number;
 use crate::read::func::read_byte;
 use crate::types::ident::NumberIdent;
 
-pub(crate) fn read_number_ident<R: Read>(reader: &mut R) -> TychoResult<NumberIdent> {
+pub(crate) fn read_number_ident<R: BufRead>(reader: &mut R) -> TychoResult<NumberIdent> {
     parse_number_ident(read_byte(reader)?)
 }
 
@@ -31,9 +31,9 @@ pub(crate) fn parse_number_ident(byte: u8) -> TychoResult<NumberIdent> {
     }
 }
 
-pub(crate) fn read_number<R: Read>(reader: &mut R, ident: &NumberIdent) -> TychoResult<Number> {
+pub(crate) fn read_number<R: BufRead>(reader: &mut R, ident: &NumberIdent) -> TychoResult<Number> {
     match ident {
-        NumberIdent::Bit => Ok(Number::Bit(read_byte(reader)? == 0x00)),
+        NumberIdent::Bit => Ok(Number::Bit(read_byte(reader)? == 0x01)),
         NumberIdent::Unsigned8 => Ok(Number::Unsigned8(parse_io(reader.read_u8())?)),
         NumberIdent::Signed8 => Ok(Number::Signed8(parse_io(reader.read_i8())?)),
         NumberIdent::Unsigned16 => Ok(Number::Unsigned16(parse_io(reader.read_u16::<BE>())?)),
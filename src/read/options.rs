@@ -0,0 +1,106 @@
+use crate::error::{Limit, TychoError, TychoStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Limits on the resources used while reading, for handling untrusted input.
+///
+/// Each limit is unbounded when `None`, which is the default.
+///
+/// ### Example
+/// ```
+/// use tycho::{ReadOptions, unmarshall_with};
+/// use tycho::error::{Limit, TychoError};
+/// use std::io::Cursor;
+///
+/// let options = ReadOptions {
+///     max_depth: Some(16),
+///     max_container_bytes: Some(1024),
+///     ..ReadOptions::default()
+/// };
+///
+/// // A list claiming to be 4GB in size.
+/// let mut bytes = Cursor::new(vec![6, 128, 128, 128, 128, 16]);
+///
+/// match unmarshall_with(&mut bytes, options).unwrap_err().inner() {
+///     TychoError::LimitExceeded { limit, .. } => assert_eq!(limit, &Limit::ContainerBytes),
+///     _ => panic!("expected limit to be exceeded")
+/// }
+/// ```
+pub struct ReadOptions {
+    /// The maximum depth of nested elements (options, variants and containers).
+    pub max_depth: Option<usize>,
+
+    /// The maximum size of a single container or compressed element, in bytes.
+    pub max_container_bytes: Option<usize>,
+
+    /// The maximum length of a single string, key or bytes value, in bytes.
+    pub max_string_len: Option<usize>,
+
+    /// The maximum total size of all containers, strings and bytes read, in bytes.
+    ///
+    /// Containers are counted at every level of nesting.
+    pub max_total_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Tracks the resources used while reading against a set of options.
+///
+/// Public so it can be used by the view container trait, but not exported.
+pub struct Limiter {
+    pub(crate) options: ReadOptions,
+    depth: usize,
+    total: usize,
+}
+
+impl Limiter {
+    pub(crate) fn new(options: ReadOptions) -> Self {
+        Limiter { options, depth: 0, total: 0 }
+    }
+
+    /// Enter a nested element.
+    ///
+    /// The depth is only increased when within the limit, so a failed enter must not be followed by an exit.
+    pub(crate) fn enter(&mut self) -> TychoStatus {
+        check(Limit::Depth, self.depth + 1, self.options.max_depth)?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leave a nested element.
+    pub(crate) fn exit(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// Account for a container of a given size.
+    pub(crate) fn container(&mut self, size: usize) -> TychoStatus {
+        check(Limit::ContainerBytes, size, self.options.max_container_bytes)?;
+        self.total(size)
+    }
+
+    /// Account for a string or bytes value of a given length.
+    pub(crate) fn string(&mut self, size: usize) -> TychoStatus {
+        check(Limit::StringLength, size, self.options.max_string_len)?;
+        self.total(size)
+    }
+
+    fn total(&mut self, size: usize) -> TychoStatus {
+        self.total = self.total.saturating_add(size);
+        check(Limit::TotalBytes, self.total, self.options.max_total_bytes)
+    }
+
+    #[cfg(feature="compression")]
+    /// The most bytes a container may hold before exceeding a limit.
+    pub(crate) fn remaining(&self) -> Option<usize> {
+        let total = self.options.max_total_bytes.map(|x| x.saturating_sub(self.total));
+        match (self.options.max_container_bytes, total) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        }
+    }
+}
+
+fn check(limit: Limit, size: usize, max: Option<usize>) -> TychoStatus {
+    match max {
+        Some(max) if size > max => Err(TychoError::LimitExceeded { limit, max }),
+        _ => Ok(())
+    }
+}
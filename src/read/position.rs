@@ -0,0 +1,63 @@
+use std::io::{self, BufRead, Read};
+
+#[cfg(feature="async_tokio")]
+use std::pin::Pin;
+#[cfg(feature="async_tokio")]
+use std::task::{Context, Poll};
+#[cfg(feature="async_tokio")]
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// A reader which keeps track of its position, used to locate errors.
+pub(crate) struct PositionReader<R> {
+    reader: R,
+    position: u64
+}
+
+impl<R> PositionReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        PositionReader { reader, position: 0 }
+    }
+
+    /// Get the amount of bytes read.
+    pub(crate) fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Get the underlying reader.
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Read for PositionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for PositionReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount);
+        self.position += amount as u64;
+    }
+}
+
+#[cfg(feature="async_tokio")]
+impl<R: AsyncRead + Unpin> AsyncRead for PositionReader<R> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        match Pin::new(&mut self.reader).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                self.position += (buf.filled().len() - filled) as u64;
+                Poll::Ready(Ok(()))
+            }
+            x => x
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+use crate::error::{TychoError, TychoResult};
+use crate::read::length::read_length;
+use crate::read::string::parse_str;
+
+pub(crate) fn read_slice<'a>(bytes: &mut &'a [u8], size: usize) -> TychoResult<&'a [u8]> {
+    if bytes.len() < size {
+        return Err(TychoError::UnexpectedEof);
+    }
+    let (head, tail) = bytes.split_at(size);
+    *bytes = tail;
+    Ok(head)
+}
+
+pub(crate) fn read_sized_slice<'a>(bytes: &mut &'a [u8]) -> TychoResult<&'a [u8]> {
+    let size = read_length(bytes)?;
+    read_slice(bytes, size)
+}
+
+pub(crate) fn read_tslice<'a>(bytes: &mut &'a [u8]) -> TychoResult<&'a [u8]> {
+    match bytes.iter().position(|x| *x == 0x00) {
+        Some(size) => {
+            let head = &bytes[..size];
+            *bytes = &bytes[size + 1..];
+            Ok(head)
+        }
+        None => Err(TychoError::UnexpectedEof)
+    }
+}
+
+pub(crate) fn read_str<'a>(bytes: &mut &'a [u8]) -> TychoResult<&'a str> {
+    parse_str(read_sized_slice(bytes)?)
+}
+
+pub(crate) fn read_tstr<'a>(bytes: &mut &'a [u8]) -> TychoResult<&'a str> {
+    parse_str(read_tslice(bytes)?)
+}
@@ -0,0 +1,84 @@
+use std::io::{BufReader, ErrorKind, Read};
+
+use crate::Element;
+use crate::error::TychoResult;
+use crate::read::element::read_element_after;
+use crate::read::options::{Limiter, ReadOptions};
+use crate::read::position::PositionReader;
+
+/// An iterator over a sequence of concatenated elements within a readable object.
+///
+/// Elements are yielded until the reader ends cleanly between two elements.
+/// If the reader ends part way through an element, `TychoError::UnexpectedEof` is yielded.
+/// After any error, the stream ends.
+///
+/// ### Example
+/// ```
+/// use std::io::Cursor;
+/// use tycho::{Element, ElementStream, marshall};
+///
+/// // Write two elements, one after the other.
+/// let mut bytes = Vec::new();
+/// marshall(&mut bytes, Element::Unit).unwrap();
+/// marshall(&mut bytes, Element::Option(None)).unwrap();
+///
+/// let elements = ElementStream::new(Cursor::new(bytes))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(elements, vec![Element::Unit, Element::Option(None)]);
+/// ```
+pub struct ElementStream<R> {
+    reader: PositionReader<BufReader<R>>,
+    options: ReadOptions,
+    finished: bool,
+}
+
+impl<R: Read> ElementStream<R> {
+    /// Create a stream of the elements within a reader.
+    pub fn new(reader: R) -> Self {
+        ElementStream { reader: PositionReader::new(BufReader::new(reader)), options: ReadOptions::default(), finished: false }
+    }
+
+    /// Set the limits used while reading each element.
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Get the amount of bytes read from the reader.
+    pub fn position(&self) -> u64 {
+        self.reader.position()
+    }
+
+    /// Get the underlying reader.
+    ///
+    /// The reader is buffered internally, so any bytes read past the last element are lost.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner().into_inner()
+    }
+}
+
+impl<R: Read> Iterator for ElementStream<R> {
+    type Item = TychoResult<Element>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        // a clean end is only found before the first byte of an element.
+        let mut byte = [0u8];
+        let result = loop {
+            match self.reader.read(&mut byte) {
+                Ok(0) => break None,
+                Ok(_) => break Some(read_element_after(&mut self.reader, byte[0], &mut Limiter::new(self.options))),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => break Some(Err(e.into()))
+            }
+        };
+
+        self.finished = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
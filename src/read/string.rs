@@ -1,53 +1,55 @@
-use std::io::Read;
+use std::io::BufRead;
 
-use crate::error::{TychoError, TychoResult};
+use crate::error::{parse_io, TychoError, TychoResult};
+use crate::read::buffer::read_terminated;
 use crate::read::func::{read_byte, read_bytes};
 use crate::read::length::read_length;
+use crate::read::options::Limiter;
 
-pub(crate) fn read_string<R: Read>(reader: &mut R) -> TychoResult<String> {
-    let length = read_length(reader)?;
-    match String::from_utf8(read_bytes(reader, length)?) {
+pub(crate) fn parse_string(bytes: Vec<u8>) -> TychoResult<String> {
+    match String::from_utf8(bytes) {
         Ok(s) => Ok(s),
-        Err(e) => Err(TychoError::StringError(e))
+        Err(e) => Err(TychoError::Utf8(e))
     }
 }
 
-pub(crate) fn read_tstring<R: Read>(reader: &mut R) -> TychoResult<String> {
-    let mut buffer = Vec::new();
-    loop {
-        let byte = read_byte(reader)?;
-        if byte == 0x00 {
-            break;
-        }
-        buffer.push(byte);
-    }
-    match String::from_utf8(buffer) {
+pub(crate) fn parse_str(bytes: &[u8]) -> TychoResult<&str> {
+    match std::str::from_utf8(bytes) {
         Ok(s) => Ok(s),
-        Err(e) => Err(TychoError::StringError(e))
+        // Copy the bytes so the error matches the owned case.
+        Err(_) => Err(parse_string(bytes.to_vec()).unwrap_err())
     }
 }
 
-pub(crate) fn read_char<R: Read>(reader: &mut R) -> TychoResult<char> {
-    let mut buffer = Vec::new();
-
-    let byte = read_byte(reader)?;
-    if byte >> 7 == 0 {
-        buffer.push(byte);
-    } else {
-        let count = if byte & 0b01000000 == 0x00 { 1 }
-        else if byte & 0b00100000 == 0x00 { 2 }
-        else if byte & 0b00010000 == 0x00 { 3 }
-        else if byte & 0b00001000 == 0x00 { 4 }
-        else if byte & 0b00000100 == 0x00 { 5 }
-        else if byte & 0b00000010 == 0x00 { 6 }
-        else { 0 };
-
-        buffer.extend_from_slice(&read_bytes(reader, count)?);
-    }
+pub(crate) fn read_string<R: BufRead>(reader: &mut R, limiter: &mut Limiter) -> TychoResult<String> {
+    let length = read_length(reader)?;
+    limiter.string(length)?;
+    parse_string(read_bytes(reader, length)?)
+}
 
+pub(crate) fn read_tstring<R: BufRead>(reader: &mut R, limiter: &mut Limiter) -> TychoResult<String> {
+    parse_string(read_terminated(reader, limiter)?)
+}
 
-    match String::from_utf8(buffer) {
-        Ok(s) => Ok(s.chars().nth(0).unwrap().clone()), //todo: unwrap :(
-        Err(e) => Err(TychoError::StringError(e))
+/// Get the width of a utf-8 encoded character from its first byte.
+///
+/// Invalid first bytes are given a width of one, so they fail to parse.
+pub(crate) fn char_width(byte: u8) -> usize {
+    match byte.leading_ones() {
+        n @ 2..=4 => n as usize,
+        _ => 1
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn parse_char(bytes: &[u8]) -> TychoResult<char> {
+    parse_str(bytes)?.chars().next().ok_or(TychoError::UnexpectedEof)
+}
+
+pub(crate) fn read_char<R: BufRead>(reader: &mut R) -> TychoResult<char> {
+    let mut buffer = [0u8; 4];
+    buffer[0] = read_byte(reader)?;
+
+    let width = char_width(buffer[0]);
+    parse_io(reader.read_exact(&mut buffer[1..width]))?;
+    parse_char(&buffer[..width])
+}
@@ -1,15 +1,18 @@
-use std::io::Read;
+use std::io::BufRead;
 
 
-use crate::error::{TychoError, TychoResult};
+use crate::error::{parse_io, TychoError, TychoResult};
 use crate::ident::ValueIdent;
+#[cfg(feature="partial")]
+use crate::ident::NumberIdent;
 use crate::read::func::{read_byte, read_bytes};
 use crate::read::length::read_length;
 use crate::read::number::{read_number, read_number_ident};
+use crate::read::options::Limiter;
 use crate::read::string::{read_char, read_string};
 use crate::{Value, Uuid};
 
-pub(crate) fn read_value_ident<R: Read>(reader: &mut R) -> TychoResult<ValueIdent> {
+pub(crate) fn read_value_ident<R: BufRead>(reader: &mut R) -> TychoResult<ValueIdent> {
     let byte = read_byte(reader)?;
 
     match byte {
@@ -25,26 +28,39 @@ pub(crate) fn read_value_ident<R: Read>(reader: &mut R) -> TychoResult<ValueIden
     }
 }
 
-pub(crate) fn read_value<R: Read>(reader: &mut R, ident: &ValueIdent) -> TychoResult<Value> {
+pub(crate) fn read_value<R: BufRead>(reader: &mut R, ident: &ValueIdent, limiter: &mut Limiter) -> TychoResult<Value> {
     match ident {
         ValueIdent::Null => Ok(Value::Null),
         ValueIdent::Boolean => Ok(Value::Boolean(read_byte(reader)? == 0x01)),
-        ValueIdent::String => Ok(Value::String(read_string(reader)?)),
+        ValueIdent::String => Ok(Value::String(read_string(reader, limiter)?)),
         ValueIdent::Char => Ok(Value::Char(read_char(reader)?)),
         ValueIdent::Number(n) => Ok(Value::Number(read_number(reader, n)?)),
         ValueIdent::Bytes => {
             let length = read_length(reader)?;
+            limiter.string(length)?;
             Ok(Value::Bytes(read_bytes(reader, length)?))
         }
-        // todo: slow + suffering
         ValueIdent::UUID => {
-            let bytes = [
-                read_byte(reader)?, read_byte(reader)?, read_byte(reader)?, read_byte(reader)?,
-                read_byte(reader)?, read_byte(reader)?, read_byte(reader)?, read_byte(reader)?,
-                read_byte(reader)?, read_byte(reader)?, read_byte(reader)?, read_byte(reader)?,
-                read_byte(reader)?, read_byte(reader)?, read_byte(reader)?, read_byte(reader)?,
-            ];
+            let mut bytes = [0u8; 16];
+            parse_io(reader.read_exact(&mut bytes))?;
             Ok(Value::UUID(Uuid::from_slice(bytes)))
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(feature="partial")]
+/// The size of a value with a fixed width, or `None` if its size depends on its contents.
+pub(crate) fn value_width(ident: &ValueIdent) -> Option<u64> {
+    match ident {
+        ValueIdent::Null => Some(0),
+        ValueIdent::Boolean => Some(1),
+        ValueIdent::Number(n) => Some(match n {
+            NumberIdent::Bit | NumberIdent::Unsigned8 | NumberIdent::Signed8 => 1,
+            NumberIdent::Unsigned16 | NumberIdent::Signed16 => 2,
+            NumberIdent::Unsigned32 | NumberIdent::Signed32 | NumberIdent::Float32 => 4,
+            NumberIdent::Unsigned64 | NumberIdent::Signed64 | NumberIdent::Float64 => 8,
+            NumberIdent::Unsigned128 | NumberIdent::Signed128 => 16,
+        }),
+        ValueIdent::UUID => Some(16),
+        ValueIdent::String | ValueIdent::Char | ValueIdent::Bytes => None,
+    }
+}
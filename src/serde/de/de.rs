@@ -7,9 +7,36 @@ use crate::serde::de::map::MapDeserializer;
 use crate::serde::de::seq::{SeqArrayDeserializer, SeqListDeserializer};
 use crate::serde::de::struct_::StructDeserializer;
 use crate::serde::de::variant::EnumDeserializer;
+use crate::uuid::UUID_NAME;
 
 pub struct TychoDeserializer(Element);
 
+pub(crate) fn visit_value<'de, V: Visitor<'de>>(value: Value, visitor: V) -> Result<V::Value, TychoError> {
+    match value {
+        Value::Null => visitor.visit_unit(),
+        Value::Boolean(v) => visitor.visit_bool(v),
+        Value::String(v) => visitor.visit_string(v),
+        Value::Char(v) => visitor.visit_char(v),
+        Value::Number(number) => match number {
+            Number::Bit(v) => visitor.visit_bool(v),
+            Number::Unsigned8(v) => visitor.visit_u8(v),
+            Number::Signed8(v) => visitor.visit_i8(v),
+            Number::Unsigned16(v) => visitor.visit_u16(v),
+            Number::Signed16(v) => visitor.visit_i16(v),
+            Number::Unsigned32(v) => visitor.visit_u32(v),
+            Number::Signed32(v) => visitor.visit_i32(v),
+            Number::Unsigned64(v) => visitor.visit_u64(v),
+            Number::Signed64(v) => visitor.visit_i64(v),
+            Number::Unsigned128(v) => visitor.visit_u128(v),
+            Number::Signed128(v) => visitor.visit_i128(v),
+            Number::Float32(v) => visitor.visit_f32(v),
+            Number::Float64(v) => visitor.visit_f64(v)
+        }
+        Value::Bytes(v) => visitor.visit_byte_buf(v),
+        Value::UUID(v) => visitor.visit_bytes(&v.bytes())
+    }
+}
+
 impl TychoDeserializer {
     pub fn new(e: Element) -> Self {
         Self(e)
@@ -50,29 +77,7 @@ impl<'de> Deserializer<'de> for TychoDeserializer {
         V: Visitor<'de> {
         match self.0 {
             Element::Unit => visitor.visit_unit(),
-            Element::Value(value) => match value {
-                Value::Null => visitor.visit_unit(),
-                Value::Boolean(v) => visitor.visit_bool(v),
-                Value::String(v) => visitor.visit_string(v),
-                Value::Char(v) => visitor.visit_char(v),
-                Value::Number(number) => match number {
-                    Number::Bit(v) => visitor.visit_bool(v),
-                    Number::Unsigned8(v) => visitor.visit_u8(v),
-                    Number::Signed8(v) => visitor.visit_i8(v),
-                    Number::Unsigned16(v) => visitor.visit_u16(v),
-                    Number::Signed16(v) => visitor.visit_i16(v),
-                    Number::Unsigned32(v) => visitor.visit_u32(v),
-                    Number::Signed32(v) => visitor.visit_i32(v),
-                    Number::Unsigned64(v) => visitor.visit_u64(v),
-                    Number::Signed64(v) => visitor.visit_i64(v),
-                    Number::Unsigned128(_) => visitor.visit_unit(), //todo: fix this shit
-                    Number::Signed128(_) => visitor.visit_unit(), //todo: fix this shit
-                    Number::Float32(v) => visitor.visit_f32(v),
-                    Number::Float64(v) => visitor.visit_f64(v)
-                }
-                Value::Bytes(v) => visitor.visit_byte_buf(v),
-                Value::UUID(v) => visitor.visit_bytes(&v.bytes())
-            },
+            Element::Value(value) => visit_value(value, visitor),
             Element::Option(option) => match option {
                 Some(v) => visitor.visit_some(TychoDeserializer::new(*v)),
                 None => visitor.visit_none()
@@ -84,10 +89,10 @@ impl<'de> Deserializer<'de> for TychoDeserializer {
             Element::Map(_, x) => visitor.visit_map(MapDeserializer::new(x)),
 
             #[cfg(feature="compression")]
-            Element::Compression(x) => TychoDeserializer::new(*x).deserialize_any(visitor),
+            Element::Compression(_, x) => TychoDeserializer::new(*x).deserialize_any(visitor),
 
             #[cfg(not(feature="compression"))]
-            Element::Compression(x) => TychoDeserializer::new(Element::Value(Value::Bytes(x))).deserialize_bytes(visitor),
+            Element::Compression(_, x) => TychoDeserializer::new(Element::Value(Value::Bytes(x))).deserialize_bytes(visitor),
         }
     }
 
@@ -118,6 +123,12 @@ impl<'de> Deserializer<'de> for TychoDeserializer {
     fn deserialize_u64<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
         where V: Visitor<'de> { self.deserialize_any(visitor) }
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+        where V: Visitor<'de> { self.deserialize_any(visitor) }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+        where V: Visitor<'de> { self.deserialize_any(visitor) }
+
     fn deserialize_f32<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
         where V: Visitor<'de> { self.deserialize_any(visitor) }
 
@@ -150,8 +161,12 @@ impl<'de> Deserializer<'de> for TychoDeserializer {
         self.deserialize_any(visitor)
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
         V: Visitor<'de> {
+        if name == UUID_NAME {
+            return self.deserialize_any(visitor);
+        }
+
         // todo; unsure if this is correct
         visitor.visit_newtype_struct(self)
     }
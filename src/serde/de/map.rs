@@ -1,19 +1,17 @@
-use std::collections::HashMap;
-
 use serde::de::{DeserializeSeed, MapAccess};
 
-use crate::{Element, Value};
+use crate::{Element, ElementMap, Value};
 use crate::error::TychoError;
 use crate::serde::de::TychoDeserializer;
 
 pub struct MapDeserializer {
-    map: HashMap<Value, Element>,
+    iter: <ElementMap<Value, Element> as IntoIterator>::IntoIter,
     value: Option<Element>
 }
 
 impl MapDeserializer {
-    pub fn new(m: HashMap<Value, Element>) -> Self {
-        Self { map: m, value: None}
+    pub fn new(m: ElementMap<Value, Element>) -> Self {
+        Self { iter: m.into_iter(), value: None }
     }
 }
 
@@ -22,15 +20,12 @@ impl<'de> MapAccess<'de> for MapDeserializer {
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<<K as DeserializeSeed<'de>>::Value>, Self::Error> where
         K: DeserializeSeed<'de> {
-        if self.map.len() == 0 {
-            return Ok(None)
-        } else {
-            let f_key = self.map.keys().nth(0).unwrap().clone();
-            let (key, value) = self.map.remove_entry(&f_key).unwrap();
-
-            self.value = Some(value);
-
-            Ok(Some(seed.deserialize(TychoDeserializer::new(Element::Value(key)))?))
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                Ok(Some(seed.deserialize(TychoDeserializer::new(Element::Value(key)))?))
+            }
+            None => Ok(None)
         }
     }
 
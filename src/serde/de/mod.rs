@@ -1,9 +1,10 @@
 pub use de::TychoDeserializer;
+pub(crate) use de::visit_value;
 
 mod seq;
 mod de;
 mod map;
 mod struct_;
-mod ident;
+pub(crate) mod ident;
 mod variant;
 
@@ -1,21 +1,19 @@
-use std::collections::HashMap;
-
 use serde::de::{DeserializeSeed, MapAccess};
 
-use crate::Element;
+use crate::{Element, ElementMap};
 use crate::error::TychoError;
 use crate::serde::de::ident::TychoIdentDeserializer;
 use crate::serde::de::TychoDeserializer;
 
 pub struct StructDeserializer {
-    map: HashMap<String, Element>,
+    iter: <ElementMap<String, Element> as IntoIterator>::IntoIter,
     value: Option<Element>
 }
 
 impl StructDeserializer {
-    pub fn new(m: HashMap<String, Element>) -> Self {
+    pub fn new(m: ElementMap<String, Element>) -> Self {
         Self {
-            map: m,
+            iter: m.into_iter(),
             value: None
         }
     }
@@ -26,15 +24,12 @@ impl<'de> MapAccess<'de> for StructDeserializer {
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<<K as DeserializeSeed<'de>>::Value>, Self::Error> where
         K: DeserializeSeed<'de> {
-        if self.map.len() == 0 {
-            return Ok(None)
-        } else {
-            let f_key = self.map.keys().nth(0).unwrap().clone();
-            let (key, value) = self.map.remove_entry(&f_key).unwrap();
-
-            self.value = Some(value);
-
-            Ok(Some(seed.deserialize(TychoIdentDeserializer::new(&key))?))
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                Ok(Some(seed.deserialize(TychoIdentDeserializer::new(&key))?))
+            }
+            None => Ok(None)
         }
     }
 
@@ -1,3 +1,5 @@
 pub(crate) mod ser;
 pub(crate) mod de;
-//pub(crate) mod types;
\ No newline at end of file
+pub(crate) mod write;
+pub(crate) mod read;
+//pub(crate) mod types;
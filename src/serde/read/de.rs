@@ -0,0 +1,151 @@
+use serde::de::Visitor;
+use serde::Deserializer;
+use serde::forward_to_deserialize_any;
+
+use crate::error::{TychoError, TychoResult, TychoStatus};
+use crate::read::element::parse_element_ident;
+use crate::read::func::{read_byte, read_bytes};
+use crate::read::length::read_length;
+use crate::read::string::read_tstring;
+use crate::read::value::read_value_ident;
+use crate::serde::read::map::ReadMapAccess;
+use crate::serde::read::seq::{ReadArrayAccess, ReadListAccess};
+use crate::serde::read::source::Source;
+use crate::serde::read::struct_::ReadStructAccess;
+use crate::serde::read::value::ReadValueDeserializer;
+use crate::serde::read::variant::ReadEnumAccess;
+use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::uuid::UUID_NAME;
+
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
+#[cfg(feature="compression")]
+use crate::read::element::parse_codec;
+#[cfg(feature="compression")]
+use std::io::Cursor;
+#[cfg(feature="compression")]
+use crate::serde::read::source::IoSource;
+
+/// A deserializer that walks tycho bytes directly from a source, without building an element tree.
+pub struct TychoReadDeserializer<'a, S> {
+    pub(crate) source: &'a mut S
+}
+
+impl<'a, S> TychoReadDeserializer<'a, S> {
+    pub fn new(source: &'a mut S) -> Self {
+        Self { source }
+    }
+
+    /// Read a container size prefix, returning the position at which the container ends.
+    pub(crate) fn container_end<'de>(&mut self) -> TychoResult<u64> where S: Source<'de> {
+        let size = read_length(self.source)?;
+        self.source.limiter().container(size)?;
+        Ok(self.source.position() + size as u64)
+    }
+
+    /// Deserialize the body of an element, after its prefix.
+    #[cfg_attr(not(feature="compression"), allow(unused_variables))]
+    fn deserialize_element<'de, V: Visitor<'de>>(mut self, ident: ElementIdent, prefix: u8, visitor: V) -> TychoResult<V::Value> where S: Source<'de> {
+        match ident {
+            ElementIdent::Unit => visitor.visit_unit(),
+            ElementIdent::Value => {
+                let ident = read_value_ident(self.source)?;
+                ReadValueDeserializer::new(self.source, ident).deserialize_any(visitor)
+            },
+            ElementIdent::None => visitor.visit_none(),
+            ElementIdent::Some => visitor.visit_some(self),
+            ElementIdent::Variant => {
+                let name = self.source.limited(read_tstring)?;
+                visitor.visit_enum(ReadEnumAccess::new(name, self.source))
+            },
+            ElementIdent::Struct => {
+                let end = self.container_end()?;
+                let value = visitor.visit_map(ReadStructAccess::new(self.source, end))?;
+                finish_container(self.source, end)?;
+                Ok(value)
+            },
+            ElementIdent::List => {
+                let end = self.container_end()?;
+                let value = visitor.visit_seq(ReadListAccess::new(self.source, end))?;
+                finish_container(self.source, end)?;
+                Ok(value)
+            },
+            ElementIdent::Array => {
+                let ident = read_value_ident(self.source)?;
+                let end = if ident == ValueIdent::Null { self.source.position() } else { self.container_end()? };
+                let value = visitor.visit_seq(ReadArrayAccess::new(self.source, end, ident))?;
+                finish_container(self.source, end)?;
+                Ok(value)
+            },
+            ElementIdent::Map => {
+                let ident = read_value_ident(self.source)?;
+                let end = if ident == ValueIdent::Null { self.source.position() } else { self.container_end()? };
+                let value = visitor.visit_map(ReadMapAccess::new(self.source, end, ident))?;
+                finish_container(self.source, end)?;
+                Ok(value)
+            },
+            ElementIdent::Compression => {
+                let size = read_length(self.source)?;
+                self.source.limiter().container(size)?;
+                let bytes = read_bytes(self.source, size)?;
+
+                #[cfg(feature="compression")]
+                {
+                    let data = decompress_limited(&bytes, parse_codec(prefix)?, self.source.limiter())?;
+                    let mut source = IoSource::with_limiter(Cursor::new(data), *self.source.limiter());
+                    let value = TychoReadDeserializer::new(&mut source).deserialize_any(visitor)?;
+                    *self.source.limiter() = *source.limiter();
+                    Ok(value)
+                }
+
+                #[cfg(not(feature="compression"))]
+                {
+                    visitor.visit_byte_buf(bytes)
+                }
+            }
+        }
+    }
+}
+
+/// Skip any unread items left within a container by a visitor.
+pub(crate) fn finish_container<'de, S: Source<'de>>(source: &mut S, end: u64) -> TychoStatus {
+    let position = source.position();
+
+    if position > end {
+        Err(TychoError::ContainerOverflow)
+    } else {
+        source.skip(end - position)
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> Deserializer<'de> for TychoReadDeserializer<'a, S> {
+    type Error = TychoError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        let source = self.source;
+        let prefix = read_byte(source)?;
+        let ident = parse_element_ident(prefix)?;
+
+        source.limiter().enter()?;
+        let value = TychoReadDeserializer::new(&mut *source).deserialize_element(ident, prefix, visitor);
+        source.limiter().exit();
+
+        value
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        if name == UUID_NAME {
+            return self.deserialize_any(visitor);
+        }
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
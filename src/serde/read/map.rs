@@ -0,0 +1,37 @@
+use serde::de::{DeserializeSeed, MapAccess};
+
+use crate::error::TychoError;
+use crate::ident::ValueIdent;
+use crate::serde::read::source::Source;
+use crate::serde::read::TychoReadDeserializer;
+use crate::serde::read::value::ReadValueDeserializer;
+
+pub struct ReadMapAccess<'a, S> {
+    source: &'a mut S,
+    end: u64,
+    ident: ValueIdent
+}
+
+impl<'a, S> ReadMapAccess<'a, S> {
+    pub fn new(source: &'a mut S, end: u64, ident: ValueIdent) -> Self {
+        Self { source, end, ident }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> MapAccess<'de> for ReadMapAccess<'a, S> {
+    type Error = TychoError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<<K as DeserializeSeed<'de>>::Value>, Self::Error> where
+        K: DeserializeSeed<'de> {
+        if self.source.position() >= self.end {
+            Ok(None)
+        } else {
+            Ok(Some(seed.deserialize(ReadValueDeserializer::new(&mut *self.source, self.ident.clone()))?))
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<<V as DeserializeSeed<'de>>::Value, Self::Error> where
+        V: DeserializeSeed<'de> {
+        seed.deserialize(TychoReadDeserializer::new(&mut *self.source))
+    }
+}
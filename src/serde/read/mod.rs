@@ -0,0 +1,10 @@
+pub(crate) use de::TychoReadDeserializer;
+pub(crate) use source::{IoSource, SliceSource};
+
+pub(crate) mod de;
+pub(crate) mod source;
+pub(crate) mod seq;
+pub(crate) mod map;
+pub(crate) mod struct_;
+pub(crate) mod variant;
+pub(crate) mod value;
@@ -0,0 +1,56 @@
+use serde::de::{DeserializeSeed, SeqAccess};
+
+use crate::error::TychoError;
+use crate::ident::ValueIdent;
+use crate::serde::read::source::Source;
+use crate::serde::read::TychoReadDeserializer;
+use crate::serde::read::value::ReadValueDeserializer;
+
+pub struct ReadListAccess<'a, S> {
+    source: &'a mut S,
+    end: u64
+}
+
+impl<'a, S> ReadListAccess<'a, S> {
+    pub fn new(source: &'a mut S, end: u64) -> Self {
+        Self { source, end }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> SeqAccess<'de> for ReadListAccess<'a, S> {
+    type Error = TychoError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<<T as DeserializeSeed<'de>>::Value>, Self::Error> where
+        T: DeserializeSeed<'de> {
+        if self.source.position() >= self.end {
+            Ok(None)
+        } else {
+            Ok(Some(seed.deserialize(TychoReadDeserializer::new(&mut *self.source))?))
+        }
+    }
+}
+
+pub struct ReadArrayAccess<'a, S> {
+    source: &'a mut S,
+    end: u64,
+    ident: ValueIdent
+}
+
+impl<'a, S> ReadArrayAccess<'a, S> {
+    pub fn new(source: &'a mut S, end: u64, ident: ValueIdent) -> Self {
+        Self { source, end, ident }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> SeqAccess<'de> for ReadArrayAccess<'a, S> {
+    type Error = TychoError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<<T as DeserializeSeed<'de>>::Value>, Self::Error> where
+        T: DeserializeSeed<'de> {
+        if self.source.position() >= self.end {
+            Ok(None)
+        } else {
+            Ok(Some(seed.deserialize(ReadValueDeserializer::new(&mut *self.source, self.ident.clone()))?))
+        }
+    }
+}
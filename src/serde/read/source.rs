@@ -0,0 +1,175 @@
+use std::io::{self, BufRead, Read};
+
+use crate::error::{parse_io, TychoError, TychoResult, TychoStatus};
+use crate::read::buffer::read_terminated;
+use crate::read::func::read_bytes;
+use crate::read::options::{Limiter, ReadOptions};
+
+/// Bytes read from a source, either borrowed from the source or copied out of it.
+pub enum Reference<'de> {
+    Borrowed(&'de [u8]),
+    Copied(Vec<u8>)
+}
+
+/// A readable source of tycho bytes, which keeps track of its position.
+pub trait Source<'de>: BufRead + Sized {
+    /// Get the amount of bytes read from the source.
+    fn position(&self) -> u64;
+
+    /// Get the limiter tracking the resources used while reading the source.
+    fn limiter(&mut self) -> &mut Limiter;
+
+    /// Run a read function with the source's limiter.
+    fn limited<T, F: FnOnce(&mut Self, &mut Limiter) -> TychoResult<T>>(&mut self, f: F) -> TychoResult<T> {
+        let mut limiter = *self.limiter();
+        let result = f(self, &mut limiter);
+        *self.limiter() = limiter;
+        result
+    }
+
+    /// Skip over a number of bytes.
+    fn skip(&mut self, size: u64) -> TychoStatus {
+        let skipped = parse_io(io::copy(&mut self.by_ref().take(size), &mut io::sink()))?;
+        if skipped != size {
+            return Err(TychoError::UnexpectedEof);
+        }
+        Ok(())
+    }
+
+    /// Read a number of bytes, borrowing them from the source if possible.
+    fn read_ref(&mut self, size: usize) -> TychoResult<Reference<'de>> {
+        Ok(Reference::Copied(read_bytes(self, size)?))
+    }
+
+    /// Read bytes up to a null terminator, borrowing them from the source if possible.
+    ///
+    /// The terminator is consumed, but not included.
+    fn read_tref(&mut self) -> TychoResult<Reference<'de>> {
+        self.limited(read_terminated).map(Reference::Copied)
+    }
+}
+
+/// A source over any readable object.
+pub struct IoSource<R: Read> {
+    reader: R,
+    position: u64,
+    limiter: Limiter
+}
+
+impl<R: Read> IoSource<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_limiter(reader, Limiter::default())
+    }
+
+    pub fn with_options(reader: R, options: ReadOptions) -> Self {
+        Self::with_limiter(reader, Limiter::new(options))
+    }
+
+    pub fn with_limiter(reader: R, limiter: Limiter) -> Self {
+        Self { reader, position: 0, limiter }
+    }
+}
+
+impl<R: Read> Read for IoSource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for IoSource<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount);
+        self.position += amount as u64;
+    }
+}
+
+impl<'de, R: BufRead> Source<'de> for IoSource<R> {
+    fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn limiter(&mut self) -> &mut Limiter {
+        &mut self.limiter
+    }
+}
+
+/// A source over an in-memory slice of bytes, which strings and bytes can be borrowed from.
+pub struct SliceSource<'de> {
+    slice: &'de [u8],
+    position: usize,
+    limiter: Limiter
+}
+
+impl<'de> SliceSource<'de> {
+    pub fn new(slice: &'de [u8]) -> Self {
+        Self::with_options(slice, ReadOptions::default())
+    }
+
+    pub fn with_options(slice: &'de [u8], options: ReadOptions) -> Self {
+        Self { slice, position: 0, limiter: Limiter::new(options) }
+    }
+
+    fn remaining(&self) -> &'de [u8] {
+        &self.slice[self.position..]
+    }
+}
+
+impl<'de> Read for SliceSource<'de> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.remaining().read(buf)?;
+        self.position += size;
+        Ok(size)
+    }
+}
+
+impl<'de> BufRead for SliceSource<'de> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position += amount;
+    }
+}
+
+impl<'de> Source<'de> for SliceSource<'de> {
+    fn position(&self) -> u64 {
+        self.position as u64
+    }
+
+    fn limiter(&mut self) -> &mut Limiter {
+        &mut self.limiter
+    }
+
+    fn skip(&mut self, size: u64) -> TychoStatus {
+        self.read_ref(size as usize)?;
+        Ok(())
+    }
+
+    fn read_ref(&mut self, size: usize) -> TychoResult<Reference<'de>> {
+        let remaining = self.remaining();
+        if remaining.len() < size {
+            return Err(TychoError::UnexpectedEof);
+        }
+        self.position += size;
+        Ok(Reference::Borrowed(&remaining[..size]))
+    }
+
+    fn read_tref(&mut self) -> TychoResult<Reference<'de>> {
+        let remaining = self.remaining();
+        match remaining.iter().position(|x| *x == 0x00) {
+            Some(size) => {
+                self.limiter.string(size)?;
+                self.position += size + 1;
+                Ok(Reference::Borrowed(&remaining[..size]))
+            }
+            None => Err(TychoError::UnexpectedEof)
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::de::{DeserializeSeed, MapAccess};
+use serde::de::value::BorrowedStrDeserializer;
+
+use crate::error::TychoError;
+use crate::read::string::{parse_str, parse_string};
+use crate::serde::de::ident::TychoIdentDeserializer;
+use crate::serde::read::source::{Reference, Source};
+use crate::serde::read::TychoReadDeserializer;
+
+pub struct ReadStructAccess<'a, S> {
+    source: &'a mut S,
+    end: u64
+}
+
+impl<'a, S> ReadStructAccess<'a, S> {
+    pub fn new(source: &'a mut S, end: u64) -> Self {
+        Self { source, end }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> MapAccess<'de> for ReadStructAccess<'a, S> {
+    type Error = TychoError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<<K as DeserializeSeed<'de>>::Value>, Self::Error> where
+        K: DeserializeSeed<'de> {
+        if self.source.position() >= self.end {
+            Ok(None)
+        } else {
+            match self.source.read_tref()? {
+                Reference::Borrowed(key) =>
+                    Ok(Some(seed.deserialize(BorrowedStrDeserializer::<TychoError>::new(parse_str(key)?))?)),
+                Reference::Copied(key) => Ok(Some(seed.deserialize(TychoIdentDeserializer::new(&parse_string(key)?))?))
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<<V as DeserializeSeed<'de>>::Value, Self::Error> where
+        V: DeserializeSeed<'de> {
+        seed.deserialize(TychoReadDeserializer::new(&mut *self.source))
+    }
+}
@@ -0,0 +1,78 @@
+use serde::de::Visitor;
+use serde::Deserializer;
+use serde::forward_to_deserialize_any;
+
+use crate::error::TychoError;
+use crate::ident::ValueIdent;
+use crate::read::length::read_length;
+use crate::read::string::{parse_str, parse_string};
+use crate::read::value::read_value;
+use crate::serde::de::visit_value;
+use crate::serde::read::source::{Reference, Source};
+use crate::uuid::UUID_NAME;
+
+/// Visit a string, borrowing it from the source if possible.
+pub(crate) fn visit_str_ref<'de, V: Visitor<'de>>(reference: Reference<'de>, visitor: V) -> Result<V::Value, TychoError> {
+    match reference {
+        Reference::Borrowed(bytes) => visitor.visit_borrowed_str(parse_str(bytes)?),
+        Reference::Copied(bytes) => visitor.visit_string(parse_string(bytes)?)
+    }
+}
+
+/// Visit bytes, borrowing them from the source if possible.
+pub(crate) fn visit_bytes_ref<'de, V: Visitor<'de>>(reference: Reference<'de>, visitor: V) -> Result<V::Value, TychoError> {
+    match reference {
+        Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+        Reference::Copied(bytes) => visitor.visit_byte_buf(bytes)
+    }
+}
+
+/// Deserializes a single value of a known type from a source.
+///
+/// Used for value elements, and the untagged items of arrays and keys of maps.
+pub struct ReadValueDeserializer<'a, S> {
+    source: &'a mut S,
+    ident: ValueIdent
+}
+
+impl<'a, S> ReadValueDeserializer<'a, S> {
+    pub fn new(source: &'a mut S, ident: ValueIdent) -> Self {
+        Self { source, ident }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> Deserializer<'de> for ReadValueDeserializer<'a, S> {
+    type Error = TychoError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        match self.ident {
+            ValueIdent::String => {
+                let size = read_length(self.source)?;
+                self.source.limiter().string(size)?;
+                visit_str_ref(self.source.read_ref(size)?, visitor)
+            }
+            ValueIdent::Bytes => {
+                let size = read_length(self.source)?;
+                self.source.limiter().string(size)?;
+                visit_bytes_ref(self.source.read_ref(size)?, visitor)
+            }
+            ident => visit_value(self.source.limited(|source, limiter| read_value(source, &ident, limiter))?, visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        if name == UUID_NAME {
+            return self.deserialize_any(visitor);
+        }
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
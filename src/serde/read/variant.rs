@@ -0,0 +1,55 @@
+use serde::de::{DeserializeSeed, EnumAccess, IgnoredAny, VariantAccess, Visitor};
+use serde::Deserializer;
+
+use crate::error::TychoError;
+use crate::serde::de::ident::TychoIdentDeserializer;
+use crate::serde::read::source::Source;
+use crate::serde::read::TychoReadDeserializer;
+
+pub struct ReadEnumAccess<'a, S> {
+    name: String,
+    source: &'a mut S
+}
+
+impl<'a, S> ReadEnumAccess<'a, S> {
+    pub fn new(name: String, source: &'a mut S) -> Self {
+        Self { name, source }
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> EnumAccess<'de> for ReadEnumAccess<'a, S> {
+    type Error = TychoError;
+    type Variant = TychoReadDeserializer<'a, S>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(<V as DeserializeSeed<'de>>::Value, Self::Variant), Self::Error> where
+        V: DeserializeSeed<'de> {
+        Ok((
+            seed.deserialize(TychoIdentDeserializer::new(&self.name))?,
+            TychoReadDeserializer::new(self.source)
+        ))
+    }
+}
+
+impl<'de, 'a, S: Source<'de>> VariantAccess<'de> for TychoReadDeserializer<'a, S> {
+    type Error = TychoError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.deserialize_ignored_any(IgnoredAny)?;
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<<T as DeserializeSeed<'de>>::Value, Self::Error> where
+        T: DeserializeSeed<'de> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error> where
+        V: Visitor<'de> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
@@ -1,9 +1,7 @@
-use std::collections::HashMap;
-
 use serde::ser::{Error, SerializeMap};
 use serde::{Serialize, Serializer};
 
-use crate::{Element, Value};
+use crate::{Element, ElementMap, Value};
 use crate::error::TychoError;
 use crate::ident::ValueIdent;
 use crate::into::ident::Ident;
@@ -11,7 +9,7 @@ use crate::serde::ser::TychoSerializer;
 use std::fmt;
 
 pub struct MapSerializer {
-    content: HashMap<Value, Element>,
+    content: ElementMap<Value, Element>,
     map_type: ValueIdent,
     key: Option<Value>
 }
@@ -19,7 +17,7 @@ pub struct MapSerializer {
 impl MapSerializer {
     pub fn new() -> Self {
         Self {
-            content: HashMap::new(),
+            content: ElementMap::new(),
             map_type: ValueIdent::Null,
             key: None
         }
@@ -27,7 +25,7 @@ impl MapSerializer {
 
     pub fn typed(x: ValueIdent) -> Self {
         Self {
-            content: HashMap::new(),
+            content: ElementMap::new(),
             map_type: x,
             key: None
         }
@@ -46,7 +44,7 @@ impl SerializeMap for MapSerializer {
             if self.map_type == ValueIdent::Null {
               self.map_type = value.ident();
             } else if value.ident() != self.map_type {
-                return Err(TychoError::MismatchedType { found: value.ident(), expected: self.map_type.clone() })
+                return Err(TychoError::ValueMismatch { expected: self.map_type.clone(), found: value.ident() })
             }
             self.key = Some(value);
         } else {
@@ -1,13 +1,15 @@
 use paste;
 use serde::{Serialize, Serializer};
+use serde::ser::Error;
 
-use crate::{Element, Number};
+use crate::{Element, Number, Uuid};
 use crate::error::TychoError;
 use crate::serde::ser::map::MapSerializer;
 use crate::serde::ser::seq::{SeqSerializer, SeqSerializerType};
 use crate::serde::ser::struct_::StructSerializer;
 use crate::serde::ser::variant::{VariantSeqSerializer, VariantStructSerializer};
 use crate::Value;
+use crate::uuid::UUID_NAME;
 
 macro_rules! serialize_number {
     ($ident: ident, $type: ty) => {
@@ -87,8 +89,15 @@ impl Serializer for TychoSerializer {
         Ok(Element::Variant(variant.to_string(), Element::Unit.into()))
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where
+    fn serialize_newtype_struct<T: ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where
         T: Serialize {
+        if cfg!(feature="serde_types") && name == UUID_NAME {
+            return match value.serialize(Self)? {
+                Element::Value(Value::Bytes(x)) if x.len() == 16 => Ok(Element::Value(Value::UUID(Uuid::from_bytes(&x)))),
+                _ => Err(TychoError::custom("Invalid serde transfer type for Uuid."))
+            }
+        }
+
         Ok(value.serialize(Self)?)
     }
 
@@ -1,9 +1,7 @@
-use std::collections::HashMap;
-
 use serde::ser::{SerializeStruct, Error};
 use serde::Serialize;
 
-use crate::{Element, Uuid, Number};
+use crate::{Element, ElementMap, Number};
 use crate::Value;
 use crate::error::TychoError;
 use crate::serde::ser::TychoSerializer;
@@ -13,14 +11,14 @@ use crate::serde::ser::map::MapSerializer;
 
 #[allow(dead_code)]
 pub struct StructSerializer {
-    content: HashMap<String, Element>,
+    content: ElementMap<String, Element>,
     name: String
 }
 
 impl StructSerializer {
     pub fn new(name: &str) -> Self {
         Self {
-            content: HashMap::new(),
+            content: ElementMap::new(),
             name: name.to_string()
         }
     }
@@ -90,11 +88,6 @@ impl SerializeStruct for StructSerializer {
     #[cfg(feature="serde_types")]
     fn end(mut self) -> Result<Self::Ok, Self::Error> {
         match self.name.as_str() {
-            "___tycho___/uuid" => if let Some(Element::Value(Value::Bytes(x))) = self.content.get("inner") {
-                Ok(Element::Value(Value::UUID(Uuid::from_bytes(&x))))
-            } else {
-                Err(Self::Error::custom("Invalid serde transfer type for Uuid."))
-            },
             "__tycho__/array" => if let Some(x) = self.content.remove("inner") {
                 Ok(x)
             } else {
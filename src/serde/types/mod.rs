@@ -36,7 +36,7 @@ impl Serialize for Element {
                 s.serialize_field("inner", x)?;
                 s.end()
             }
-            Element::Compression(c) => {
+            Element::Compression(_, c) => {
                 let mut s = serializer.serialize_struct("___tycho___/compression", 1)?;
                 s.serialize_field("inner", c);
                 s.end()
@@ -61,7 +61,7 @@ impl Serialize for Element {
             Element::List(x) => x.serialize(serializer),
             Element::Array(_i, x) => x.serialize(serializer),
             Element::Map(_i, x) => x.serialize(serializer),
-            Element::Compression(c) => c.serialize(serializer),
+            Element::Compression(_, c) => c.serialize(serializer),
         }
     }
 }
@@ -94,27 +94,8 @@ impl Serialize for Number {
             Number::Signed32(x) => serializer.serialize_i32(*x),
             Number::Unsigned64(x) => serializer.serialize_u64(*x),
             Number::Signed64(x) => serializer.serialize_i64(*x),
-
-            #[cfg(not(feature="serde_types"))]
-            Number::Unsigned128(x) => serializer.serialize_bytes(&x.to_be_bytes()),
-            #[cfg(not(feature="serde_types"))]
-            Number::Signed128(x) => serializer.serialize_bytes(&x.to_be_bytes()),
-
-            #[cfg(feature="serde_types")]
-            Number::Unsigned128(x) => {
-                let mut stu = serializer.serialize_struct("___tycho___/u128", 2)?;
-                stu.serialize_field("0", &((x >> 64) as u64))?;
-                stu.serialize_field("1", &((x & 0xFFFF_FFFF_FFFF_FFFF) as u64))?;
-                stu.end()
-            }
-
-            #[cfg(feature="serde_types")]
-            Number::Signed128(x) => {
-                let mut stu = serializer.serialize_struct("___tycho___/i128", 2)?;
-                stu.serialize_field("0", &(((*x as u128) >> 64) as u64))?;
-                stu.serialize_field("1", &(((*x as u128) & 0xFFFF_FFFF_FFFF_FFFF) as u64))?;
-                stu.end()
-            }
+            Number::Unsigned128(x) => serializer.serialize_u128(*x),
+            Number::Signed128(x) => serializer.serialize_i128(*x),
 
             Number::Float32(x) => serializer.serialize_f32(*x),
             Number::Float64(x) => serializer.serialize_f64(*x),
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde::ser::{Error, SerializeMap};
+use serde::Serialize;
+
+use crate::error::{TychoError, TychoResult, TychoStatus};
+use crate::ident::ValueIdent;
+use crate::read::element::parse_element_ident;
+use crate::read::value::read_value_ident;
+use crate::serde::write::plan::{Output, Slot};
+use crate::serde::write::TychoWriteSerializer;
+use crate::write::func::{write_byte, write_bytes};
+use crate::write::length::write_length;
+use crate::write::size::{length_size, value_ident_size};
+use crate::write::value::write_value_ident;
+
+/// Writes a map, with the size and key type found while sizing.
+pub struct WriteMapSerializer<'a, W: Write> {
+    writer: &'a mut Output<W>,
+    index: usize,
+    slot: Slot,
+    // The position of the map's contents.
+    start: usize,
+    // While sizing, the keys written, to find repeated keys.
+    keys: HashSet<Vec<u8>>,
+    key: bool
+}
+
+impl<'a, W: Write> WriteMapSerializer<'a, W> {
+    pub(crate) fn new(writer: &'a mut Output<W>) -> TychoResult<Self> {
+        let (index, slot) = writer.start();
+
+        if !writer.is_sizing() {
+            let map_type = slot.ident.clone().unwrap_or(ValueIdent::Null);
+            write_byte(writer, &0x08)?;
+            write_value_ident(writer, &map_type)?;
+            if map_type != ValueIdent::Null {
+                write_length(writer, slot.size)?;
+            }
+        }
+
+        Ok(Self {
+            start: writer.written,
+            writer,
+            index,
+            slot,
+            keys: HashSet::new(),
+            key: false
+        })
+    }
+}
+
+impl<'a, W: Write> SerializeMap for WriteMapSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> TychoStatus {
+        let mut output = Output::new(Vec::new());
+        key.serialize(TychoWriteSerializer::new(&mut output))?;
+        let data = output.into_inner();
+
+        if data[0] != 0x01 {
+            return Err(TychoError::InvalidKeyType { found: parse_element_ident(data[0])? })
+        }
+
+        let mut payload = &data[1..];
+        let ident = read_value_ident(&mut payload)?;
+
+        match &self.slot.ident {
+            None => self.slot.ident = Some(ident),
+            Some(map_type) if map_type != &ident => {
+                return Err(TychoError::ValueMismatch { expected: map_type.clone(), found: ident })
+            }
+            _ => ()
+        }
+
+        // the element serializer keeps one item for each key, which can not be written directly.
+        if self.writer.is_sizing() && !self.keys.insert(payload.to_vec()) {
+            self.writer.duplicate = true;
+        }
+
+        self.key = true;
+        write_bytes(self.writer, payload)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> TychoStatus {
+        if !self.key {
+            return Err(TychoError::custom("Invalid key state."));
+        }
+        self.key = false;
+
+        value.serialize(TychoWriteSerializer::new(self.writer))
+    }
+
+    fn end(mut self) -> Result<Self::Ok, Self::Error> {
+        if !self.writer.is_sizing() {
+            return self.writer.finish(self.index, self.slot, self.start, 0);
+        }
+
+        let map_type = self.slot.ident.clone().unwrap_or(ValueIdent::Null);
+        self.slot.size = self.writer.written - self.start;
+
+        let header = 1 + value_ident_size(&map_type) + if map_type == ValueIdent::Null { 0 } else { length_size(self.slot.size) };
+        self.writer.finish(self.index, self.slot, self.start, header)
+    }
+}
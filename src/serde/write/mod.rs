@@ -0,0 +1,8 @@
+pub(crate) use plan::Output;
+pub(crate) use ser::TychoWriteSerializer;
+
+pub(crate) mod ser;
+pub(crate) mod seq;
+pub(crate) mod map;
+pub(crate) mod struct_;
+pub(crate) mod plan;
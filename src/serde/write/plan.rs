@@ -0,0 +1,100 @@
+use std::io::{self, Sink, Write};
+
+use crate::error::{TychoError, TychoStatus};
+use crate::types::ident::ValueIdent;
+
+/// The size of a container, and the type of its values, found while sizing.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Slot {
+    /// The size of the container's contents.
+    pub(crate) size: usize,
+    /// The type of an array's values or a map's keys, or `None` for a list.
+    pub(crate) ident: Option<ValueIdent>,
+}
+
+/// A writer for the write serializer, which counts the bytes written.
+///
+/// Objects are serialized twice. First to a sink, recording a slot for each container in the order they start,
+/// then to the writer, following the slots to write each size prefix before the contents of its container.
+pub struct Output<W> {
+    writer: W,
+    sizing: bool,
+    slots: Vec<Slot>,
+    next: usize,
+    /// The amount of bytes written.
+    pub(crate) written: usize,
+    /// The position and type of the last value element started.
+    pub(crate) last_value: Option<(usize, ValueIdent)>,
+    /// If a key was repeated within a struct or map, found while sizing.
+    pub(crate) duplicate: bool,
+}
+
+impl Output<Sink> {
+    /// Create an output which sizes an object, without writing it.
+    pub(crate) fn sizing() -> Self {
+        Output { sizing: true, ..Output::new(io::sink()) }
+    }
+}
+
+impl<W: Write> Output<W> {
+    /// Create an output which writes containers following the slots of a sized output.
+    pub(crate) fn writing(writer: W, sized: Output<Sink>) -> Self {
+        Output { slots: sized.slots, ..Output::new(writer) }
+    }
+
+    /// Create an output which writes to a writer, without any slots.
+    ///
+    /// Containers are written with empty size prefixes, for finding the prefix of a value.
+    pub(crate) fn new(writer: W) -> Self {
+        Output { writer, sizing: false, slots: Vec::new(), next: 0, written: 0, last_value: None, duplicate: false }
+    }
+
+    pub(crate) fn is_sizing(&self) -> bool {
+        self.sizing
+    }
+
+    pub(crate) fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Start a container, returning the index of its slot, and the slot found while sizing.
+    pub(crate) fn start(&mut self) -> (usize, Slot) {
+        if self.sizing {
+            self.slots.push(Slot::default());
+            (self.slots.len() - 1, Slot::default())
+        } else {
+            self.next += 1;
+            (self.next - 1, self.slots.get(self.next - 1).cloned().unwrap_or_default())
+        }
+    }
+
+    /// Finish a container, where `start` is the position of its contents, and `header` is the size of its prefix.
+    ///
+    /// While sizing, nothing is written, so the slot is recorded and the bytes counted
+    /// are replaced with the size of the container once written.
+    /// While writing, the contents are checked against the size found while sizing,
+    /// as an object which serializes differently the second time would be given the wrong size prefix.
+    pub(crate) fn finish(&mut self, index: usize, slot: Slot, start: usize, header: usize) -> TychoStatus {
+        if self.sizing {
+            self.written = start + header + slot.size;
+            self.slots[index] = slot;
+            Ok(())
+        } else if index < self.slots.len() && self.written - start != slot.size {
+            Err(TychoError::Other("Object serialized differently while sizing and writing.".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<W: Write> Write for Output<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.writer.write(buf)?;
+        self.written += size;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
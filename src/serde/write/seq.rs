@@ -0,0 +1,161 @@
+use std::io::Write;
+
+use serde::ser::{SerializeSeq, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
+use serde::Serialize;
+
+use crate::error::{TychoError, TychoResult, TychoStatus};
+use crate::ident::ValueIdent;
+use crate::serde::write::plan::{Output, Slot};
+use crate::serde::write::TychoWriteSerializer;
+use crate::write::func::write_byte;
+use crate::write::length::write_length;
+use crate::write::size::{length_size, value_ident_size};
+use crate::write::value::write_value_ident;
+
+/// Writes a sequence as either a list or an array, with the size and type found while sizing.
+pub struct WriteSeqSerializer<'a, W: Write> {
+    writer: &'a mut Output<W>,
+    index: usize,
+    slot: Slot,
+    // The position of the sequence's contents.
+    start: usize,
+    // While sizing, the total size of the items' payloads, if they are values of one type.
+    payloads: usize,
+    array_opt: bool,
+    empty: bool,
+}
+
+impl<'a, W: Write> WriteSeqSerializer<'a, W> {
+    pub(crate) fn new(writer: &'a mut Output<W>) -> TychoResult<Self> {
+        let (index, slot) = writer.start();
+
+        if !writer.is_sizing() {
+            match &slot.ident {
+                Some(ident) => {
+                    write_byte(writer, &0x07)?;
+                    write_value_ident(writer, ident)?;
+                    if ident != &ValueIdent::Null {
+                        write_length(writer, slot.size)?;
+                    }
+                }
+                None => {
+                    write_byte(writer, &0x06)?;
+                    write_length(writer, slot.size)?;
+                }
+            }
+        }
+
+        Ok(Self {
+            start: writer.written,
+            writer,
+            index,
+            slot,
+            payloads: 0,
+            array_opt: true,
+            empty: true
+        })
+    }
+
+    pub fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> TychoStatus {
+        if !self.writer.is_sizing() {
+            return if self.slot.ident.is_some() {
+                value.serialize(TychoWriteSerializer::payload(self.writer))
+            } else {
+                value.serialize(TychoWriteSerializer::new(self.writer))
+            };
+        }
+
+        let start = self.writer.written;
+        value.serialize(TychoWriteSerializer::new(self.writer))?;
+        self.empty = false;
+
+        if cfg!(feature = "serde_optimise") && self.array_opt {
+            // the item is a value if a value element started where the item did.
+            match &self.writer.last_value {
+                Some((position, ident)) if *position == start => {
+                    match &self.slot.ident {
+                        None => self.slot.ident = Some(ident.clone()),
+                        Some(array_type) if array_type != ident => self.array_opt = false,
+                        _ => ()
+                    }
+                    self.payloads += self.writer.written - start - 1 - value_ident_size(ident);
+                }
+                _ => self.array_opt = false
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> TychoStatus {
+        if !self.writer.is_sizing() {
+            return self.writer.finish(self.index, self.slot, self.start, 0);
+        }
+
+        let header = if cfg!(feature = "serde_optimise") && self.array_opt && !self.empty {
+            let array_type = self.slot.ident.clone().unwrap_or(ValueIdent::Null);
+            self.slot.size = if array_type == ValueIdent::Null { 0 } else { self.payloads };
+
+            1 + value_ident_size(&array_type) + if array_type == ValueIdent::Null { 0 } else { length_size(self.slot.size) }
+        } else {
+            self.slot.ident = None;
+            self.slot.size = self.writer.written - self.start;
+
+            1 + length_size(self.slot.size)
+        };
+
+        self.writer.finish(self.index, self.slot, self.start, header)
+    }
+}
+
+impl<'a, W: Write> SerializeSeq for WriteSeqSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W: Write> SerializeTuple for WriteSeqSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W: Write> SerializeTupleStruct for WriteSeqSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W: Write> SerializeTupleVariant for WriteSeqSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,185 @@
+use std::io::Write;
+
+use serde::{Serialize, Serializer};
+
+use crate::{Element, Number, Value};
+use crate::error::{TychoError, TychoStatus};
+use crate::into::ident::Ident;
+use crate::serde::ser::TychoSerializer;
+use crate::serde::write::map::WriteMapSerializer;
+use crate::serde::write::plan::Output;
+use crate::serde::write::seq::WriteSeqSerializer;
+use crate::serde::write::struct_::WriteStructSerializer;
+use crate::types::ident::ValueIdent;
+use crate::uuid::UUID_NAME;
+use crate::write::element::write_element;
+use crate::write::func::{write_byte, write_sized};
+use crate::write::string::{write_string, write_tstring};
+use crate::write::value::{write_value, write_value_ident};
+
+macro_rules! serialize_number {
+    ($ident: ident, $type: ty) => {
+        paste::item! {
+            fn [< serialize_ $type >](self, v: $type) -> Result<Self::Ok, Self::Error> {
+                self.value(&Value::Number(Number::$ident(v)))
+            }
+
+        }
+    };
+}
+
+/// A serializer that writes tycho bytes directly to a writer, without building an element tree.
+///
+/// Produces the same bytes as `TychoSerializer` followed by `write_element`,
+/// when given an output which has been sized (see `Output`).
+pub struct TychoWriteSerializer<'a, W: Write> {
+    writer: &'a mut Output<W>,
+    // Write only the payload of values, for the items of an array.
+    payload: bool
+}
+
+impl<'a, W: Write> TychoWriteSerializer<'a, W> {
+    pub fn new(writer: &'a mut Output<W>) -> Self {
+        Self { writer, payload: false }
+    }
+
+    /// Create a serializer for an item of an array, which is written without its element and value prefix.
+    pub(crate) fn payload(writer: &'a mut Output<W>) -> Self {
+        Self { writer, payload: true }
+    }
+
+    fn value(mut self, value: &Value) -> TychoStatus {
+        self.value_prefix(&value.ident())?;
+        write_value(self.writer, value)
+    }
+
+    /// Write the element and value prefix of a value, and record where it started.
+    fn value_prefix(&mut self, ident: &ValueIdent) -> TychoStatus {
+        if self.payload {
+            return Ok(());
+        }
+
+        self.writer.last_value = Some((self.writer.written, ident.clone()));
+        write_byte(self.writer, &0x01)?;
+        write_value_ident(self.writer, ident)
+    }
+
+    fn variant(&mut self, variant: &str) -> TychoStatus {
+        write_byte(self.writer, &0x04)?;
+        write_tstring(self.writer, variant)
+    }
+}
+
+impl<'a, W: Write> Serializer for TychoWriteSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+    type SerializeSeq = WriteSeqSerializer<'a, W>;
+    type SerializeTuple = WriteSeqSerializer<'a, W>;
+    type SerializeTupleStruct = WriteSeqSerializer<'a, W>;
+    type SerializeTupleVariant = WriteSeqSerializer<'a, W>;
+    type SerializeMap = WriteMapSerializer<'a, W>;
+    type SerializeStruct = WriteStructSerializer<'a, W>;
+    type SerializeStructVariant = WriteStructSerializer<'a, W>;
+
+    fn is_human_readable(&self) -> bool { false }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.value(&Value::Boolean(v))
+    }
+
+    serialize_number!(Unsigned8, u8);
+    serialize_number!(Signed8, i8);
+    serialize_number!(Unsigned16, u16);
+    serialize_number!(Signed16, i16);
+    serialize_number!(Unsigned32, u32);
+    serialize_number!(Signed32, i32);
+    serialize_number!(Unsigned64, u64);
+    serialize_number!(Signed64, i64);
+    serialize_number!(Unsigned128, u128);
+    serialize_number!(Signed128, i128);
+    serialize_number!(Float32, f32);
+    serialize_number!(Float64, f64);
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.value(&Value::Char(v))
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.value_prefix(&ValueIdent::String)?;
+        write_string(self.writer, v)
+    }
+
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.value_prefix(&ValueIdent::Bytes)?;
+        write_sized(self.writer, v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        write_byte(self.writer, &0x02)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        write_byte(self.writer, &0x03)?;
+        value.serialize(TychoWriteSerializer::new(self.writer))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        write_byte(self.writer, &0x00)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        write_byte(self.writer, &0x00)
+    }
+
+    fn serialize_unit_variant(mut self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.variant(variant)?;
+        write_byte(self.writer, &0x00)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        // Internal tycho types are handled by the element serializer.
+        if cfg!(feature="serde_types") && name == UUID_NAME {
+            return match TychoSerializer.serialize_newtype_struct(name, value)? {
+                Element::Value(value) => self.value(&value),
+                element => write_element(self.writer, &element)
+            };
+        }
+
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(mut self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        self.variant(variant)?;
+        value.serialize(TychoWriteSerializer::new(self.writer))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        WriteSeqSerializer::new(self.writer)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        WriteSeqSerializer::new(self.writer)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        WriteSeqSerializer::new(self.writer)
+    }
+
+    fn serialize_tuple_variant(mut self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.variant(variant)?;
+        WriteSeqSerializer::new(self.writer)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        WriteMapSerializer::new(self.writer)
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        WriteStructSerializer::new(self.writer, name)
+    }
+
+    fn serialize_struct_variant(mut self, name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.variant(variant)?;
+        WriteStructSerializer::new(self.writer, name)
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde::ser::{SerializeStruct, SerializeStructVariant};
+use serde::Serialize;
+
+use crate::error::{TychoError, TychoResult, TychoStatus};
+use crate::serde::ser::struct_::StructSerializer;
+use crate::serde::write::plan::{Output, Slot};
+use crate::serde::write::TychoWriteSerializer;
+use crate::write::element::write_element;
+use crate::write::func::write_byte;
+use crate::write::length::write_length;
+use crate::write::size::length_size;
+use crate::write::string::write_tstring;
+
+/// Writes a struct, with the size found while sizing.
+pub struct WriteStructSerializer<'a, W: Write> {
+    writer: &'a mut Output<W>,
+    index: usize,
+    slot: Slot,
+    // The position of the struct's contents.
+    start: usize,
+    // While sizing, the fields written, to find repeated fields.
+    fields: HashSet<&'static str>,
+    // Internal tycho types are handled by the element serializer.
+    delegate: Option<StructSerializer>
+}
+
+impl<'a, W: Write> WriteStructSerializer<'a, W> {
+    pub(crate) fn new(writer: &'a mut Output<W>, name: &str) -> TychoResult<Self> {
+        let delegate = if cfg!(feature="serde_types") && name.starts_with("__tycho__/") {
+            Some(StructSerializer::new(name))
+        } else {
+            None
+        };
+
+        let (index, slot) = if delegate.is_none() { writer.start() } else { (0, Slot::default()) };
+
+        if delegate.is_none() && !writer.is_sizing() {
+            write_byte(writer, &0x05)?;
+            write_length(writer, slot.size)?;
+        }
+
+        Ok(Self {
+            start: writer.written,
+            writer,
+            index,
+            slot,
+            fields: HashSet::new(),
+            delegate
+        })
+    }
+
+    fn field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> TychoStatus {
+        if let Some(delegate) = &mut self.delegate {
+            return delegate.serialize_field(key, value);
+        }
+
+        // the element serializer keeps one item for each field, which can not be written directly.
+        if self.writer.is_sizing() && !self.fields.insert(key) {
+            self.writer.duplicate = true;
+        }
+
+        write_tstring(self.writer, key)?;
+        value.serialize(TychoWriteSerializer::new(self.writer))
+    }
+
+    fn finish(mut self) -> TychoStatus {
+        if let Some(delegate) = self.delegate {
+            return write_element(self.writer, &delegate.end()?);
+        }
+
+        if !self.writer.is_sizing() {
+            return self.writer.finish(self.index, self.slot, self.start, 0);
+        }
+
+        self.slot.size = self.writer.written - self.start;
+        let header = 1 + length_size(self.slot.size);
+        self.writer.finish(self.index, self.slot, self.start, header)
+    }
+}
+
+impl<'a, W: Write> SerializeStruct for WriteStructSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W: Write> SerializeStructVariant for WriteStructSerializer<'a, W> {
+    type Ok = ();
+    type Error = TychoError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
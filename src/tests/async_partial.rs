@@ -0,0 +1,120 @@
+use std::io::Cursor;
+
+use futures::StreamExt;
+
+use crate::{Element, marshall_vec, Value};
+use crate::error::{PathSegment, TychoError};
+use crate::partial::{PartialElement, PartialPointer, PartialReader};
+
+fn list_reader(items: Vec<Element>) -> PartialReader<Cursor<Vec<u8>>> {
+    PartialReader::from(Cursor::new(marshall_vec(Element::List(items)).unwrap()))
+}
+
+#[test]
+fn stream_partial_list() {
+    tokio_test::block_on(async {
+        let mut reader = list_reader(vec![Element::Unit, Element::Value(Value::Boolean(true))]);
+
+        let mut list = match reader.element_async().await.unwrap() {
+            PartialElement::List(list) => list,
+            _ => panic!("expected a list")
+        };
+
+        let items: Vec<_> = list.stream_async(&mut reader).collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[0], Ok(PartialElement::Unit)));
+        assert!(matches!(items[1], Ok(PartialElement::Value(Value::Boolean(true)))));
+        assert!(list.finished());
+    });
+}
+
+#[test]
+fn stream_partial_struct() {
+    tokio_test::block_on(async {
+        let mut data = crate::ElementMap::new();
+        data.insert("foo".to_string(), Element::Unit);
+        let bytes = marshall_vec(Element::Struct(data)).unwrap();
+        let mut reader = PartialReader::from(Cursor::new(bytes));
+
+        let mut fields = match reader.element_async().await.unwrap() {
+            PartialElement::Struct(fields) => fields,
+            _ => panic!("expected a struct")
+        };
+
+        let mut stream = fields.stream_async(&mut reader);
+        let (key, value) = stream.next().await.unwrap().unwrap();
+        assert_eq!(key, "foo");
+        assert!(matches!(value, PartialElement::Unit));
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn stream_partial_error() {
+    tokio_test::block_on(async {
+        let mut bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Unit, Element::Unit])).unwrap();
+        // replace the second item with an invalid prefix.
+        let length = bytes.len();
+        bytes[length - 2] = 0x60;
+        let mut reader = PartialReader::from(Cursor::new(bytes));
+
+        let mut list = match reader.element_async().await.unwrap() {
+            PartialElement::List(list) => list,
+            _ => panic!("expected a list")
+        };
+
+        let mut stream = list.stream_async(&mut reader);
+        assert!(stream.next().await.unwrap().is_ok());
+
+        // the error is yielded, rather than ending the stream.
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(error.inner(), TychoError::InvalidIdent { .. }));
+        assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn read_full_async() {
+    tokio_test::block_on(async {
+        let element = Element::List(vec![
+            Element::Option(Some(Box::new(Element::Value(Value::Boolean(true))))),
+            Element::Variant("Foo".to_string(), Box::new(Element::List(vec![Element::Unit]))),
+        ]);
+        let mut reader = PartialReader::from(Cursor::new(marshall_vec(element.clone()).unwrap()));
+
+        let partial = reader.element_async().await.unwrap();
+        assert_eq!(partial.read_full_async(&mut reader).await.unwrap(), element);
+    });
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn deserialize_async() {
+    tokio_test::block_on(async {
+        let bytes = marshall_vec(crate::to_element(vec!["foo".to_string(), "bar".to_string()]).unwrap()).unwrap();
+        let mut reader = PartialReader::from(Cursor::new(bytes));
+
+        let partial = reader.element_async().await.unwrap();
+        let items: Vec<String> = partial.deserialize_async(&mut reader).await.unwrap();
+        assert_eq!(items, vec!["foo".to_string(), "bar".to_string()]);
+    });
+}
+
+#[test]
+fn element_at_async() {
+    tokio_test::block_on(async {
+        let mut bytes = marshall_vec(Element::Unit).unwrap();
+        bytes.extend(marshall_vec(Element::List(vec![Element::Unit])).unwrap());
+        let length = bytes.len() as u64;
+        let mut reader = PartialReader::from(Cursor::new(bytes));
+
+        let element = reader.element_at_async(&PartialPointer::new(1, length - 1)).await.unwrap();
+        assert_eq!(reader.position(), 0);
+        assert_eq!(element.read_full_async(&mut reader).await.unwrap(), Element::List(vec![Element::Unit]));
+
+        let error = reader.element_at_async(&PartialPointer::new(0, 2)).await.unwrap_err();
+        assert!(matches!(error.inner(), TychoError::PointerNotFound));
+    });
+}
@@ -0,0 +1,76 @@
+use crate::{Element, ElementMap, marshall_async, marshall_vec, Number, Uuid, Value};
+use crate::ident::{NumberIdent, ValueIdent};
+
+fn marshall_async_vec(element: Element) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    tokio_test::block_on(marshall_async(&mut buffer, element)).unwrap();
+    buffer
+}
+
+fn example() -> Element {
+    let mut fields = ElementMap::new();
+    fields.insert("unit".to_string(), Element::Unit);
+    fields.insert("some".to_string(), Element::Option(Some(Box::new(Value::Char('é').into()))));
+    fields.insert("none".to_string(), Element::Option(None));
+    fields.insert("variant".to_string(), Element::Variant("Name".to_string(), Box::new(Value::Null.into())));
+    fields.insert("uuid".to_string(), Value::UUID(Uuid::from_string("2f543f3c-0659-4e92-33b0-c8a85c2ac85e").unwrap()).into());
+    fields.insert("numbers".to_string(), Element::List(vec![
+        Value::Number(Number::Bit(true)).into(),
+        Value::Number(Number::Signed16(-5)).into(),
+        Value::Number(Number::Float64(1.5)).into(),
+        Value::Number(Number::Unsigned128(u128::MAX)).into(),
+    ]));
+    fields.insert("array".to_string(), Element::Array(
+        ValueIdent::Number(NumberIdent::Unsigned32),
+        (0..100).map(|x| Value::Number(Number::Unsigned32(x))).collect()
+    ));
+    fields.insert("empty".to_string(), Element::Array(ValueIdent::String, Vec::new()));
+
+    let mut map = ElementMap::new();
+    map.insert(Value::String("a".to_string()), Element::List(Vec::new()));
+    map.insert(Value::String("b".to_string()), Value::Bytes(vec![7; 300]).into());
+    fields.insert("map".to_string(), Element::Map(ValueIdent::String, map));
+
+    Element::Struct(fields)
+}
+
+#[test]
+fn async_writer_identical() {
+    // cloned, as struct field order is not fixed between maps.
+    let element = example();
+    assert_eq!(marshall_async_vec(element.clone()), marshall_vec(element).unwrap());
+    assert_eq!(marshall_async_vec(Element::Unit), vec![0]);
+}
+
+#[test]
+fn async_writer_chunks() {
+    // larger than a single chunk, with nested containers.
+    let element = Element::List((0..1000).map(|_| example()).collect());
+    assert_eq!(marshall_async_vec(element.clone()), marshall_vec(element).unwrap());
+}
+
+#[cfg(feature="compression")]
+#[test]
+fn async_writer_compression() {
+    use crate::into::compression::CompressElement;
+    use crate::unmarshall_vec;
+
+    let element = Element::List(vec![example().compress(), Element::Unit.compress()]);
+    assert_eq!(marshall_async_vec(element.clone()), marshall_vec(element).unwrap());
+
+    // compressed bytes are prefixed with their length, as the reader expects.
+    let element = Element::List(vec![Value::String("Hello".repeat(50)).into()]).compress();
+    assert_eq!(unmarshall_vec(marshall_async_vec(element.clone())).unwrap(), element);
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn async_writer_serde() {
+    use crate::{to_bytes, to_writer_async};
+
+    let data = vec![Some("Hello".to_string()), None];
+
+    let mut buffer = Vec::new();
+    tokio_test::block_on(to_writer_async(&mut buffer, &data)).unwrap();
+    assert_eq!(buffer, to_bytes(&data).unwrap());
+}
@@ -0,0 +1,108 @@
+use std::io::{BufReader, Cursor, Read};
+
+use crate::{Element, marshall_vec, unmarshall, unmarshall_buffered, unmarshall_vec, Value};
+use crate::error::{Limit, TychoError};
+use crate::read::options::{Limiter, ReadOptions};
+use crate::read::string::read_tstring;
+use crate::view::{ElementRef, ValueRef};
+
+fn chars() -> Element {
+    Element::List(vec!['a', 'é', '€', '🦀'].into_iter().map(|x| Element::Value(Value::Char(x))).collect())
+}
+
+#[test]
+fn buffer_chars() {
+    let bytes = marshall_vec(chars()).unwrap();
+
+    assert_eq!(unmarshall_vec(bytes.clone()).unwrap(), chars());
+    assert_eq!(unmarshall(&mut Cursor::new(bytes.clone())).unwrap(), chars());
+
+    match ElementRef::new(&bytes).unwrap() {
+        ElementRef::List(list) => assert_eq!(list.get(3).unwrap(), Some(ElementRef::Value(ValueRef::Char('🦀')))),
+        _ => panic!("expected a list")
+    }
+}
+
+#[test]
+fn buffer_invalid_char() {
+    // a continuation byte can not start a character.
+    let bytes = vec![0x01, 0x03, 0x80];
+    assert!(matches!(unmarshall_vec(bytes).unwrap_err().inner(), TychoError::Utf8(_)));
+}
+
+#[test]
+fn buffer_unmarshall_buffered_position() {
+    // the reader is left directly after the element, so the next can be read.
+    let mut bytes = marshall_vec(Element::Value(Value::String("foo".to_string()))).unwrap();
+    let length = bytes.len() as u64;
+    bytes.extend(marshall_vec(Element::Variant("bar".to_string(), Box::new(Element::Unit))).unwrap());
+
+    let mut reader = Cursor::new(bytes.clone());
+    assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Value(Value::String("foo".to_string())));
+    assert_eq!(reader.position(), length);
+    assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Variant("bar".to_string(), Box::new(Element::Unit)));
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert!(rest.is_empty());
+
+    // bytes read ahead are kept in the caller's buffer.
+    let mut reader = BufReader::with_capacity(4, bytes.as_slice());
+    assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Value(Value::String("foo".to_string())));
+    assert_eq!(unmarshall_buffered(&mut reader).unwrap(), Element::Variant("bar".to_string(), Box::new(Element::Unit)));
+}
+
+#[test]
+fn buffer_unmarshall_position() {
+    // plain readers are read no further than the element.
+    let mut reader = Cursor::new(vec![0x00, 0x01, 0x01, 0x01]);
+    assert_eq!(unmarshall(&mut reader).unwrap(), Element::Unit);
+    assert_eq!(reader.position(), 1);
+    assert_eq!(unmarshall(&mut reader).unwrap(), Element::Value(Value::Boolean(true)));
+    assert_eq!(reader.position(), 4);
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn buffer_from_reader_position() {
+    let mut reader = Cursor::new(vec![0x01, 0x01, 0x01, 0x01, 0x01, 0x00]);
+    assert!(crate::from_reader::<_, bool>(&mut reader).unwrap());
+    assert_eq!(reader.position(), 3);
+    assert!(!crate::from_reader::<_, bool>(&mut reader).unwrap());
+}
+
+#[test]
+fn buffer_unmarshall_large() {
+    // larger than a single held byte, read from a reader with no buffer of its own.
+    let element = Element::List((0..10_000).map(|x| Element::Value(Value::String(x.to_string()))).collect());
+    let bytes = marshall_vec(element.clone()).unwrap();
+    assert_eq!(unmarshall(&mut bytes.as_slice()).unwrap(), element);
+}
+
+#[test]
+fn buffer_tstring_chunked() {
+    let mut bytes = "a".repeat(100).into_bytes();
+    bytes.extend([0x00, 0x01]);
+
+    let mut reader = BufReader::with_capacity(3, bytes.as_slice());
+    assert_eq!(read_tstring(&mut reader, &mut Limiter::default()).unwrap(), "a".repeat(100));
+
+    // the terminator is consumed, and nothing after it.
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, vec![0x01]);
+
+    let mut reader = BufReader::with_capacity(3, bytes.as_slice());
+    let mut limiter = Limiter::new(ReadOptions { max_string_len: Some(10), ..ReadOptions::default() });
+    let error = read_tstring(&mut reader, &mut limiter).unwrap_err();
+    assert!(matches!(error, TychoError::LimitExceeded { limit: Limit::StringLength, .. }));
+}
+
+#[cfg(feature="async_tokio")]
+#[test]
+fn buffer_chars_async() {
+    tokio_test::block_on(async {
+        let bytes = marshall_vec(chars()).unwrap();
+        assert_eq!(crate::unmarshall_async(&mut Cursor::new(bytes)).await.unwrap(), chars());
+    });
+}
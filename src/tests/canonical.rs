@@ -0,0 +1,136 @@
+use crate::{Element, ElementMap, is_canonical, marshall_canonical_vec, marshall_vec, Number, unmarshall_vec, Value};
+use crate::error::TychoError;
+use crate::ident::{NumberIdent, ValueIdent};
+
+fn structure(keys: &[&str]) -> Element {
+    let mut fields = ElementMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        fields.insert(key.to_string(), Element::Value(Value::Number(Number::Unsigned8(i as u8))));
+    }
+    Element::Struct(fields)
+}
+
+#[test]
+fn canonical_struct_sorted() {
+    let bytes = marshall_canonical_vec(structure(&["b", "a", "ab"])).unwrap();
+
+    if let Element::Struct(fields) = unmarshall_vec(bytes.clone()).unwrap() {
+        assert_eq!(fields.len(), 3);
+    } else {
+        panic!("expected struct")
+    }
+
+    // keys are sorted by their terminated encoding: "a\0" < "ab\0" < "b\0"
+    assert_eq!(bytes, vec![
+        5, 19,
+        97, 0, 1, 4, 1, 1,
+        97, 98, 0, 1, 4, 1, 2,
+        98, 0, 1, 4, 1, 0
+    ]);
+    assert!(is_canonical(&bytes));
+}
+
+#[test]
+fn canonical_map_sorted() {
+    let mut a = ElementMap::new();
+    a.insert(Value::Number(Number::Unsigned16(300)), Element::Unit);
+    a.insert(Value::Number(Number::Unsigned16(2)), Element::Unit);
+
+    let mut b = ElementMap::new();
+    b.insert(Value::Number(Number::Unsigned16(2)), Element::Unit);
+    b.insert(Value::Number(Number::Unsigned16(300)), Element::Unit);
+
+    let ident = ValueIdent::Number(NumberIdent::Unsigned16);
+    let a = marshall_canonical_vec(Element::Map(ident.clone(), a)).unwrap();
+    let b = marshall_canonical_vec(Element::Map(ident, b)).unwrap();
+
+    assert_eq!(a, b);
+    assert!(is_canonical(&a));
+}
+
+#[test]
+fn canonical_nan() {
+    let nan = f64::from_bits(0x7FF8_0000_0000_0001);
+    assert!(nan.is_nan());
+
+    let element = Element::Value(Value::Number(Number::Float64(nan)));
+    assert_eq!(
+        marshall_canonical_vec(element.clone()).unwrap(),
+        marshall_vec(Element::Value(Value::Number(Number::Float64(f64::NAN)))).unwrap()
+    );
+    assert!(!is_canonical(&marshall_vec(element).unwrap()));
+}
+
+#[test]
+fn canonical_bit() {
+    let bytes = marshall_canonical_vec(Element::Value(Value::Number(Number::Bit(true)))).unwrap();
+    assert!(is_canonical(&bytes));
+    assert_eq!(unmarshall_vec(bytes).unwrap(), Element::Value(Value::Number(Number::Bit(true))));
+}
+
+#[test]
+fn canonical_rejects() {
+    // duplicate key
+    assert!(!is_canonical(&[5, 6, 97, 0, 0, 97, 0, 0]));
+    // trailing bytes
+    assert!(!is_canonical(&[0, 0]));
+    // non-minimal length prefix
+    assert!(!is_canonical(&[6, 0x81, 0x00, 0]));
+    // non-canonical boolean
+    assert!(!is_canonical(&[1, 1, 2]));
+    // truncated
+    assert!(!is_canonical(&[5, 4, 97]));
+}
+
+#[test]
+fn canonical_duplicate_key() {
+    // distinct NaNs are normalised to the same key.
+    let mut map = ElementMap::new();
+    map.insert(Value::Number(Number::Float64(f64::NAN)), Element::Unit);
+    map.insert(Value::Number(Number::Float64(f64::from_bits(0x7FF8_0000_0000_0001))), Element::Unit);
+    assert_eq!(map.len(), 2);
+
+    let error = marshall_canonical_vec(Element::Map(ValueIdent::Number(NumberIdent::Float64), map)).unwrap_err();
+    assert!(matches!(error, TychoError::DuplicateKey));
+}
+
+#[test]
+fn canonical_types_kept() {
+    // numbers are not narrowed to their smallest width.
+    let small = marshall_canonical_vec(Element::Value(Value::Number(Number::Unsigned8(10)))).unwrap();
+    let wide = marshall_canonical_vec(Element::Value(Value::Number(Number::Unsigned32(10)))).unwrap();
+    assert_eq!(small, vec![1, 4, 1, 10]);
+    assert_eq!(wide, vec![1, 4, 3, 0, 0, 0, 10]);
+    assert!(is_canonical(&small) && is_canonical(&wide));
+
+    // lists of values are not converted to arrays, or the reverse.
+    let values = vec![Value::Boolean(true), Value::Boolean(false)];
+    let list = marshall_canonical_vec(Element::List(values.iter().cloned().map(Element::Value).collect())).unwrap();
+    let array = marshall_canonical_vec(Element::Array(ValueIdent::Boolean, values)).unwrap();
+    assert_ne!(list, array);
+    assert!(is_canonical(&list) && is_canonical(&array));
+}
+
+#[cfg(feature="compression")]
+#[test]
+fn canonical_compression() {
+    let error = marshall_canonical_vec(Element::Compression(Default::default(), Box::new(Element::Unit))).unwrap_err();
+    assert!(matches!(error, TychoError::NonCanonical));
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn canonical_serde() {
+    use serde::Serialize;
+    use crate::to_bytes_canonical;
+
+    #[derive(Serialize)]
+    struct Example {
+        zulu: u8,
+        alpha: Vec<u8>
+    }
+
+    let bytes = to_bytes_canonical(Example { zulu: 1, alpha: vec![1, 2] }).unwrap();
+    assert!(is_canonical(&bytes));
+    assert_eq!(&bytes[2..8], &[97, 108, 112, 104, 97, 0]);
+}
@@ -0,0 +1,188 @@
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{Element, marshall_vec, ReadOptions, Value};
+use crate::codec::{DEFAULT_MAX_DEPTH, Framing, TychoCodec};
+use crate::error::{Limit, TychoError};
+use crate::ident::ValueIdent;
+use crate::write::length::write_length;
+
+fn records() -> Vec<Element> {
+    vec![
+        Element::Unit,
+        Element::Value(Value::String("Hello".to_string())),
+        Element::Option(Some(Box::new(Element::Variant("Foo".to_string(), Box::new(Element::Unit))))),
+        Element::List(vec![Element::Option(None), Element::Value(Value::Boolean(true))]),
+        Element::Array(ValueIdent::Boolean, vec![Value::Boolean(false)]),
+        Element::Array(ValueIdent::Null, vec![]),
+    ]
+}
+
+fn encode_all(codec: &mut TychoCodec) -> BytesMut {
+    let mut buffer = BytesMut::new();
+    for record in records() {
+        codec.encode(record, &mut buffer).unwrap();
+    }
+    buffer
+}
+
+fn decode_all(codec: &mut TychoCodec, buffer: &mut BytesMut) -> Vec<Element> {
+    let mut elements = Vec::new();
+    while let Some(element) = codec.decode(buffer).unwrap() {
+        elements.push(element);
+    }
+    elements
+}
+
+#[test]
+fn codec_length_prefixed() {
+    let mut codec = TychoCodec::new();
+    let mut buffer = encode_all(&mut codec);
+
+    // each frame is the element, prefixed with its size.
+    assert_eq!(&buffer[..2], &[1, 0]);
+
+    assert_eq!(decode_all(&mut codec, &mut buffer), records());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn codec_self_delimited() {
+    let mut codec = TychoCodec::new().with_framing(Framing::SelfDelimited);
+    let mut buffer = encode_all(&mut codec);
+
+    // frames are the same bytes as marshalled elements.
+    let expected: Vec<u8> = records().into_iter().flat_map(|x| marshall_vec(x).unwrap()).collect();
+    assert_eq!(&buffer[..], &expected[..]);
+
+    assert_eq!(decode_all(&mut codec, &mut buffer), records());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn codec_partial_frames() {
+    for framing in [Framing::LengthPrefixed, Framing::SelfDelimited] {
+        let mut codec = TychoCodec::new().with_framing(framing);
+        let bytes = encode_all(&mut codec);
+
+        // feed the bytes one at a time, as if from a slow stream.
+        let mut buffer = BytesMut::new();
+        let mut elements = Vec::new();
+        for byte in bytes.iter() {
+            buffer.extend_from_slice(&[*byte]);
+            if let Some(element) = codec.decode(&mut buffer).unwrap() {
+                elements.push(element);
+            }
+        }
+
+        assert_eq!(elements, records());
+    }
+}
+
+#[test]
+fn codec_max_frame_size() {
+    let element = Element::Value(Value::String("Hello World".to_string()));
+
+    for framing in [Framing::LengthPrefixed, Framing::SelfDelimited] {
+        let mut codec = TychoCodec::new().with_framing(framing);
+        let mut buffer = BytesMut::new();
+        codec.encode(element.clone(), &mut buffer).unwrap();
+
+        let mut limited = codec.with_max_frame_size(4);
+        assert!(matches!(
+            limited.encode(element.clone(), &mut BytesMut::new()),
+            Err(TychoError::LimitExceeded { limit: Limit::FrameSize, max: 4 })
+        ));
+        assert!(matches!(
+            limited.decode(&mut buffer),
+            Err(TychoError::LimitExceeded { limit: Limit::FrameSize, max: 4 })
+        ));
+    }
+}
+
+#[test]
+fn codec_incomplete_frame_too_large() {
+    // a list which declares a large size, before any of its contents arrive.
+    let mut buffer = BytesMut::from(&[0x06, 0xFF, 0xFF, 0x03][..]);
+    let mut codec = TychoCodec::new().with_framing(Framing::SelfDelimited).with_max_frame_size(16);
+
+    assert!(matches!(
+        codec.decode(&mut buffer),
+        Err(TychoError::LimitExceeded { limit: Limit::FrameSize, .. })
+    ));
+}
+
+#[test]
+fn codec_deep_frame() {
+    // a chain of options, which is too deep to read, whether or not it has ended.
+    let mut incomplete = BytesMut::from(&vec![0x03; 4 * 1024 * 1024][..]);
+    let mut codec = TychoCodec::new().with_framing(Framing::SelfDelimited);
+    assert!(matches!(
+        codec.decode(&mut incomplete).unwrap_err().inner(),
+        TychoError::LimitExceeded { limit: Limit::Depth, .. }
+    ));
+
+    // without a depth limit, the incomplete chain is walked without recursing.
+    let mut codec = codec.with_options(ReadOptions::default());
+    assert!(codec.decode(&mut incomplete).unwrap().is_none());
+
+    let mut frame = vec![0x03; DEFAULT_MAX_DEPTH];
+    frame.push(0x00);
+
+    let mut prefixed = Vec::new();
+    write_length(&mut prefixed, frame.len()).unwrap();
+    prefixed.extend(&frame);
+
+    for (framing, bytes) in [(Framing::LengthPrefixed, prefixed), (Framing::SelfDelimited, frame)] {
+        let mut codec = TychoCodec::new().with_framing(framing);
+        let mut buffer = BytesMut::from(&bytes[..]);
+
+        assert!(matches!(
+            codec.decode(&mut buffer).unwrap_err().inner(),
+            TychoError::LimitExceeded { limit: Limit::Depth, .. }
+        ));
+    }
+}
+
+#[test]
+fn codec_incomplete_variant_too_long() {
+    // a variant name which has not ended, but is already longer than the limit.
+    let mut buffer = BytesMut::from(&[0x04, b'a', b'b', b'c', b'd', b'e'][..]);
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+    let mut codec = TychoCodec::new().with_framing(Framing::SelfDelimited).with_options(options);
+
+    assert!(matches!(
+        codec.decode(&mut buffer),
+        Err(TychoError::LimitExceeded { limit: Limit::StringLength, max: 4 })
+    ));
+}
+
+#[test]
+fn codec_trailing_data() {
+    // the frame holds a unit element, followed by an extra byte.
+    let mut buffer = BytesMut::from(&[2, 0x00, 0x00][..]);
+
+    let error = TychoCodec::new().decode(&mut buffer).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::TrailingData));
+    assert_eq!(error.offset(), Some(1));
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn serde_codec() {
+    use std::collections::HashMap;
+    use crate::codec::SerdeCodec;
+
+    let mut codec = SerdeCodec::<HashMap<String, u32>>::from(TychoCodec::new().with_framing(Framing::SelfDelimited));
+    let mut buffer = BytesMut::new();
+
+    let mut value = HashMap::new();
+    value.insert("foo".to_string(), 10);
+    codec.encode(value.clone(), &mut buffer).unwrap();
+    codec.encode(HashMap::new(), &mut buffer).unwrap();
+
+    assert_eq!(codec.decode(&mut buffer).unwrap(), Some(value));
+    assert_eq!(codec.decode(&mut buffer).unwrap(), Some(HashMap::new()));
+    assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+}
+
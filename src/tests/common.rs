@@ -0,0 +1,16 @@
+//! Fixtures shared between tests.
+use crate::{Element, ElementMap, Value};
+
+pub(crate) fn string(x: &str) -> Element {
+    Element::Value(Value::String(x.to_string()))
+}
+
+pub(crate) fn structure(fields: Vec<(&str, Element)>) -> Element {
+    Element::Struct(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect::<ElementMap<_, _>>())
+}
+
+/// Returns true if `part` starts within `bytes`, so was borrowed from it rather than copied.
+pub(crate) fn within(bytes: &[u8], part: &[u8]) -> bool {
+    let range = bytes.as_ptr_range();
+    range.start <= part.as_ptr() && part.as_ptr() < range.end
+}
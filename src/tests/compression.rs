@@ -0,0 +1,170 @@
+use crate::{Element, ElementMap, marshall_vec, unmarshall_vec, Value};
+use crate::compression::{Codec, CompressElement, Compression};
+use crate::error::TychoError;
+use crate::view::ElementRef;
+
+fn example() -> Element {
+    let mut fields = ElementMap::new();
+    fields.insert("name".to_string(), Element::Value(Value::String("example".repeat(20))));
+    fields.insert("data".to_string(), Element::Value(Value::Bytes(vec![7; 1000])));
+    fields.insert("list".to_string(), Element::List(vec![Element::Unit, Element::Option(None)]));
+    Element::Struct(fields)
+}
+
+/// The codecs enabled by features.
+fn codecs() -> Vec<Codec> {
+    #[allow(unused_mut)]
+    let mut codecs = vec![Codec::Gzip];
+    #[cfg(feature="compression_zstd")]
+    codecs.push(Codec::Zstd);
+    #[cfg(feature="compression_lz4")]
+    codecs.push(Codec::Lz4);
+    #[cfg(feature="compression_brotli")]
+    codecs.push(Codec::Brotli);
+    codecs
+}
+
+/// Each codec, with the prefix of its compression elements.
+const PREFIXES: [(Codec, u8); 4] = [(Codec::Gzip, 0xF0), (Codec::Zstd, 0xF1), (Codec::Lz4, 0xF2), (Codec::Brotli, 0xF3)];
+
+#[test]
+fn compression_codecs() {
+    for (codec, prefix) in PREFIXES.iter().filter(|(codec, _)| codecs().contains(codec)) {
+        let element = example().compress_with(*codec, 5);
+        let bytes = marshall_vec(element.clone()).unwrap();
+
+        // the prefix identifies the codec, and is followed by the size of the compressed bytes.
+        assert_eq!(bytes[0], *prefix);
+        assert_eq!(bytes[1] as usize, bytes.len() - 2);
+        assert!(bytes.len() < marshall_vec(example()).unwrap().len());
+
+        assert_eq!(unmarshall_vec(bytes).unwrap(), element);
+    }
+}
+
+#[test]
+fn compression_within_containers() {
+    let element = Element::List(codecs().into_iter()
+        .map(|codec| Element::Unit.compress_with(codec, 1))
+        .chain(vec![Element::Value(Value::Boolean(true))])
+        .collect());
+
+    assert_eq!(unmarshall_vec(marshall_vec(element.clone()).unwrap()).unwrap(), element);
+}
+
+#[test]
+fn compression_levels() {
+    // the level is not read back, and does not affect equality.
+    let fast = example().compress_with(Codec::Gzip, 1);
+    let small = example().compress_with(Codec::Gzip, 100);
+    assert_eq!(fast, small);
+    assert_eq!(fast, example().compress());
+
+    let bytes = marshall_vec(small).unwrap();
+    match unmarshall_vec(bytes).unwrap() {
+        Element::Compression(compression, _) => {
+            assert_eq!(compression.codec(), Codec::Gzip);
+            assert_eq!(compression.level(), None);
+        }
+        _ => panic!("expected a compression element")
+    }
+
+    assert_ne!(Compression::new(Codec::Gzip), Compression::new(Codec::Zstd));
+    assert_eq!(Compression::new(Codec::Zstd).with_level(3).level(), Some(3));
+}
+
+#[test]
+fn compression_view() {
+    for codec in codecs() {
+        let bytes = marshall_vec(example().compress_with(codec, 3)).unwrap();
+
+        match ElementRef::new(&bytes).unwrap() {
+            ElementRef::Compression(c) => {
+                assert_eq!(c.codec, codec);
+                assert_eq!(c.element().unwrap(), example());
+            }
+            _ => panic!("expected a compression element")
+        }
+        assert_eq!(ElementRef::new(&bytes).unwrap().to_element().unwrap(), example().compress_with(codec, 3));
+    }
+}
+
+#[cfg(not(feature="compression_zstd"))]
+#[test]
+fn compression_unsupported() {
+    let error = marshall_vec(Element::Unit.compress_with(Codec::Zstd, 3)).unwrap_err();
+    assert!(matches!(error, TychoError::UnsupportedCodec(Codec::Zstd)));
+
+    let error = unmarshall_vec(vec![0xF1, 2, 1, 2]).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::UnsupportedCodec(Codec::Zstd)));
+}
+
+#[test]
+fn compression_invalid_data() {
+    for (codec, prefix) in PREFIXES.iter().filter(|(codec, _)| codecs().contains(codec)) {
+        let error = unmarshall_vec(vec![*prefix, 8, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap_err();
+        assert!(matches!(error.inner(), TychoError::Compression(_)), "{:?}: {:?}", codec, error);
+        assert_eq!(error.offset(), Some(0));
+    }
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn compression_partial() {
+    use crate::partial::{PartialElement, PartialReader};
+
+    for codec in codecs() {
+        let element = Element::List(vec![Element::Unit, example().compress_with(codec, 3)]);
+        let mut reader = PartialReader::from_vec(marshall_vec(element.clone()).unwrap());
+
+        let list = match reader.element().unwrap() {
+            PartialElement::List(list) => list,
+            _ => panic!("expected a list")
+        };
+        let mut compressed = match list.nth(&mut reader, 1).unwrap() {
+            Some(PartialElement::Compression(c)) => c,
+            _ => panic!("expected a compression element")
+        };
+        assert_eq!(compressed.codec, codec);
+
+        // the element is read from the decompressed bytes.
+        assert_eq!(compressed.element(&mut reader).unwrap(), example());
+
+        let mut inner = compressed.reader(&mut reader).unwrap();
+        let record = match inner.element().unwrap() {
+            PartialElement::Struct(s) => s,
+            _ => panic!("expected a struct")
+        };
+        assert!(matches!(record.get(&mut inner, "list").unwrap(), Some(PartialElement::List(_))));
+
+        assert_eq!(PartialElement::from(list).read_full(&mut reader).unwrap(), element);
+    }
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn compression_serde() {
+    use crate::from_bytes;
+
+    for codec in codecs() {
+        let bytes = marshall_vec(Element::Value(Value::String("Hello".to_string())).compress_with(codec, 3)).unwrap();
+        assert_eq!(from_bytes::<String>(&bytes).unwrap(), "Hello");
+    }
+}
+
+#[cfg(feature="async_tokio")]
+#[test]
+fn compression_async() {
+    use crate::{marshall_async, unmarshall_async};
+
+    for codec in codecs() {
+        let element = Element::List(vec![example().compress_with(codec, 3), Element::Unit]);
+
+        let mut bytes = Vec::new();
+        tokio_test::block_on(marshall_async(&mut bytes, element.clone())).unwrap();
+        assert_eq!(bytes, marshall_vec(element.clone()).unwrap());
+
+        let read = tokio_test::block_on(unmarshall_async(&mut bytes.as_slice())).unwrap();
+        assert_eq!(read, element);
+    }
+}
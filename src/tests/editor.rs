@@ -0,0 +1,159 @@
+use std::io::Cursor;
+
+use crate::{Element, marshall_vec, unmarshall_vec};
+use crate::error::TychoError;
+use crate::ident::ElementIdent;
+use crate::partial::{PartialEditor, PartialElement, PartialReader};
+use crate::partial::types::{PartialList, PartialStruct};
+use crate::tests::common::{string, structure};
+
+fn editor(element: &Element) -> PartialEditor<Cursor<Vec<u8>>> {
+    PartialEditor::new(PartialReader::from(Cursor::new(marshall_vec(element.clone()).unwrap())))
+}
+
+fn contents(editor: PartialEditor<Cursor<Vec<u8>>>) -> Element {
+    unmarshall_vec(editor.into_inner().into_inner().into_inner()).unwrap()
+}
+
+fn root_struct(editor: &mut PartialEditor<Cursor<Vec<u8>>>) -> PartialStruct {
+    editor.reader().jump(&0).unwrap();
+    match editor.reader().element().unwrap() {
+        PartialElement::Struct(s) => s,
+        _ => panic!("expected a struct")
+    }
+}
+
+fn field_list(editor: &mut PartialEditor<Cursor<Vec<u8>>>, key: &str) -> PartialList {
+    let root = root_struct(editor);
+    match root.get(editor.reader(), key).unwrap() {
+        Some(PartialElement::List(list)) => list,
+        Some(PartialElement::Option(Some(x))) => match *x {
+            PartialElement::List(list) => list,
+            _ => panic!("expected a list")
+        },
+        _ => panic!("expected a list")
+    }
+}
+
+#[test]
+fn editor_set_field() {
+    let element = structure(vec![("name", string("foo")), ("items", Element::List(vec![Element::Unit]))]);
+    let mut editor = editor(&element);
+
+    // replace an existing field with a larger value, and add another.
+    let pointer = root_struct(&mut editor).pointer;
+    editor.set_field(&pointer, "name", string(&"x".repeat(200))).unwrap();
+    let pointer = root_struct(&mut editor).pointer;
+    editor.set_field(&pointer, "added", Element::Unit).unwrap();
+
+    assert_eq!(contents(editor), structure(vec![
+        ("name", string(&"x".repeat(200))),
+        ("items", Element::List(vec![Element::Unit])),
+        ("added", Element::Unit),
+    ]));
+}
+
+#[test]
+fn editor_remove_field() {
+    let element = structure(vec![("name", string(&"x".repeat(200))), ("other", Element::Unit)]);
+    let mut editor = editor(&element);
+
+    let pointer = root_struct(&mut editor).pointer;
+    assert!(editor.remove_field(&pointer, "name").unwrap());
+    let pointer = root_struct(&mut editor).pointer;
+    assert!(!editor.remove_field(&pointer, "missing").unwrap());
+
+    // the data is shortened, as well as the size prefix.
+    let expected = structure(vec![("other", Element::Unit)]);
+    let bytes = editor.into_inner().into_inner().into_inner();
+    assert_eq!(bytes, marshall_vec(expected).unwrap());
+}
+
+#[test]
+fn editor_nested_items() {
+    // a list within an option within a struct, within a list.
+    let inner = structure(vec![("items", Element::Option(Some(Box::new(Element::List(vec![string("a"), string("c")])))))]);
+    let element = structure(vec![("first", Element::List(vec![Element::Unit])), ("inner", Element::List(vec![inner]))]);
+    let mut editor = editor(&element);
+
+    let nested = |editor: &mut PartialEditor<Cursor<Vec<u8>>>| {
+        let outer = field_list(editor, "inner");
+        let record = match outer.nth(editor.reader(), 0).unwrap() {
+            Some(PartialElement::Struct(s)) => s,
+            _ => panic!("expected a struct")
+        };
+        match record.get(editor.reader(), "items").unwrap() {
+            Some(PartialElement::Option(Some(x))) => match *x {
+                PartialElement::List(list) => list.pointer,
+                _ => panic!("expected a list")
+            },
+            _ => panic!("expected an option")
+        }
+    };
+
+    let pointer = nested(&mut editor);
+    editor.insert_item(&pointer, 1, string(&"b".repeat(300))).unwrap();
+    let pointer = nested(&mut editor);
+    editor.insert_item(&pointer, 3, string("d")).unwrap();
+    let pointer = nested(&mut editor);
+    assert!(editor.remove_item(&pointer, 0).unwrap());
+    let pointer = nested(&mut editor);
+    assert!(!editor.remove_item(&pointer, 5).unwrap());
+    let pointer = nested(&mut editor);
+    assert!(matches!(editor.insert_item(&pointer, 5, Element::Unit), Err(TychoError::OutOfBounds { index: 5, length: 3 })));
+
+    let items = Element::List(vec![string(&"b".repeat(300)), string("c"), string("d")]);
+    let inner = structure(vec![("items", Element::Option(Some(Box::new(items))))]);
+    assert_eq!(contents(editor), structure(vec![("first", Element::List(vec![Element::Unit])), ("inner", Element::List(vec![inner]))]));
+}
+
+#[test]
+fn editor_large_tail() {
+    // the list size crosses a length prefix width, and the bytes after the edit span several chunks.
+    let tail = string(&"t".repeat(16_200));
+    let element = structure(vec![("items", Element::List(vec![Element::Unit, tail.clone()]))]);
+    let mut editor = editor(&element);
+
+    let pointer = field_list(&mut editor, "items").pointer;
+    editor.insert_item(&pointer, 0, string(&"b".repeat(300))).unwrap();
+
+    let grown = structure(vec![("items", Element::List(vec![string(&"b".repeat(300)), Element::Unit, tail]))]);
+    assert_eq!(unmarshall_vec(editor.reader().reader.get_ref().clone()).unwrap(), grown);
+
+    let pointer = field_list(&mut editor, "items").pointer;
+    assert!(editor.remove_item(&pointer, 0).unwrap());
+
+    assert_eq!(contents(editor), element);
+}
+
+#[test]
+fn editor_replace() {
+    let element = structure(vec![("first", Element::List(vec![])), ("second", Element::List(vec![Element::Unit]))]);
+    let mut editor = editor(&element);
+
+    // replace an empty container, which ends at the start of its contents.
+    let pointer = field_list(&mut editor, "first").pointer;
+    editor.replace(&pointer, string("replaced")).unwrap();
+
+    let pointer = field_list(&mut editor, "second").pointer;
+    let root = root_struct(&mut editor).pointer;
+    assert!(matches!(
+        editor.set_field(&pointer, "x", Element::Unit),
+        Err(TychoError::ElementMismatch { expected: ElementIdent::Struct, found: ElementIdent::List })
+    ));
+    editor.replace(&root, Element::Unit).unwrap();
+
+    assert_eq!(contents(editor), Element::Unit);
+}
+
+#[cfg(feature="partial_state")]
+#[test]
+fn editor_outdated_pointer() {
+    let element = structure(vec![("first", Element::List(vec![]))]);
+    let mut editor = editor(&element);
+
+    let pointer = root_struct(&mut editor).pointer;
+    editor.set_field(&pointer, "second", Element::Unit).unwrap();
+
+    assert!(matches!(editor.set_field(&pointer, "third", Element::Unit), Err(TychoError::OutdatedPointer)));
+}
@@ -0,0 +1,186 @@
+use std::convert::TryFrom;
+
+use crate::{Element, ElementMap, marshall_vec, Number, unmarshall_vec, Value};
+use crate::collections::{Array, List, Map};
+use crate::error::{PathSegment, TychoError};
+use crate::ident::{ElementIdent, NumberIdent, ValueIdent};
+use crate::tests::common::structure;
+
+/// Marshall an element, replacing the element after a given key with an invalid ident.
+fn corrupt(element: Element, key: &[u8]) -> (Vec<u8>, u64) {
+    let mut bytes = marshall_vec(element).unwrap();
+    let offset = bytes.windows(key.len()).position(|x| x == key).unwrap() + key.len();
+    bytes[offset] = 0x99;
+    (bytes, offset as u64)
+}
+
+fn users() -> Element {
+    let city = structure(vec![("city", Value::String("Paris".to_string()).into())]);
+    structure(vec![("users", Element::List(vec![
+        structure(vec![("name", Value::String("Sam".to_string()).into())]),
+        structure(vec![("address", city)]),
+    ]))])
+}
+
+#[test]
+fn errors_located() {
+    let (bytes, offset) = corrupt(users(), b"city\0");
+    let error = unmarshall_vec(bytes).unwrap_err();
+
+    assert!(matches!(error.inner(), TychoError::InvalidIdent { found: 0x99, .. }));
+    assert_eq!(error.offset(), Some(offset));
+    assert_eq!(error.path().unwrap().to_string(), ".users[1].address.city");
+    assert_eq!(error.to_string(), format!(
+        "Found invalid ident byte '153' when reading element ident (at byte {}, .users[1].address.city)", offset));
+}
+
+#[test]
+fn errors_located_map() {
+    let mut map = ElementMap::new();
+    map.insert(Value::String("key".to_string()), Element::Variant("Name".to_string(), Box::new(Element::Unit)));
+
+    let (bytes, offset) = corrupt(Element::Map(ValueIdent::String, map), b"Name\0");
+    let error = unmarshall_vec(bytes).unwrap_err();
+
+    assert_eq!(error.offset(), Some(offset));
+    assert_eq!(error.path().unwrap().0, vec![
+        PathSegment::Key(Value::String("key".to_string())),
+        PathSegment::Variant("Name".to_string()),
+    ]);
+    assert_eq!(error.path().unwrap().to_string(), "[\"key\"]::Name");
+}
+
+#[test]
+fn errors_located_root() {
+    let error = unmarshall_vec(vec![0x99]).unwrap_err();
+    assert_eq!(error.offset(), Some(0));
+    assert_eq!(error.path().unwrap().to_string(), ".");
+}
+
+#[test]
+fn errors_container_size() {
+    // a list of size 1, holding a value of 2 bytes.
+    let error = unmarshall_vec(vec![6, 1, 1, 1, 1]).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::ContainerOverflow));
+    assert_eq!(error.offset(), Some(0));
+}
+
+#[cfg(feature="async_tokio")]
+#[test]
+fn errors_located_async() {
+    use std::io::Cursor;
+    use crate::unmarshall_async;
+
+    let (bytes, offset) = corrupt(users(), b"city\0");
+    let error = tokio_test::block_on(unmarshall_async(&mut Cursor::new(bytes))).unwrap_err();
+
+    assert_eq!(error.offset(), Some(offset));
+    assert_eq!(error.path().unwrap().to_string(), ".users[1].address.city");
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn errors_located_partial() {
+    use crate::partial::{PartialElement, PartialReader};
+
+    let (bytes, offset) = corrupt(users(), b"city\0");
+    let mut reader = PartialReader::from_vec(bytes);
+
+    let mut root = match reader.element().unwrap() {
+        PartialElement::Struct(x) => x,
+        _ => panic!("expected struct")
+    };
+    let mut list = match root.next(&mut reader).unwrap() {
+        Some((_, PartialElement::List(x))) => x,
+        _ => panic!("expected list")
+    };
+
+    assert!(list.next(&mut reader).unwrap().is_some());
+    let mut user = match list.next(&mut reader).unwrap() {
+        Some(PartialElement::Struct(x)) => x,
+        _ => panic!("expected struct")
+    };
+    let mut address = match user.next(&mut reader).unwrap() {
+        Some((_, PartialElement::Struct(x))) => x,
+        _ => panic!("expected struct")
+    };
+
+    // paths are relative to the container being read.
+    let error = address.next(&mut reader).unwrap_err();
+    assert_eq!(error.offset(), Some(offset));
+    assert_eq!(error.path().unwrap().to_string(), ".city");
+}
+
+#[test]
+fn errors_typed() {
+    assert!(matches!(unmarshall_vec(vec![1, 4, 1]).unwrap_err().inner(), TychoError::UnexpectedEof));
+    assert!(matches!(unmarshall_vec(vec![1, 2, 1, 0xFF]).unwrap_err().inner(), TychoError::Utf8(_)));
+}
+
+#[test]
+fn errors_source() {
+    use std::error::Error;
+
+    // a located error has the error it locates as its source, which has the utf-8 error as its own.
+    let error = unmarshall_vec(vec![1, 2, 1, 0xFF]).unwrap_err();
+    let source = error.source().unwrap();
+    assert!(matches!(source.downcast_ref::<TychoError>(), Some(TychoError::Utf8(_))));
+    assert!(source.source().unwrap().is::<std::string::FromUtf8Error>());
+
+    assert!(TychoError::UnexpectedEof.source().is_none());
+}
+
+#[test]
+fn errors_try_from() {
+    let number = Element::Value(Value::Number(Number::Unsigned8(1)));
+
+    match u16::try_from(number.clone()) {
+        Err(TychoError::ValueMismatch { expected, found }) => {
+            assert_eq!(found, ValueIdent::Number(NumberIdent::Unsigned8));
+            assert_eq!(expected, ValueIdent::Number(NumberIdent::Unsigned16));
+        }
+        x => panic!("expected mismatched type, found {:?}", x)
+    }
+
+    match String::try_from(Element::List(vec![number.clone()])) {
+        Err(TychoError::ElementMismatch { expected, found }) => {
+            assert_eq!(expected, ElementIdent::Value);
+            assert_eq!(found, ElementIdent::List);
+        }
+        x => panic!("expected type mismatch, found {:?}", x)
+    }
+
+    assert!(matches!(List::try_from(number.clone()),
+        Err(TychoError::ElementMismatch { expected: ElementIdent::List, found: ElementIdent::Value })));
+
+    // items which do not match the array type are an error, rather than dropped.
+    let array = Element::Array(ValueIdent::Number(NumberIdent::Unsigned8), vec![Value::Number(Number::Unsigned8(1))]);
+    assert!(matches!(Array::<u16>::try_from(array.clone()), Err(TychoError::ValueMismatch { .. })));
+    assert_eq!(Array::<u8>::try_from(array).unwrap().0, vec![1]);
+
+    // empty containers are written without a type.
+    assert!(Array::<u8>::try_from(Element::Array(ValueIdent::Null, Vec::new())).is_ok());
+    assert!(Map::<String>::try_from(Element::Map(ValueIdent::Null, ElementMap::new())).is_ok());
+}
+
+#[cfg(feature="compression")]
+#[test]
+fn errors_decompress() {
+    use crate::into::compression::DecompressElement;
+
+    assert!(matches!(Element::Unit.decompress(),
+        Err(TychoError::ElementMismatch { expected: ElementIdent::Compression, found: ElementIdent::Unit })));
+
+    // a compression element holding invalid gzip data.
+    let error = unmarshall_vec(vec![0xF0, 2, 1, 2]).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::Compression(_)));
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn errors_serde() {
+    use crate::from_bytes;
+
+    let bytes = marshall_vec(Element::Value(Value::String("foo".to_string()))).unwrap();
+    assert!(matches!(from_bytes::<u8>(&bytes), Err(TychoError::Serde(_))));
+}
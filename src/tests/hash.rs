@@ -0,0 +1,117 @@
+use crate::{Element, ElementMap, marshall_canonical_vec, Number, Value};
+use crate::error::TychoError;
+use crate::hash::{Digest, MerkleKey, MerkleTree, Sha256};
+use crate::tests::common::structure;
+
+fn number(n: u8) -> Element {
+    Element::Value(Value::Number(Number::Unsigned8(n)))
+}
+
+fn example() -> Element {
+    let mut map = ElementMap::new();
+    map.insert(Value::String("x".to_string()), number(1));
+    map.insert(Value::String("y".to_string()), number(2));
+
+    structure(vec![
+        ("foo", number(10)),
+        ("bar", Element::List(vec![number(1), structure(vec![("baz", number(3))])])),
+        ("map", Element::Map(crate::ident::ValueIdent::String, map)),
+    ])
+}
+
+#[test]
+fn hash_digest_canonical() {
+    let a = structure(vec![("foo", number(1)), ("bar", number(2))]);
+    let b = structure(vec![("bar", number(2)), ("foo", number(1))]);
+
+    let digest = a.digest::<Sha256>().unwrap();
+    assert_eq!(digest, b.digest::<Sha256>().unwrap());
+    assert_eq!(digest, Sha256::digest(marshall_canonical_vec(a).unwrap()));
+    assert_ne!(digest, structure(vec![("foo", number(2)), ("bar", number(1))]).digest::<Sha256>().unwrap());
+}
+
+#[test]
+fn hash_merkle_update() {
+    let mut tree = MerkleTree::<Sha256>::new(&example()).unwrap();
+    assert_eq!(tree.digest(), &example().merkle_digest::<Sha256>().unwrap());
+
+    let untouched = *tree.get(&MerkleKey::Field("foo")).unwrap().digest();
+
+    // replace a nested field
+    tree.update(&[MerkleKey::Field("bar"), MerkleKey::Index(1), MerkleKey::Field("baz")], &number(4)).unwrap();
+    // insert a map entry
+    let key = Value::String("z".to_string());
+    tree.update(&[MerkleKey::Field("map"), MerkleKey::Key(&key)], &number(3)).unwrap();
+
+    let mut expected = example();
+    if let Element::Struct(fields) = &mut expected {
+        if let Some(Element::List(items)) = fields.get_mut("bar") {
+            items[1] = structure(vec![("baz", number(4))]);
+        }
+        if let Some(Element::Map(_, map)) = fields.get_mut("map") {
+            map.insert(key.clone(), number(3));
+        }
+    }
+
+    assert_eq!(tree.digest(), &expected.merkle_digest::<Sha256>().unwrap());
+    assert_ne!(tree.digest(), &example().merkle_digest::<Sha256>().unwrap());
+    assert_eq!(tree.get(&MerkleKey::Field("foo")).unwrap().digest(), &untouched);
+}
+
+#[test]
+fn hash_merkle_invalid_path() {
+    let mut tree = MerkleTree::<Sha256>::new(&example()).unwrap();
+
+    assert!(matches!(tree.update(&[MerkleKey::Index(0)], &number(0)), Err(TychoError::PathMismatch)));
+    assert!(matches!(tree.update(&[MerkleKey::Field("bar"), MerkleKey::Index(5)], &number(0)),
+                     Err(TychoError::OutOfBounds { index: 5, length: 2 })));
+    assert!(matches!(tree.update(&[MerkleKey::Field("missing"), MerkleKey::Index(0)], &number(0)), Err(TychoError::PathMismatch)));
+}
+
+#[test]
+fn hash_merkle_leaf() {
+    let element = number(5);
+    let mut bytes = vec![0x00];
+    bytes.extend(marshall_canonical_vec(element.clone()).unwrap());
+
+    assert_eq!(element.merkle_digest::<Sha256>().unwrap(), Sha256::digest(&bytes));
+}
+
+#[cfg(feature="hash_blake3")]
+#[test]
+fn hash_blake3() {
+    use crate::hash::Blake3;
+
+    let digest = example().digest::<Blake3>().unwrap();
+    assert_eq!(digest.as_slice(), blake3::hash(&marshall_canonical_vec(example()).unwrap()).as_bytes());
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn hash_partial() {
+    use crate::partial::{PartialElement, PartialReader};
+
+    let inner = structure(vec![("b", number(2)), ("a", Element::List(vec![number(1)]))]);
+    let root = structure(vec![("inner", inner.clone()), ("z", number(9))]);
+
+    let mut reader = PartialReader::from_vec(marshall_canonical_vec(root.clone()).unwrap());
+
+    if let PartialElement::Struct(mut s) = reader.element().unwrap() {
+        assert_eq!(s.digest::<Sha256, _>(&mut reader).unwrap(), root.digest::<Sha256>().unwrap());
+
+        let (key, value) = s.next(&mut reader).unwrap().unwrap();
+        assert_eq!(key, "inner");
+
+        if let PartialElement::Struct(inner_partial) = value {
+            assert_eq!(inner_partial.digest::<Sha256, _>(&mut reader).unwrap(), inner.digest::<Sha256>().unwrap());
+        } else {
+            panic!("expected struct")
+        }
+
+        // the head is unaffected by computing digests.
+        let (key, _) = s.next(&mut reader).unwrap().unwrap();
+        assert_eq!(key, "z");
+    } else {
+        panic!("expected struct")
+    }
+}
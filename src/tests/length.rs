@@ -1,5 +1,6 @@
-use std::io::{BufWriter, Cursor};
+use std::io::{BufReader, BufWriter, Cursor};
 
+use crate::error::TychoError;
 use crate::read::length::read_length;
 use crate::write::length::write_length;
 
@@ -37,3 +38,22 @@ sample_test!(4, 128, vec![128, 1]);
 sample_test!(5, 255, vec![255, 1]);
 sample_test!(6, 2097151, vec![255, 255, 127]);
 sample_test!(7, 2147483647, vec![255, 255, 255, 255, 7]);
+
+#[test]
+fn varlength_decode_chunked() {
+    // lengths split across buffered blocks are joined together.
+    for value in [128, 2097151, 2147483647, usize::MAX] {
+        let bytes = encode_var_length(value);
+        let mut reader = BufReader::with_capacity(1, bytes.as_slice());
+        assert_eq!(read_length(&mut reader).unwrap(), value);
+    }
+}
+
+#[test]
+fn varlength_decode_errors() {
+    let mut overflow: &[u8] = &[255, 255, 255, 255, 255, 255, 255, 255, 255, 2];
+    assert!(matches!(read_length(&mut overflow), Err(TychoError::LengthOverflow)));
+
+    let mut truncated = BufReader::with_capacity(1, &[255u8, 255][..]);
+    assert!(matches!(read_length(&mut truncated), Err(TychoError::UnexpectedEof)));
+}
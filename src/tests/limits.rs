@@ -0,0 +1,199 @@
+use std::io::Cursor;
+
+use crate::{Element, marshall_vec, ReadOptions, unmarshall_vec, unmarshall_with, Value};
+use crate::error::{Limit, TychoError};
+
+fn limited(bytes: Vec<u8>, options: ReadOptions) -> Result<Element, TychoError> {
+    unmarshall_with(&mut Cursor::new(bytes), options)
+}
+
+fn assert_limit<T: std::fmt::Debug>(result: Result<T, TychoError>, expected: Limit) {
+    match result.as_ref().map_err(TychoError::inner) {
+        Err(TychoError::LimitExceeded { limit, .. }) => assert_eq!(limit, &expected),
+        x => panic!("expected {:?} limit, found {:?}", expected, x)
+    }
+}
+
+fn nested(depth: usize) -> Vec<u8> {
+    let mut bytes = vec![0x03; depth - 1];
+    bytes.push(0x00);
+    bytes
+}
+
+#[test]
+fn limits_length_overflow() {
+    let bytes = vec![6, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F];
+    assert!(matches!(unmarshall_vec(bytes).unwrap_err().inner(), TychoError::LengthOverflow));
+}
+
+#[test]
+fn limits_declared_size() {
+    // a list declaring ~4GB, with no data behind it.
+    let bytes = vec![6, 0x80, 0x80, 0x80, 0x80, 0x10];
+    assert!(matches!(unmarshall_vec(bytes).unwrap_err().inner(), TychoError::UnexpectedEof));
+}
+
+#[test]
+fn limits_depth() {
+    let options = ReadOptions { max_depth: Some(8), ..ReadOptions::default() };
+
+    assert!(limited(nested(8), options).is_ok());
+    assert_limit(limited(nested(9), options), Limit::Depth);
+
+    // depth is counted through containers.
+    let list = Element::List(vec![Element::List(vec![Element::Unit])]);
+    let options = ReadOptions { max_depth: Some(2), ..ReadOptions::default() };
+    assert_limit(limited(marshall_vec(list).unwrap(), options), Limit::Depth);
+}
+
+#[test]
+fn limits_container() {
+    let list = marshall_vec(Element::List(vec![Element::Unit; 10])).unwrap();
+
+    let options = ReadOptions { max_container_bytes: Some(10), ..ReadOptions::default() };
+    assert!(limited(list.clone(), options).is_ok());
+
+    let options = ReadOptions { max_container_bytes: Some(9), ..ReadOptions::default() };
+    assert_limit(limited(list, options), Limit::ContainerBytes);
+}
+
+#[test]
+fn limits_string() {
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+
+    let value = marshall_vec(Value::String("Hello".to_string())).unwrap();
+    assert_limit(limited(value, options), Limit::StringLength);
+
+    let value = marshall_vec(Value::Bytes(vec![0; 5])).unwrap();
+    assert_limit(limited(value, options), Limit::StringLength);
+
+    // an unterminated key is stopped once it exceeds the limit.
+    let mut key = vec![5, 100];
+    key.extend_from_slice(&[97; 100]);
+    assert_limit(limited(key, options), Limit::StringLength);
+}
+
+#[test]
+fn limits_total() {
+    let list = Element::List(vec![Value::String("Hello".to_string()).into(); 4]);
+    let bytes = marshall_vec(list).unwrap();
+
+    // the list is 32 bytes, holding 4 strings of 5 bytes.
+    let options = ReadOptions { max_total_bytes: Some(52), ..ReadOptions::default() };
+    assert!(limited(bytes.clone(), options).is_ok());
+
+    let options = ReadOptions { max_total_bytes: Some(51), ..ReadOptions::default() };
+    assert_limit(limited(bytes, options), Limit::TotalBytes);
+}
+
+#[cfg(feature="compression")]
+#[test]
+fn limits_decompression() {
+    use crate::compression::Compression;
+    use crate::write::compress::compress;
+    use crate::write::length::write_length;
+
+    let inner = marshall_vec(Value::Bytes(vec![0; 100_000])).unwrap();
+    let compressed = compress(&inner, &Compression::default()).unwrap();
+
+    let mut bytes = vec![0xF0];
+    write_length(&mut bytes, compressed.len()).unwrap();
+    bytes.extend(compressed);
+
+    assert!(limited(bytes.clone(), ReadOptions::default()).is_ok());
+
+    let options = ReadOptions { max_container_bytes: Some(10_000), ..ReadOptions::default() };
+    assert_limit(limited(bytes, options), Limit::ContainerBytes);
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn limits_serde() {
+    use crate::{from_bytes_with, to_bytes};
+
+    let bytes = to_bytes(Some(Some(Some(10u8)))).unwrap();
+
+    let options = ReadOptions { max_depth: Some(4), ..ReadOptions::default() };
+    assert_eq!(from_bytes_with::<Option<Option<Option<u8>>>>(&bytes, options).unwrap(), Some(Some(Some(10))));
+
+    let options = ReadOptions { max_depth: Some(3), ..ReadOptions::default() };
+    assert_limit(from_bytes_with::<Option<Option<Option<u8>>>>(&bytes, options), Limit::Depth);
+
+    let bytes = to_bytes(vec!["Hello".to_string()]).unwrap();
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+    assert_limit(from_bytes_with::<Vec<String>>(&bytes, options), Limit::StringLength);
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn limits_partial() {
+    use crate::partial::PartialReader;
+
+    let options = ReadOptions { max_depth: Some(8), ..ReadOptions::default() };
+    assert!(PartialReader::from_vec(nested(8)).with_options(options).element().is_ok());
+    assert_limit(PartialReader::from_vec(nested(9)).with_options(options).element(), Limit::Depth);
+
+    let list = marshall_vec(Element::List(vec![Element::Unit; 10])).unwrap();
+    let options = ReadOptions { max_container_bytes: Some(9), ..ReadOptions::default() };
+    assert_limit(PartialReader::from_vec(list).with_options(options).element(), Limit::ContainerBytes);
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn limits_partial_errors() {
+    use crate::partial::PartialReader;
+
+    // a variant with a name over the string limit, followed by a unit.
+    let mut bytes = marshall_vec(Element::Variant("Name".to_string(), Box::new(Element::Unit))).unwrap();
+    let unit = bytes.len() as u64;
+    bytes.push(0x00);
+
+    let options = ReadOptions { max_depth: Some(3), max_string_len: Some(3), ..ReadOptions::default() };
+    let mut reader = PartialReader::from_vec(bytes).with_options(options);
+
+    // failed reads leave the depth they entered.
+    for _ in 0..5 {
+        reader.jump(&0).unwrap();
+        assert_limit(reader.element(), Limit::StringLength);
+    }
+
+    reader.jump(&unit).unwrap();
+    assert!(reader.element().is_ok());
+}
+
+#[cfg(feature="async_tokio")]
+#[test]
+fn limits_async() {
+    use futures::StreamExt;
+    use crate::{ElementStreamAsync, unmarshall_async_with};
+
+    let options = ReadOptions { max_depth: Some(8), ..ReadOptions::default() };
+    assert!(tokio_test::block_on(unmarshall_async_with(&mut Cursor::new(nested(8)), options)).is_ok());
+    assert_limit(tokio_test::block_on(unmarshall_async_with(&mut Cursor::new(nested(9)), options)), Limit::Depth);
+
+    let string = marshall_vec(Element::Value(Value::String("Hello".to_string()))).unwrap();
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+    assert_limit(tokio_test::block_on(unmarshall_async_with(&mut Cursor::new(string.clone()), options)), Limit::StringLength);
+
+    // each element of a stream is read within the limits.
+    let mut bytes = nested(2);
+    bytes.extend(string);
+    let mut elements: Vec<_> = tokio_test::block_on(ElementStreamAsync::new(Cursor::new(bytes)).with_options(options).collect());
+    assert_eq!(elements.len(), 2);
+    assert_limit(elements.pop().unwrap(), Limit::StringLength);
+    assert!(elements[0].is_ok());
+}
+
+#[cfg(all(feature="partial", feature="async_tokio"))]
+#[test]
+fn limits_partial_async() {
+    use crate::partial::PartialReader;
+
+    let options = ReadOptions { max_depth: Some(8), ..ReadOptions::default() };
+    assert!(tokio_test::block_on(PartialReader::from_vec(nested(8)).with_options(options).element_async()).is_ok());
+    assert_limit(tokio_test::block_on(PartialReader::from_vec(nested(9)).with_options(options).element_async()), Limit::Depth);
+
+    let variant = marshall_vec(Element::Variant("Name".to_string(), Box::new(Element::Unit))).unwrap();
+    let options = ReadOptions { max_string_len: Some(3), ..ReadOptions::default() };
+    assert_limit(tokio_test::block_on(PartialReader::from_vec(variant).with_options(options).element_async()), Limit::StringLength);
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use crate::{Element, ElementMap, marshall_vec, unmarshall_vec, Value};
+use crate::ident::ValueIdent;
+
+#[test]
+fn map_api() {
+    let mut map = ElementMap::new();
+    assert!(map.is_empty());
+
+    assert_eq!(map.insert("a".to_string(), Element::Unit), None);
+    assert_eq!(map.insert("b".to_string(), Element::Value(Value::Boolean(true))), None);
+    assert_eq!(map.insert("a".to_string(), Element::Value(Value::Null)), Some(Element::Unit));
+
+    assert_eq!(map.len(), 2);
+    assert!(map.contains_key("a"));
+    assert_eq!(map["a"], Element::Value(Value::Null));
+    assert_eq!(map.get("c"), None);
+
+    assert_eq!(map.remove("a"), Some(Element::Value(Value::Null)));
+    assert_eq!(map.iter().count(), 1);
+}
+
+#[test]
+fn map_hash_map() {
+    let mut hash_map = HashMap::new();
+    hash_map.insert(Value::String("x".to_string()), Element::Unit);
+    hash_map.insert(Value::String("y".to_string()), Element::Unit);
+
+    let map = ElementMap::from(hash_map.clone());
+    let element = Element::Map(ValueIdent::String, map.clone());
+    let bytes = marshall_vec(element.clone()).unwrap();
+
+    assert_eq!(unmarshall_vec(bytes).unwrap(), element);
+    assert_eq!(HashMap::from(map), hash_map);
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{Element, marshall_vec, Value};
+use crate::error::TychoError;
+use crate::partial::{PartialElement, PartialMmapReader, PartialPointer, PartialReader};
+use crate::view::{ElementRef, ValueRef};
+
+/// A file within the temp directory, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, bytes: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("tycho-{}-{}", std::process::id(), name));
+        fs::write(&path, bytes).unwrap();
+        TempFile(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn records() -> (Vec<u8>, Vec<PartialPointer>) {
+    let mut bytes = Vec::new();
+    let mut pointers = Vec::new();
+    for element in [
+        Element::Value(Value::String("foo".to_string())),
+        Element::List(vec![Element::Value(Value::Bytes(vec![1, 2, 3])), Element::Unit]),
+    ] {
+        let record = marshall_vec(element).unwrap();
+        pointers.push(PartialPointer::new(bytes.len() as u64, record.len() as u64));
+        bytes.extend(record);
+    }
+    (bytes, pointers)
+}
+
+#[test]
+fn mmap_element() {
+    let (bytes, pointers) = records();
+    let file = TempFile::new("mmap_element", &bytes);
+    let mut reader: PartialMmapReader = PartialReader::mmap(&file.0).unwrap();
+
+    assert!(matches!(reader.element().unwrap(), PartialElement::Value(Value::String(x)) if x == "foo"));
+
+    let list = reader.element_at(&pointers[1]).unwrap();
+    assert_eq!(list.read_full(&mut reader).unwrap(), Element::List(vec![Element::Value(Value::Bytes(vec![1, 2, 3])), Element::Unit]));
+}
+
+#[test]
+fn mmap_view() {
+    let (bytes, pointers) = records();
+    let file = TempFile::new("mmap_view", &bytes);
+    let reader = PartialReader::mmap(&file.0).unwrap();
+
+    assert_eq!(reader.view(&pointers[0]).unwrap(), ElementRef::Value(ValueRef::String("foo")));
+
+    let list = match reader.view(&pointers[1]).unwrap() {
+        ElementRef::List(list) => list,
+        _ => panic!("expected a list")
+    };
+    let data = list.get(0).unwrap().unwrap();
+    assert_eq!(data, ElementRef::Value(ValueRef::Bytes(&[1, 2, 3])));
+
+    // borrowed values point into the mapping.
+    let mapped = reader.slice(&PartialPointer::new(0, bytes.len() as u64)).unwrap().as_ptr_range();
+    if let ElementRef::Value(ValueRef::Bytes(x)) = data {
+        assert!(mapped.contains(&x.as_ptr()));
+    }
+}
+
+#[test]
+fn mmap_slice_out_of_bounds() {
+    let (bytes, _) = records();
+    let file = TempFile::new("mmap_slice_out_of_bounds", &bytes);
+    let reader = PartialReader::mmap(&file.0).unwrap();
+
+    let error = reader.slice(&PartialPointer::new(2, bytes.len() as u64)).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::PointerNotFound));
+    assert_eq!(error.offset(), Some(2));
+}
+
+#[test]
+fn mmap_missing_file() {
+    let path = std::env::temp_dir().join(format!("tycho-{}-missing", std::process::id()));
+    assert!(PartialReader::mmap(path).is_err());
+}
@@ -1,9 +1,55 @@
+mod common;
+
 mod encode;
 mod length;
 mod numbers;
+mod view;
+mod canonical;
+mod limits;
+mod errors;
+mod stream;
+mod query;
+mod buffer;
+mod map;
 
 #[cfg(feature="serde")]
 mod docs;
 
 #[cfg(feature="serde")]
-mod serde;
\ No newline at end of file
+mod serde;
+
+#[cfg(feature="serde")]
+mod writer;
+
+#[cfg(feature="serde")]
+mod reader;
+
+#[cfg(feature="serde")]
+mod wide;
+
+#[cfg(all(feature="serde", feature="ordered"))]
+mod ordered;
+
+#[cfg(feature="hash_sha2")]
+mod hash;
+
+#[cfg(feature="async_tokio")]
+mod async_writer;
+
+#[cfg(feature="async_tokio")]
+mod codec;
+
+#[cfg(all(feature="partial", feature="async_tokio"))]
+mod async_partial;
+
+#[cfg(feature="partial")]
+mod partial;
+
+#[cfg(feature="partial")]
+mod editor;
+
+#[cfg(feature="mmap")]
+mod mmap;
+
+#[cfg(feature="compression")]
+mod compression;
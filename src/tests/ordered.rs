@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use crate::{Element, ElementMap, marshall_vec, to_bytes, to_element, unmarshall_vec, Value};
+use crate::ident::ValueIdent;
+
+#[derive(Serialize)]
+struct Example {
+    zulu: u8,
+    alpha: bool,
+    mike: String,
+    bravo: ()
+}
+
+fn example() -> Example {
+    Example { zulu: 1, alpha: true, mike: "m".to_string(), bravo: () }
+}
+
+#[test]
+fn ordered_struct_fields() {
+    let element = to_element(example()).unwrap();
+
+    if let Element::Struct(fields) = &element {
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["zulu", "alpha", "mike", "bravo"]);
+    } else {
+        panic!("expected struct")
+    }
+
+    // both serializers write fields in declaration order.
+    assert_eq!(to_bytes(example()).unwrap(), marshall_vec(element).unwrap());
+}
+
+#[test]
+fn ordered_wire_order() {
+    let mut fields = ElementMap::new();
+    fields.insert("b".to_string(), Element::Unit);
+    fields.insert("a".to_string(), Element::Unit);
+
+    let mut map = ElementMap::new();
+    map.insert(Value::String("y".to_string()), Element::Unit);
+    map.insert(Value::String("x".to_string()), Element::Unit);
+    fields.insert("map".to_string(), Element::Map(ValueIdent::String, map));
+
+    let bytes = marshall_vec(Element::Struct(fields)).unwrap();
+    let element = unmarshall_vec(bytes.clone()).unwrap();
+
+    if let Element::Struct(fields) = &element {
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["b", "a", "map"]);
+    } else {
+        panic!("expected struct")
+    }
+
+    assert_eq!(marshall_vec(element).unwrap(), bytes);
+}
@@ -0,0 +1,341 @@
+use crate::{Element, marshall_vec, Number, Value};
+use crate::ident::{NumberIdent, ValueIdent};
+use crate::error::{PathSegment, TychoError};
+use crate::partial::{PartialElement, PartialPointer, PartialReader};
+
+fn corrupt_list() -> PartialReader<std::io::Cursor<Vec<u8>>> {
+    let mut bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Unit, Element::Unit])).unwrap();
+    // replace the second item with an invalid prefix.
+    let length = bytes.len();
+    bytes[length - 2] = 0x60;
+    PartialReader::from_vec(bytes)
+}
+
+#[test]
+fn partial_try_iter() {
+    let bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Option(None)])).unwrap();
+    let mut reader = PartialReader::from_vec(bytes);
+
+    let mut list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    let items = list.try_iter(&mut reader).collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(matches!(items[..], [PartialElement::Unit, PartialElement::Option(None)]));
+    assert!(list.finished());
+}
+
+#[test]
+fn partial_try_iter_error() {
+    let mut reader = corrupt_list();
+    let mut list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    let mut items = list.try_iter(&mut reader);
+    assert!(items.next().unwrap().is_ok());
+
+    let error = items.next().unwrap().unwrap_err();
+    assert!(matches!(error.inner(), TychoError::InvalidIdent { .. }));
+    assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn partial_item_overflow() {
+    let mut bytes = marshall_vec(Element::List(vec![Element::Unit, Element::Value(Value::String("abcdef".to_string()))])).unwrap();
+    // shorten the list, so the second item ends past it.
+    bytes[1] = 3;
+    let mut reader = PartialReader::from_vec(bytes);
+
+    let mut list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    let mut items = list.try_iter(&mut reader);
+    assert!(items.next().unwrap().is_ok());
+
+    let error = items.next().unwrap().unwrap_err();
+    assert!(matches!(error.inner(), TychoError::ContainerOverflow));
+    assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+    assert!(!list.finished());
+}
+
+#[test]
+fn partial_iter_lossy() {
+    let mut reader = corrupt_list();
+    let mut list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    // the error ends the iterator, as if the list were shorter.
+    assert_eq!(list.iter(&mut reader).count(), 1);
+    assert!(!list.finished());
+}
+
+fn record() -> Element {
+    let mut data = crate::ElementMap::new();
+    data.insert("name".to_string(), Element::Value(Value::String("Hello".to_string())));
+    data.insert("tags".to_string(), Element::Array(ValueIdent::String, vec![
+        Value::String("foo".to_string()), Value::String("bar".to_string()),
+    ]));
+    data.insert("scores".to_string(), Element::Array(ValueIdent::Number(NumberIdent::Unsigned32), vec![
+        Value::Number(Number::Unsigned32(10)), Value::Number(Number::Unsigned32(20)),
+    ]));
+    data.insert("nested".to_string(), Element::List(vec![
+        Element::Option(None), Element::Variant("Foo".to_string(), Box::new(Element::Unit)),
+    ]));
+    let mut map = crate::ElementMap::new();
+    map.insert(Value::Char('x'), Element::Unit);
+    map.insert(Value::Char('y'), Element::Value(Value::Boolean(true)));
+    data.insert("map".to_string(), Element::Map(ValueIdent::Char, map));
+    Element::Struct(data)
+}
+
+fn record_struct(reader: &mut PartialReader<std::io::Cursor<Vec<u8>>>) -> crate::partial::types::PartialStruct {
+    match reader.element().unwrap() {
+        PartialElement::Struct(s) => s,
+        _ => panic!("expected a struct")
+    }
+}
+
+#[test]
+fn partial_struct_get() {
+    let mut reader = PartialReader::from_vec(marshall_vec(record()).unwrap());
+    let record = record_struct(&mut reader);
+    let top = reader.pointer;
+
+    assert!(matches!(record.get(&mut reader, "name").unwrap(), Some(PartialElement::Value(Value::String(x))) if x == "Hello"));
+    assert!(record.get(&mut reader, "missing").unwrap().is_none());
+
+    // the reader and head are left where they were.
+    assert_eq!(reader.pointer, top);
+    assert_eq!(record.head, 0);
+}
+
+#[test]
+fn partial_list_nth() {
+    let mut reader = PartialReader::from_vec(marshall_vec(record()).unwrap());
+    let record = record_struct(&mut reader);
+
+    let list = match record.get(&mut reader, "nested").unwrap() {
+        Some(PartialElement::List(list)) => list,
+        _ => panic!("expected a list")
+    };
+
+    assert!(matches!(list.nth(&mut reader, 0).unwrap(), Some(PartialElement::Option(None))));
+    assert!(matches!(list.nth(&mut reader, 1).unwrap(), Some(PartialElement::Variant(name, _)) if name == "Foo"));
+    assert!(list.nth(&mut reader, 2).unwrap().is_none());
+}
+
+#[test]
+fn partial_map_get() {
+    let mut reader = PartialReader::from_vec(marshall_vec(record()).unwrap());
+    let record = record_struct(&mut reader);
+
+    let map = match record.get(&mut reader, "map").unwrap() {
+        Some(PartialElement::Map(map)) => map,
+        _ => panic!("expected a map")
+    };
+
+    assert!(matches!(map.get(&mut reader, &Value::Char('y')).unwrap(), Some(PartialElement::Value(Value::Boolean(true)))));
+    assert!(map.get(&mut reader, &Value::Char('z')).unwrap().is_none());
+    assert!(map.get(&mut reader, &Value::Boolean(true)).unwrap().is_none());
+}
+
+#[test]
+fn partial_array_nth() {
+    let mut reader = PartialReader::from_vec(marshall_vec(record()).unwrap());
+    let record = record_struct(&mut reader);
+
+    // fixed width values are found directly.
+    let scores = match record.get(&mut reader, "scores").unwrap() {
+        Some(PartialElement::Array(array)) => array,
+        _ => panic!("expected an array")
+    };
+    assert_eq!(scores.nth(&mut reader, 1).unwrap(), Some(Value::Number(Number::Unsigned32(20))));
+    assert_eq!(scores.nth(&mut reader, 2).unwrap(), None);
+
+    // variable width values are skipped over.
+    let tags = match record.get(&mut reader, "tags").unwrap() {
+        Some(PartialElement::Array(array)) => array,
+        _ => panic!("expected an array")
+    };
+    assert_eq!(tags.nth(&mut reader, 1).unwrap(), Some(Value::String("bar".to_string())));
+    assert_eq!(tags.nth(&mut reader, 2).unwrap(), None);
+}
+
+#[test]
+fn partial_array_nth_overflow() {
+    let array = Element::Array(ValueIdent::Number(NumberIdent::Unsigned32), vec![
+        Value::Number(Number::Unsigned32(10)),
+        Value::Number(Number::Unsigned32(20)),
+    ]);
+    let mut bytes = marshall_vec(array).unwrap();
+    // shorten the array, so the second value ends past it.
+    let length = bytes.len();
+    bytes[length - 9] = 6;
+    let mut reader = PartialReader::from_vec(bytes);
+
+    let array = match reader.element().unwrap() {
+        PartialElement::Array(array) => array,
+        _ => panic!("expected an array")
+    };
+    assert_eq!(array.nth(&mut reader, 0).unwrap(), Some(Value::Number(Number::Unsigned32(10))));
+
+    let error = array.nth(&mut reader, 1).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::ContainerOverflow));
+    assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+    assert_eq!(array.nth(&mut reader, 2).unwrap(), None);
+}
+
+#[test]
+fn partial_get_error() {
+    let mut reader = corrupt_list();
+    let list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    let error = list.nth(&mut reader, 2).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::InvalidIdent { .. }));
+    assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+}
+
+#[test]
+fn partial_read_full() {
+    let element = Element::List(vec![record(), Element::Option(Some(Box::new(Element::Unit)))]);
+    let mut reader = PartialReader::from_vec(marshall_vec(element.clone()).unwrap());
+
+    let partial = reader.element().unwrap();
+    let top = reader.pointer;
+    assert_eq!(partial.read_full(&mut reader).unwrap(), element);
+    assert_eq!(reader.pointer, top);
+
+    // a subtree can be read on its own.
+    let record = match &partial {
+        PartialElement::List(list) => list.nth(&mut reader, 0).unwrap().unwrap(),
+        _ => panic!("expected a list")
+    };
+    assert_eq!(record.read_full(&mut reader).unwrap(), self::record());
+}
+
+#[test]
+fn partial_read_full_error() {
+    let mut reader = corrupt_list();
+    let partial = reader.element().unwrap();
+
+    let error = partial.read_full(&mut reader).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::InvalidIdent { .. }));
+    assert_eq!(error.path().unwrap().0, vec![PathSegment::Index(1)]);
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn partial_deserialize() {
+    use serde::Deserialize;
+    use crate::to_element;
+
+    #[derive(Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+    struct Record {
+        name: String,
+        scores: Vec<u32>,
+    }
+
+    let records = vec![
+        Record { name: "foo".to_string(), scores: vec![1, 2] },
+        Record { name: "bar".to_string(), scores: vec![] },
+    ];
+    let mut reader = PartialReader::from_vec(marshall_vec(to_element(records.clone()).unwrap()).unwrap());
+
+    let list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    let second = list.nth(&mut reader, 1).unwrap().unwrap();
+    assert_eq!(second.deserialize::<Record, _>(&mut reader).unwrap(), records[1]);
+    assert_eq!(PartialElement::from(list).deserialize::<Vec<Record>, _>(&mut reader).unwrap(), records);
+}
+
+fn record_reader() -> (PartialReader<std::io::Cursor<Vec<u8>>>, Vec<PartialPointer>) {
+    let mut bytes = Vec::new();
+    bytes.extend(marshall_vec(Element::Value(Value::String("foo".to_string()))).unwrap());
+    bytes.extend(marshall_vec(Element::List(vec![Element::Unit, Element::Unit])).unwrap());
+    bytes.extend(marshall_vec(Element::Value(Value::Boolean(true))).unwrap());
+    let length = bytes.len() as u64;
+
+    let mut reader = PartialReader::from_vec(bytes);
+    let mut pointers = Vec::new();
+    while reader.position() < length {
+        let start = reader.position();
+        reader.element().unwrap();
+        pointers.push(PartialPointer::new(start, reader.position() - start));
+    }
+    (reader, pointers)
+}
+
+#[test]
+fn partial_element_at() {
+    let (mut reader, pointers) = record_reader();
+    assert_eq!(pointers.len(), 3);
+
+    let list = reader.element_at(&pointers[1]).unwrap();
+    assert_eq!(reader.position(), pointers[2].end());
+    assert_eq!(list.read_full(&mut reader).unwrap(), Element::List(vec![Element::Unit, Element::Unit]));
+
+    let value = reader.element_at(&pointers[2]).unwrap();
+    assert!(matches!(value, PartialElement::Value(Value::Boolean(true))));
+    let value = reader.element_at(&pointers[0]).unwrap();
+    assert!(matches!(value, PartialElement::Value(Value::String(x)) if x == "foo"));
+}
+
+#[test]
+fn partial_element_at_mismatch() {
+    let (mut reader, pointers) = record_reader();
+    let pointer = PartialPointer::new(pointers[1].position(), pointers[1].size() + 1);
+
+    let error = reader.element_at(&pointer).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::PointerNotFound));
+    assert_eq!(error.offset(), Some(pointers[1].position()));
+    assert_eq!(reader.position(), pointers[2].end());
+}
+
+#[cfg(feature="serde")]
+#[test]
+fn partial_pointer_serde() {
+    use crate::{from_element, to_element};
+
+    let (mut reader, pointers) = record_reader();
+    let stored = marshall_vec(to_element(&pointers).unwrap()).unwrap();
+    let restored: Vec<PartialPointer> = from_element(crate::unmarshall_vec(stored).unwrap()).unwrap();
+
+    assert_eq!(restored.len(), pointers.len());
+    for (pointer, original) in restored.iter().zip(&pointers) {
+        assert_eq!((pointer.position(), pointer.size()), (original.position(), original.size()));
+    }
+    assert!(matches!(reader.element_at(&restored[2]).unwrap(), PartialElement::Value(Value::Boolean(true))));
+}
+
+#[test]
+fn partial_buffer_large() {
+    // more items than fit within the buffer, so lookups jump outside of it.
+    let items: Vec<Element> = (0..2000).map(|x| Element::Value(Value::String(format!("item {}", x)))).collect();
+    let bytes = marshall_vec(Element::List(items.clone())).unwrap();
+    let mut reader = PartialReader::from(std::io::Cursor::new(bytes));
+
+    let list = match reader.element().unwrap() {
+        PartialElement::List(list) => list,
+        _ => panic!("expected a list")
+    };
+
+    for index in [1500, 3, 1999, 4, 5] {
+        let item = list.nth(&mut reader, index).unwrap().unwrap();
+        assert_eq!(item.read_full(&mut reader).unwrap(), items[index]);
+    }
+    assert_eq!(PartialElement::from(list).read_full(&mut reader).unwrap(), Element::List(items));
+}
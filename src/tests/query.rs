@@ -0,0 +1,113 @@
+use crate::{Element, ElementMap, Number, Value};
+use crate::error::TychoError;
+use crate::ident::{NumberIdent, ValueIdent};
+use crate::query::{Query, Selector};
+use crate::tests::common::{string, structure};
+
+fn user(name: &str, city: &str) -> Element {
+    let address = structure(vec![("city", string(city))]);
+    structure(vec![("name", string(name)), ("address", Element::Option(Some(Box::new(address))))])
+}
+
+fn example() -> Element {
+    let mut meta = ElementMap::new();
+    meta.insert(Value::String("key".to_string()), string("value"));
+
+    let mut root = ElementMap::new();
+    root.insert("users".to_string(), Element::List(vec![user("Alice", "Paris"), user("Bob", "Rome")]));
+    root.insert("meta".to_string(), Element::Map(ValueIdent::String, meta));
+    root.insert("items".to_string(), Element::Array(
+        ValueIdent::Number(NumberIdent::Unsigned16),
+        (0..20).map(|x| Value::Number(Number::Unsigned16(x))).collect()
+    ));
+    Element::Struct(root)
+}
+
+fn numbers(range: std::ops::Range<u16>) -> Vec<Element> {
+    range.map(|x| Element::Value(Value::Number(Number::Unsigned16(x)))).collect()
+}
+
+#[test]
+fn query_parse() {
+    assert_eq!(Query::parse("users[*].address.city").unwrap().selectors(), &[
+        Selector::Field("users".to_string()),
+        Selector::All,
+        Selector::Field("address".to_string()),
+        Selector::Field("city".to_string()),
+    ]);
+    assert_eq!(Query::parse("meta[\"k\\\"ey\"]").unwrap().selectors(), &[
+        Selector::Field("meta".to_string()),
+        Selector::Field("k\"ey".to_string()),
+    ]);
+    assert_eq!(Query::parse("[3..10][..2][4..][7].*").unwrap().selectors(), &[
+        Selector::Range(Some(3), Some(10)),
+        Selector::Range(None, Some(2)),
+        Selector::Range(Some(4), None),
+        Selector::Index(7),
+        Selector::All,
+    ]);
+    assert!(Query::parse("").unwrap().selectors().is_empty());
+}
+
+#[test]
+fn query_parse_errors() {
+    for (query, position) in [("users.", 6), ("users[", 6), ("[\"foo", 5), ("[1.2]", 3), ("a b", 1), ("[x]", 1)] {
+        match Query::parse(query) {
+            Err(TychoError::InvalidQuery { position: found, .. }) => assert_eq!(found, position, "{}", query),
+            x => panic!("expected an invalid query for {}, found {:?}", query, x)
+        }
+    }
+}
+
+#[test]
+fn query_select() {
+    let root = example();
+    let select = |query: &str| Query::parse(query).unwrap().select(&root);
+
+    assert_eq!(select("users[*].address.city"), vec![string("Paris"), string("Rome")]);
+    assert_eq!(select("users[1].name"), vec![string("Bob")]);
+    assert_eq!(select("meta[\"key\"]"), vec![string("value")]);
+    assert_eq!(select("items[3..6]"), numbers(3..6));
+    assert_eq!(select("items[18..]"), numbers(18..20));
+    assert_eq!(select("items[..2]"), numbers(0..2));
+    assert_eq!(select(""), vec![root.clone()]);
+    assert!(select("users[5].name").is_empty());
+    assert!(select("missing.field").is_empty());
+}
+
+#[cfg(feature="partial")]
+#[test]
+fn query_select_partial() {
+    use crate::marshall_vec;
+    use crate::partial::{PartialElement, PartialReader};
+
+    let root = example();
+    let mut reader = PartialReader::from_vec(marshall_vec(root.clone()).unwrap());
+    let partial = reader.element().unwrap();
+
+    // the same queries match the same elements as in memory.
+    for query in ["users[*].address.city", "users[1]", "meta[\"key\"]", "items[3..6]", "items[18..]", "items[7]", "*", "", "users[5]"] {
+        let query = Query::parse(query).unwrap();
+        assert_eq!(query.select_partial_elements(&mut reader, &partial).unwrap(), query.select(&root));
+    }
+
+    let cities = Query::parse("users[*].address.city").unwrap().select_partial(&mut reader, &partial).unwrap();
+    assert!(matches!(&cities[..], [PartialElement::Value(Value::String(a)), PartialElement::Value(Value::String(b))] if a == "Paris" && b == "Rome"));
+}
+
+#[cfg(all(feature="partial", feature="compression"))]
+#[test]
+fn query_select_compressed() {
+    use crate::marshall_vec;
+    use crate::compression::CompressElement;
+    use crate::partial::PartialReader;
+
+    let root = Element::List(vec![example().compress()]);
+    let query = Query::parse("[0].users[0].name").unwrap();
+    assert_eq!(query.select(&root), vec![string("Alice")]);
+
+    let mut reader = PartialReader::from_vec(marshall_vec(root).unwrap());
+    let partial = reader.element().unwrap();
+    assert!(query.select_partial(&mut reader, &partial).unwrap().is_empty());
+    assert_eq!(query.select_partial_elements(&mut reader, &partial).unwrap(), vec![string("Alice")]);
+}
@@ -0,0 +1,140 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use serde::{Deserialize, Serialize};
+use serde::de::IgnoredAny;
+
+use crate::{from_bytes, from_element, from_reader, from_slice, to_bytes, to_element};
+use crate::tests::common::within;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Single {
+    foo: String
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Nested {
+    name: String,
+    tags: Vec<String>,
+    scores: Vec<u16>,
+    mixed: (u8, bool, char),
+    child: Option<Single>,
+    none: Option<Single>,
+    kinds: Vec<Kind>,
+    flags: HashMap<u32, bool>,
+    empty: Vec<u8>,
+    unit: ()
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Kind {
+    Unit,
+    New(i64),
+    Tuple(u8, String),
+    Struct { bar: f32 }
+}
+
+fn nested() -> Nested {
+    let mut flags = HashMap::new();
+    flags.insert(10, true);
+    flags.insert(20, false);
+
+    Nested {
+        name: "example".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        scores: vec![1, 2, 3],
+        mixed: (1, true, 'x'),
+        child: Some(Single { foo: "Hi".to_string() }),
+        none: None,
+        kinds: vec![Kind::Unit, Kind::New(-5), Kind::Tuple(1, "a".to_string()), Kind::Struct { bar: 1.5 }],
+        flags,
+        empty: vec![],
+        unit: ()
+    }
+}
+
+#[test]
+fn reader_slice() {
+    let bytes = to_bytes(nested()).unwrap();
+    assert_eq!(from_slice::<Nested>(&bytes).unwrap(), nested());
+    assert_eq!(from_bytes::<Nested>(&bytes).unwrap(), nested());
+}
+
+#[test]
+fn reader_reader() {
+    let bytes = to_bytes(nested()).unwrap();
+    assert_eq!(from_reader::<_, Nested>(&mut Cursor::new(bytes)).unwrap(), nested());
+}
+
+#[test]
+fn reader_matches_element() {
+    let bytes = to_bytes(nested()).unwrap();
+    assert_eq!(
+        from_slice::<Nested>(&bytes).unwrap(),
+        from_element::<Nested, _>(to_element(nested()).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn reader_skips_unknown() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Partial {
+        name: String,
+        unit: ()
+    }
+
+    let bytes = to_bytes(nested()).unwrap();
+    assert_eq!(from_slice::<Partial>(&bytes).unwrap(), Partial { name: "example".to_string(), unit: () });
+}
+
+#[test]
+fn reader_truncated() {
+    let bytes = to_bytes(nested()).unwrap();
+    assert!(from_slice::<Nested>(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[serde(with = "serde_bytes")]
+    data: &'a [u8],
+    #[serde(borrow)]
+    tags: Vec<&'a str>,
+    #[serde(borrow)]
+    lookup: HashMap<&'a str, u8>,
+    #[serde(borrow)]
+    cow: Cow<'a, str>
+}
+
+#[test]
+fn reader_borrowed() {
+    let mut lookup = HashMap::new();
+    lookup.insert("key", 1);
+
+    let value = Borrowed {
+        name: "example",
+        data: &[1, 2, 3],
+        tags: vec!["a", "b"],
+        lookup,
+        cow: Cow::Borrowed("cow")
+    };
+
+    let bytes = to_bytes(&value).unwrap();
+    let data: Borrowed = from_slice(&bytes).unwrap();
+
+    assert_eq!(data, value);
+    assert!(within(&bytes, data.name.as_bytes()));
+    assert!(within(&bytes, data.data));
+    assert!(within(&bytes, data.tags[0].as_bytes()));
+    assert!(within(&bytes, data.lookup.keys().next().unwrap().as_bytes()));
+}
+
+#[test]
+fn reader_borrowed_keys() {
+    let bytes = to_bytes(nested()).unwrap();
+    let data: HashMap<&str, IgnoredAny> = from_slice(&bytes).unwrap();
+
+    assert!(data.contains_key("name"));
+    assert!(data.keys().all(|key| within(&bytes, key.as_bytes())));
+}
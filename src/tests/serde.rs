@@ -1,4 +1,7 @@
-use crate::{Uuid, marshall_vec, to_element, from_element, Element};
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use crate::{Uuid, marshall_vec, to_element, from_element, Element, Value, to_bytes, from_bytes, from_reader};
 use serde::{Serialize, Deserialize};
 use crate::collections::Array;
 
@@ -33,3 +36,43 @@ fn test_serde_array() {
 }
 */
 
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Uuids {
+    id: Uuid,
+    maybe: Option<Uuid>,
+    list: Vec<Uuid>,
+    keys: HashMap<Uuid, u8>
+}
+
+fn uuids() -> Uuids {
+    let mut keys = HashMap::new();
+    keys.insert(Uuid::from_numeric(1), 1);
+
+    Uuids {
+        id: Uuid::v4(),
+        maybe: Some(Uuid::v4()),
+        list: vec![Uuid::nil(), Uuid::from_numeric(u128::MAX)],
+        keys
+    }
+}
+
+#[cfg(feature="serde_types")]
+#[test]
+fn test_serde_uuid_value() {
+    let uuid = Uuid::v4();
+    assert_eq!(to_element(&uuid).unwrap(), Element::Value(Value::UUID(uuid.clone())));
+    assert_eq!(to_bytes(&uuid).unwrap(), marshall_vec(Element::Value(Value::UUID(uuid))).unwrap());
+}
+
+#[test]
+fn test_serde_uuid_round_trip() {
+    let value = uuids();
+
+    let e = to_element(&value).unwrap();
+    assert_eq!(from_element::<Uuids, Element>(e).unwrap(), value);
+
+    let bytes = to_bytes(&value).unwrap();
+    assert_eq!(from_bytes::<Uuids>(&bytes).unwrap(), value);
+    assert_eq!(from_reader::<_, Uuids>(&mut Cursor::new(bytes)).unwrap(), value);
+}
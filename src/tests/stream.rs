@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use crate::{Element, ElementStream, marshall_vec, ReadOptions, unmarshall_exact, Value};
+use crate::error::{Limit, TychoError};
+
+fn records() -> Vec<Element> {
+    vec![
+        Element::Unit,
+        Element::Value(Value::String("Hello".to_string())),
+        Element::List(vec![Element::Option(None)]),
+    ]
+}
+
+fn concat(elements: &[Element]) -> Vec<u8> {
+    elements.iter().flat_map(|x| marshall_vec(x.clone()).unwrap()).collect()
+}
+
+#[test]
+fn stream_elements() {
+    let stream = ElementStream::new(Cursor::new(concat(&records())));
+    assert_eq!(stream.collect::<Result<Vec<_>, _>>().unwrap(), records());
+
+    assert!(ElementStream::new(Cursor::new(Vec::new())).next().is_none());
+}
+
+#[test]
+fn stream_truncated() {
+    let mut bytes = concat(&records());
+    bytes.pop();
+    let length = bytes.len() as u64;
+
+    let mut stream = ElementStream::new(Cursor::new(bytes));
+    assert_eq!(stream.next().unwrap().unwrap(), Element::Unit);
+    assert!(stream.next().unwrap().is_ok());
+
+    // the last element is cut short, rather than ending cleanly.
+    let error = stream.next().unwrap().unwrap_err();
+    assert!(matches!(error.inner(), TychoError::UnexpectedEof));
+    assert_eq!(stream.position(), length);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn stream_options() {
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+    let mut stream = ElementStream::new(Cursor::new(concat(&records()))).with_options(options);
+
+    assert!(stream.next().unwrap().is_ok());
+    match stream.next().unwrap().unwrap_err().inner() {
+        TychoError::LimitExceeded { limit, .. } => assert_eq!(limit, &Limit::StringLength),
+        x => panic!("expected limit, found {:?}", x)
+    }
+}
+
+#[test]
+fn stream_exact() {
+    let bytes = marshall_vec(Element::Unit).unwrap();
+    assert_eq!(unmarshall_exact(&mut Cursor::new(bytes.clone())).unwrap(), Element::Unit);
+
+    let error = unmarshall_exact(&mut Cursor::new(concat(&records()))).unwrap_err();
+    assert!(matches!(error.inner(), TychoError::TrailingData));
+    assert_eq!(error.offset(), Some(1));
+}
+
+#[cfg(feature="async_tokio")]
+#[test]
+fn stream_async() {
+    use futures::StreamExt;
+    use crate::ElementStreamAsync;
+
+    let elements: Vec<_> = tokio_test::block_on(ElementStreamAsync::new(Cursor::new(concat(&records()))).collect());
+    assert_eq!(elements.into_iter().collect::<Result<Vec<_>, _>>().unwrap(), records());
+
+    let mut bytes = concat(&records());
+    bytes.pop();
+    let elements: Vec<_> = tokio_test::block_on(ElementStreamAsync::new(Cursor::new(bytes)).collect());
+    assert_eq!(elements.len(), 3);
+    assert!(matches!(elements[2].as_ref().unwrap_err().inner(), TychoError::UnexpectedEof));
+}
@@ -0,0 +1,196 @@
+use crate::{Element, ElementMap, Number, Value};
+use crate::ident::{NumberIdent, ValueIdent};
+use crate::tests::common::within;
+use crate::view::{ElementRef, ValueRef};
+use crate::write::element::write_element;
+
+fn marshall(element: &Element) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_element(&mut bytes, element).unwrap();
+    bytes
+}
+
+fn example() -> Element {
+    let mut fields = ElementMap::new();
+    fields.insert("name".to_string(), Element::Value(Value::String("example".to_string())));
+    fields.insert("data".to_string(), Element::Value(Value::Bytes(vec![1, 2, 3])));
+    fields.insert("list".to_string(), Element::List(vec![
+        Element::Unit,
+        Element::Option(Some(Box::new(Element::Value(Value::Boolean(true))))),
+        Element::Variant("Foo".to_string(), Box::new(Element::Option(None)))
+    ]));
+    fields.insert("array".to_string(), Element::Array(
+        ValueIdent::Number(NumberIdent::Unsigned16),
+        vec![Value::Number(Number::Unsigned16(10)), Value::Number(Number::Unsigned16(20))]
+    ));
+
+    let mut map = ElementMap::new();
+    map.insert(Value::String("key".to_string()), Element::Value(Value::Char('x')));
+    fields.insert("map".to_string(), Element::Map(ValueIdent::String, map));
+    fields.insert("empty".to_string(), Element::Array(ValueIdent::Null, vec![]));
+
+    Element::Struct(fields)
+}
+
+#[test]
+fn view_to_element() {
+    let bytes = marshall(&example());
+    assert_eq!(ElementRef::new(&bytes).unwrap().to_element().unwrap(), example());
+}
+
+#[test]
+fn view_struct_get() {
+    let bytes = marshall(&example());
+
+    let root = match ElementRef::new(&bytes).unwrap() {
+        ElementRef::Struct(s) => s,
+        _ => panic!("expected struct")
+    };
+
+    match root.get("name").unwrap() {
+        Some(ElementRef::Value(ValueRef::String(s))) => {
+            assert_eq!(s, "example");
+            assert!(within(&bytes, s.as_bytes()));
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+
+    match root.get("data").unwrap() {
+        Some(ElementRef::Value(ValueRef::Bytes(b))) => {
+            assert_eq!(b, &[1, 2, 3]);
+            assert!(within(&bytes, b));
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+
+    assert_eq!(root.get("missing").unwrap(), None);
+    assert_eq!(root.iter().count(), 6);
+}
+
+#[test]
+fn view_containers() {
+    let bytes = marshall(&example());
+
+    let root = match ElementRef::new(&bytes).unwrap() {
+        ElementRef::Struct(s) => s,
+        _ => panic!("expected struct")
+    };
+
+    match root.get("list").unwrap() {
+        Some(ElementRef::List(l)) => {
+            assert_eq!(l.get(0).unwrap(), Some(ElementRef::Unit));
+            assert_eq!(
+                l.get(1).unwrap(),
+                Some(ElementRef::Option(Some(Box::new(ElementRef::Value(ValueRef::Boolean(true))))))
+            );
+            assert_eq!(
+                l.get(2).unwrap(),
+                Some(ElementRef::Variant("Foo", Box::new(ElementRef::Option(None))))
+            );
+            assert_eq!(l.get(3).unwrap(), None);
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+
+    match root.get("array").unwrap() {
+        Some(ElementRef::Array(a)) => {
+            assert_eq!(a.collect().unwrap(), vec![
+                ValueRef::Number(Number::Unsigned16(10)),
+                ValueRef::Number(Number::Unsigned16(20))
+            ]);
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+
+    match root.get("map").unwrap() {
+        Some(ElementRef::Map(m)) => {
+            assert_eq!(
+                m.get(&ValueRef::String("key")).unwrap(),
+                Some(ElementRef::Value(ValueRef::Char('x')))
+            );
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+
+    match root.get("empty").unwrap() {
+        Some(ElementRef::Array(a)) => assert!(a.is_empty()),
+        x => panic!("unexpected {:?}", x)
+    }
+}
+
+#[test]
+fn view_truncated() {
+    let bytes = marshall(&example());
+    assert!(ElementRef::new(&bytes[..bytes.len() - 1]).is_err());
+
+    // the outer struct is intact, but its last item is not.
+    let mut inner = marshall(&Element::List(vec![Element::Value(Value::String("hi".to_string()))]));
+    inner[1] -= 1;
+    inner.pop();
+    match ElementRef::new(&inner).unwrap() {
+        ElementRef::List(l) => {
+            let items: Vec<_> = l.iter().collect();
+            assert_eq!(items.len(), 1);
+            assert!(items[0].is_err());
+        }
+        x => panic!("unexpected {:?}", x)
+    }
+}
+
+#[test]
+fn view_limits() {
+    use crate::ReadOptions;
+    use crate::error::{Limit, TychoError};
+
+    fn limit<T: std::fmt::Debug>(result: Result<T, TychoError>) -> Limit {
+        match result.unwrap_err().inner() {
+            TychoError::LimitExceeded { limit, .. } => *limit,
+            e => panic!("expected a limit to be exceeded, found {:?}", e)
+        }
+    }
+
+    // a long chain of options is stopped at the limit, rather than overflowing the stack.
+    let mut bytes = vec![0x03; 1_000_000];
+    bytes.push(0x00);
+    let options = ReadOptions { max_depth: Some(64), ..ReadOptions::default() };
+    assert_eq!(limit(ElementRef::with_options(&bytes, options)), Limit::Depth);
+
+    // the depth of containers is carried to the items read from them.
+    let nested = marshall(&Element::List(vec![Element::List(vec![Element::Unit])]));
+    let options = ReadOptions { max_depth: Some(2), ..ReadOptions::default() };
+    let inner = match ElementRef::with_options(&nested, options).unwrap() {
+        ElementRef::List(list) => list.get(0).unwrap().unwrap(),
+        _ => panic!("expected a list")
+    };
+    match inner {
+        ElementRef::List(list) => assert_eq!(limit(list.get(0)), Limit::Depth),
+        _ => panic!("expected a list")
+    }
+    assert!(ElementRef::new(&nested).unwrap().to_element().is_ok());
+
+    let string = marshall(&Element::Value(Value::String("Hello".to_string())));
+    let options = ReadOptions { max_string_len: Some(4), ..ReadOptions::default() };
+    assert_eq!(limit(ElementRef::with_options(&string, options)), Limit::StringLength);
+}
+
+#[cfg(feature="compression")]
+#[test]
+fn view_limits_decompression() {
+    use crate::ReadOptions;
+    use crate::compression::CompressElement;
+    use crate::error::{Limit, TychoError};
+
+    // a small compressed element, which decompresses to a large one.
+    let element = Element::Value(Value::Bytes(vec![0; 100_000])).compress();
+    let bytes = marshall(&element);
+    assert!(bytes.len() < 1000);
+
+    let options = ReadOptions { max_container_bytes: Some(1000), ..ReadOptions::default() };
+    match ElementRef::with_options(&bytes, options).unwrap() {
+        ElementRef::Compression(c) => match c.element().unwrap_err().inner() {
+            TychoError::LimitExceeded { limit, .. } => assert_eq!(*limit, Limit::ContainerBytes),
+            e => panic!("expected a limit to be exceeded, found {:?}", e)
+        },
+        _ => panic!("expected a compression element")
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Element, from_bytes, from_element, Number, to_bytes, to_element, Value};
+use crate::ident::{NumberIdent, ValueIdent};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Wide {
+    unsigned: u128,
+    signed: i128,
+    unsigned_list: Vec<u128>,
+    signed_list: Vec<i128>,
+    unsigned_keys: HashMap<u128, bool>,
+    signed_keys: HashMap<i128, i128>
+}
+
+fn wide() -> Wide {
+    let mut unsigned_keys = HashMap::new();
+    unsigned_keys.insert(u128::MAX, true);
+
+    let mut signed_keys = HashMap::new();
+    signed_keys.insert(i128::MIN, i128::MAX);
+
+    Wide {
+        unsigned: u128::MAX,
+        signed: i128::MIN,
+        unsigned_list: vec![0, 1 << 100, u128::MAX],
+        signed_list: vec![-1, i128::MAX],
+        unsigned_keys,
+        signed_keys
+    }
+}
+
+#[test]
+fn wide_element() {
+    let element = to_element(wide()).unwrap();
+    assert_eq!(from_element::<Wide, Element>(element).unwrap(), wide());
+}
+
+#[test]
+fn wide_bytes() {
+    let bytes = to_bytes(wide()).unwrap();
+    assert_eq!(from_bytes::<Wide>(&bytes).unwrap(), wide());
+}
+
+#[test]
+fn wide_value() {
+    assert_eq!(
+        to_element(u128::MAX).unwrap(),
+        Element::Value(Value::Number(Number::Unsigned128(u128::MAX)))
+    );
+    assert_eq!(from_bytes::<i128>(&to_bytes(-5i128).unwrap()).unwrap(), -5);
+}
+
+#[cfg(feature="serde_optimise")]
+#[test]
+fn wide_array() {
+    let element = to_element(vec![1u128, 2]).unwrap();
+    assert_eq!(element, Element::Array(
+        ValueIdent::Number(NumberIdent::Unsigned128),
+        vec![Value::Number(Number::Unsigned128(1)), Value::Number(Number::Unsigned128(2))]
+    ));
+    assert_eq!(from_element::<Vec<u128>, Element>(element).unwrap(), vec![1, 2]);
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{marshall_vec, to_bytes, to_element, unmarshall_vec};
+
+fn element_bytes<S: Serialize>(o: &S) -> Vec<u8> {
+    marshall_vec(to_element(o).unwrap()).unwrap()
+}
+
+#[derive(Serialize)]
+struct Single {
+    foo: String
+}
+
+#[derive(Serialize)]
+struct Nested {
+    name: String,
+    tags: Vec<String>,
+    scores: Vec<u16>,
+    mixed: (u8, bool, char),
+    child: Option<Single>,
+    kind: Kind,
+    flags: HashMap<u32, bool>
+}
+
+#[derive(Serialize)]
+enum Kind {
+    Unit,
+    New(i64),
+    Tuple(u8, String),
+    Struct { bar: f32 }
+}
+
+#[test]
+fn writer_values() {
+    assert_eq!(to_bytes(true).unwrap(), element_bytes(&true));
+    assert_eq!(to_bytes(420u16).unwrap(), element_bytes(&420u16));
+    assert_eq!(to_bytes(-10i128).unwrap(), element_bytes(&-10i128));
+    assert_eq!(to_bytes('🚀').unwrap(), element_bytes(&'🚀'));
+    assert_eq!(to_bytes("Hello World").unwrap(), element_bytes(&"Hello World"));
+    assert_eq!(to_bytes(()).unwrap(), element_bytes(&()));
+    assert_eq!(to_bytes(Some(1u8)).unwrap(), element_bytes(&Some(1u8)));
+    assert_eq!(to_bytes(Option::<u8>::None).unwrap(), element_bytes(&Option::<u8>::None));
+}
+
+#[test]
+fn writer_sequences() {
+    assert_eq!(to_bytes(vec![1u8, 2, 3]).unwrap(), element_bytes(&vec![1u8, 2, 3]));
+    assert_eq!(to_bytes(Vec::<u8>::new()).unwrap(), element_bytes(&Vec::<u8>::new()));
+    assert_eq!(to_bytes((1u8, "a", 2u32)).unwrap(), element_bytes(&(1u8, "a", 2u32)));
+    assert_eq!(to_bytes(vec![vec![1u8], vec![]]).unwrap(), element_bytes(&vec![vec![1u8], vec![]]));
+}
+
+#[test]
+fn writer_variants() {
+    for kind in [Kind::Unit, Kind::New(-5), Kind::Tuple(1, "a".to_string()), Kind::Struct { bar: 1.5 }] {
+        assert_eq!(to_bytes(&kind).unwrap(), element_bytes(&kind));
+    }
+}
+
+#[test]
+fn writer_struct() {
+    let single = Single { foo: "Hi".to_string() };
+    assert_eq!(to_bytes(&single).unwrap(), element_bytes(&single));
+
+    let mut flags = HashMap::new();
+    flags.insert(10, true);
+    flags.insert(20, false);
+
+    let nested = Nested {
+        name: "example".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        scores: vec![1, 2, 3],
+        mixed: (1, true, 'x'),
+        child: Some(single),
+        kind: Kind::Tuple(2, "b".to_string()),
+        flags
+    };
+
+    // field order is not stable for unordered elements, compare the decoded elements.
+    assert_eq!(
+        unmarshall_vec(to_bytes(&nested).unwrap()).unwrap(),
+        to_element(&nested).unwrap()
+    );
+}
+
+#[test]
+fn writer_map_key_error() {
+    let mut map = HashMap::new();
+    map.insert(vec![1u8], 1u8);
+    assert!(to_bytes(map).is_err());
+}
+
+/// A map which emits the same key twice.
+struct Repeated;
+
+impl Serialize for Repeated {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("a", &1u8)?;
+        map.serialize_entry("a", &2u8)?;
+        map.end()
+    }
+}
+
+#[test]
+fn writer_duplicate_keys() {
+    // the element keeps the last value of a repeated key.
+    assert_eq!(to_bytes(Repeated).unwrap(), vec![8, 2, 6, 1, 97, 1, 4, 1, 2]);
+    assert_eq!(to_bytes(Repeated).unwrap(), element_bytes(&Repeated));
+    assert_eq!(to_bytes(vec![Repeated]).unwrap(), element_bytes(&vec![Repeated]));
+}
+
+#[test]
+fn writer_nested_sizes() {
+    // containers within containers, with contents large enough to widen their size prefixes.
+    let long = vec!["x".repeat(200); 3];
+    let value = vec![(long.clone(), vec![Single { foo: "y".repeat(300) }]), (Vec::new(), Vec::new())];
+
+    assert_eq!(to_bytes(&value).unwrap(), element_bytes(&value));
+    assert_eq!(to_bytes(vec![vec![1u16; 100]; 2]).unwrap(), element_bytes(&vec![vec![1u16; 100]; 2]));
+}
+
+/// A list which grows each time it is serialized.
+struct Growing(std::cell::Cell<usize>);
+
+impl Serialize for Growing {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.set(self.0.get() + 1);
+        vec![(); self.0.get()].serialize(serializer)
+    }
+}
+
+#[test]
+fn writer_changed_between_passes() {
+    assert!(to_bytes(Growing(std::cell::Cell::new(0))).is_err());
+}
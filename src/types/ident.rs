@@ -46,4 +46,24 @@ pub enum ElementIdent {
     Array,
     Map,
     Compression
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// Identities for the codecs of compression elements.
+///
+/// Each codec has its own element prefix, gzip keeping `0xF0`, followed by `0xF1` to `0xF3`.
+///
+/// Gzip is always available within the `compression` feature,
+/// other codecs require their own feature to be compressed or decompressed.
+pub enum Codec {
+    /// Gzip, (`compression` feature)
+    #[default]
+    Gzip,
+    /// Zstandard, (`compression_zstd` feature)
+    Zstd,
+    /// LZ4 frames, (`compression_lz4` feature)
+    Lz4,
+    /// Brotli, (`compression_brotli` feature)
+    Brotli
+}
+
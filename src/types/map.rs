@@ -0,0 +1,226 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::Index;
+
+#[cfg(feature="ordered")]
+type Inner<K, V> = indexmap::IndexMap<K, V>;
+#[cfg(not(feature="ordered"))]
+type Inner<K, V> = HashMap<K, V>;
+
+#[cfg(feature="ordered")]
+type InnerIter<'a, K, V> = indexmap::map::Iter<'a, K, V>;
+#[cfg(not(feature="ordered"))]
+type InnerIter<'a, K, V> = std::collections::hash_map::Iter<'a, K, V>;
+
+#[cfg(feature="ordered")]
+type InnerIterMut<'a, K, V> = indexmap::map::IterMut<'a, K, V>;
+#[cfg(not(feature="ordered"))]
+type InnerIterMut<'a, K, V> = std::collections::hash_map::IterMut<'a, K, V>;
+
+#[cfg(feature="ordered")]
+type InnerIntoIter<K, V> = indexmap::map::IntoIter<K, V>;
+#[cfg(not(feature="ordered"))]
+type InnerIntoIter<K, V> = std::collections::hash_map::IntoIter<K, V>;
+
+/// The map used within struct and map elements.
+///
+/// With the `ordered` feature, items are kept in insertion order,
+/// so elements are written in the same order they were built or read in.
+/// Otherwise, items are kept in an unspecified order.
+///
+/// The api of this map is the same with or without the `ordered` feature,
+/// so enabling the feature does not break other crates using tycho.
+///
+/// ```
+/// use tycho::{Element, ElementMap};
+///
+/// let mut fields = ElementMap::new();
+/// fields.insert("foo".to_string(), Element::Unit);
+///
+/// assert_eq!(fields.get("foo"), Some(&Element::Unit));
+/// assert_eq!(fields.len(), 1);
+/// ```
+pub struct ElementMap<K, V>(Inner<K, V>);
+
+impl<K, V> ElementMap<K, V> {
+    /// Create a new empty map.
+    pub fn new() -> Self { Self(Inner::default()) }
+
+    /// Create a new empty map, with space for at least `capacity` items.
+    pub fn with_capacity(capacity: usize) -> Self { Self(Inner::with_capacity(capacity)) }
+
+    /// The number of items in the map.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Returns true if the map has no items.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Remove all items from the map.
+    pub fn clear(&mut self) { self.0.clear() }
+
+    /// Iterate over the items of the map.
+    pub fn iter(&self) -> Iter<'_, K, V> { Iter(self.0.iter()) }
+
+    /// Iterate over the items of the map, with mutable values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> { IterMut(self.0.iter_mut()) }
+
+    /// Iterate over the keys of the map.
+    pub fn keys(&self) -> impl ExactSizeIterator<Item=&K> { self.0.keys() }
+
+    /// Iterate over the values of the map.
+    pub fn values(&self) -> impl ExactSizeIterator<Item=&V> { self.0.values() }
+
+    /// Iterate over the values of the map mutably.
+    pub fn values_mut(&mut self) -> impl ExactSizeIterator<Item=&mut V> { self.0.values_mut() }
+}
+
+impl<K: Hash + Eq, V> ElementMap<K, V> {
+    /// Insert an item into the map, returning the previous value of the key.
+    ///
+    /// With the `ordered` feature, a replaced item keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> { self.0.insert(key, value) }
+
+    /// Get the value of a key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        self.0.get(key)
+    }
+
+    /// Get the value of a key mutably.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        self.0.get_mut(key)
+    }
+
+    /// Returns true if the map contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        self.0.contains_key(key)
+    }
+
+    /// Remove an item from the map, returning its value.
+    ///
+    /// With the `ordered` feature, the remaining items keep their order.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        #[cfg(feature="ordered")]
+        return self.0.shift_remove(key);
+
+        #[cfg(not(feature="ordered"))]
+        return self.0.remove(key);
+    }
+}
+
+impl<K, V> Default for ElementMap<K, V> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K: Clone, V: Clone> Clone for ElementMap<K, V> {
+    fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ElementMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter()).finish()
+    }
+}
+
+/// Maps are equal if they have the same items, regardless of order.
+impl<K: Hash + Eq, V: PartialEq> PartialEq for ElementMap<K, V> {
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+
+impl<K: Hash + Eq, V: Eq> Eq for ElementMap<K, V> {}
+
+impl<K, Q, V> Index<&Q> for ElementMap<K, V>
+    where K: Hash + Eq + Borrow<Q>, Q: ?Sized + Hash + Eq {
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in element map")
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for ElementMap<K, V> {
+    fn from_iter<T: IntoIterator<Item=(K, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for ElementMap<K, V> {
+    fn extend<T: IntoIterator<Item=(K, V)>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+impl<K: Hash + Eq, V> From<HashMap<K, V>> for ElementMap<K, V> {
+    fn from(map: HashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Hash + Eq, V> From<ElementMap<K, V>> for HashMap<K, V> {
+    fn from(map: ElementMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoIterator for ElementMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { IntoIter(self.0.into_iter()) }
+}
+
+impl<'a, K, V> IntoIterator for &'a ElementMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut ElementMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// An iterator over the items of an element map.
+pub struct Iter<'a, K, V>(InnerIter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> { self.0.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// An iterator over the items of an element map, with mutable values.
+pub struct IterMut<'a, K, V>(InnerIterMut<'a, K, V>);
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> { self.0.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+/// An owning iterator over the items of an element map.
+pub struct IntoIter<K, V>(InnerIntoIter<K, V>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> { self.0.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
@@ -1,3 +1,4 @@
 pub mod ident;
 pub mod types;
+pub mod map;
 pub mod hash;
\ No newline at end of file
@@ -1,7 +1,11 @@
-use std::collections::HashMap;
-
 use crate::types::ident::ValueIdent;
 use crate::Uuid;
+pub use crate::types::map::ElementMap;
+
+#[cfg(feature="compression")]
+use crate::into::compression::Compression;
+#[cfg(not(feature="compression"))]
+use crate::types::ident::Codec;
 
 #[derive(Debug, Clone, PartialOrd)]
 /// A numerical value tag, used when creating tycho data.
@@ -130,7 +134,7 @@ pub enum Element {
     /// An element representing an untyped string key - element paring.
     ///
     /// Directly maps to rusts `struct` object within the serialisation process.
-    Struct(HashMap<String, Element>),
+    Struct(ElementMap<String, Element>),
 
     /// ### List
     /// An element representing an untyped collection of elements.
@@ -149,12 +153,14 @@ pub enum Element {
     ///
     /// They key of a map can be any terminating value, and is strictly typed.
     /// The value of a map can be any element and is homogeneous.
-    Map(ValueIdent, HashMap<Value, Element>),
+    Map(ValueIdent, ElementMap<Value, Element>),
 
     /// ### Compression Marker
-    /// Contains an element that will be g-zip compressed.
+    /// Contains an element that will be compressed, with the codec and level to compress it with.
+    ///
+    /// Without the `compression` feature, the compressed bytes are kept with the codec that compressed them.
     #[cfg(feature="compression")]
-    Compression(Box<Element>),
+    Compression(Compression, Box<Element>),
     #[cfg(not(feature="compression"))]
-    Compression(Vec<u8>),
+    Compression(Codec, Vec<u8>),
 }
@@ -216,7 +216,14 @@ impl fmt::Display for Uuid {
 }
 
 #[cfg(feature="serde")]
-use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Visitor, de::Error as DeError, ser::SerializeStruct};
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Visitor, de::Error as DeError};
+
+/// The newtype struct name used to pass uuids through serde.
+///
+/// Tycho serializers and deserializers recognise this name,
+/// so uuids are always encoded and decoded as a uuid value.
+#[cfg(feature="serde")]
+pub(crate) const UUID_NAME: &str = "___tycho___/uuid";
 
 #[cfg(feature="serde")]
 impl Serialize for Uuid {
@@ -226,9 +233,7 @@ impl Serialize for Uuid {
         return if serializer.is_human_readable() {
             serializer.serialize_str(&self.string())
         } else {
-            let mut stu = serializer.serialize_struct("___tycho___/uuid", 1)?;
-            stu.serialize_field("inner", &UuidBytes(self.slice()))?;
-            stu.end()
+            serializer.serialize_newtype_struct(UUID_NAME, &UuidBytes(self.slice()))
         };
 
         #[cfg(not(feature="serde_types"))]
@@ -251,7 +256,7 @@ impl Serialize for UuidBytes {
 impl<'de> Deserialize<'de> for Uuid {
     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error> where
         D: Deserializer<'de> {
-        deserializer.deserialize_any(UuidVisitor)
+        deserializer.deserialize_newtype_struct(UUID_NAME, UuidVisitor)
     }
 }
 
@@ -277,22 +282,69 @@ impl<'de> Visitor<'de> for UuidVisitor {
 
     fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> where
         E: DeError, {
-        Ok(Uuid::from_bytes(&v))
+        if v.len() == 16 {
+            Ok(Uuid::from_bytes(&v))
+        } else {
+            Err(E::invalid_length(v.len(), &self))
+        }
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where
+        D: Deserializer<'de>, {
+        // formats without the uuid newtype pass the inner string or bytes through.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(self)
+        } else {
+            deserializer.deserialize_bytes(self)
+        }
     }
 }
 
+#[cfg(feature="uuid")]
 impl From<uuid::Uuid> for Uuid {
     fn from(x: uuid::Uuid) -> Self {
         Self(x.as_u128())
     }
 }
 
+#[cfg(feature="uuid")]
 impl Into<uuid::Uuid> for Uuid {
     fn into(self) -> uuid::Uuid {
         uuid::Uuid::from_u128(self.0)
     }
 }
 
+#[cfg(all(feature="uuid", feature="serde"))]
+/// Serde functions for encoding a `uuid::Uuid` as a tycho uuid. (requires `uuid` and `serde`)
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Example {
+///     #[serde(with = "tycho::uuid_serde")]
+///     id: uuid::Uuid
+/// }
+///
+/// let example = Example { id: uuid::Uuid::from_u128(10) };
+/// let bytes = tycho::to_bytes(&example).unwrap();
+///
+/// assert_eq!(tycho::from_bytes::<Example>(&bytes).unwrap(), example);
+/// ```
+pub mod uuid_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Uuid;
+
+    pub fn serialize<S: Serializer>(uuid: &uuid::Uuid, serializer: S) -> Result<S::Ok, S::Error> {
+        Uuid::from(*uuid).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<uuid::Uuid, D::Error> {
+        Ok(Uuid::deserialize(deserializer)?.into())
+    }
+}
+
 use std::hash::{Hash, Hasher};
 
 impl Hash for Uuid {
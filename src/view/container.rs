@@ -0,0 +1,79 @@
+//! The common container structure used within borrowed container types.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::error::TychoResult;
+use crate::read::options::Limiter;
+
+pub trait ContainerRefType<'a> {
+    type ItemType;
+    type ItemParam: Debug + Clone + PartialEq;
+
+    fn read_item(bytes: &mut &'a [u8], param: &Self::ItemParam, limiter: &mut Limiter) -> TychoResult<Self::ItemType>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerRef<'a, T: ContainerRefType<'a>> {
+    /// The bytes within the container.
+    pub bytes: &'a [u8],
+    /// Parameters for the container.
+    pub param: T::ItemParam,
+    /// The limits items are read within, from the depth of the container.
+    limiter: Limiter,
+    _phantom: PhantomData<T>
+}
+
+impl<'a, T: ContainerRefType<'a>> PartialEq for ContainerRef<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.param == other.param
+    }
+}
+
+impl<'a, T: ContainerRefType<'a>> ContainerRef<'a, T> {
+    /// Create a new borrowed container
+    pub(crate) fn new(bytes: &'a [u8], param: T::ItemParam, limiter: Limiter) -> Self {
+        ContainerRef { bytes, param, limiter, _phantom: Default::default() }
+    }
+
+    /// Returns if the container has no items.
+    pub fn is_empty(&self) -> bool { self.bytes.is_empty() }
+
+    /// Get an iterator of the container.
+    ///
+    /// Items are decoded as the iterator is advanced.
+    pub fn iter(&self) -> ContainerRefIterator<'a, T> {
+        ContainerRefIterator { bytes: self.bytes, param: self.param.clone(), limiter: self.limiter, _phantom: Default::default() }
+    }
+
+    /// Collect all items within the container.
+    pub fn collect(&self) -> TychoResult<Vec<T::ItemType>> {
+        self.iter().collect()
+    }
+}
+
+pub struct ContainerRefIterator<'a, T: ContainerRefType<'a>> {
+    bytes: &'a [u8],
+    param: T::ItemParam,
+    limiter: Limiter,
+    _phantom: PhantomData<T>
+}
+
+impl<'a, T: ContainerRefType<'a>> Iterator for ContainerRefIterator<'a, T> {
+    type Item = TychoResult<T::ItemType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let item = T::read_item(&mut self.bytes, &self.param, &mut self.limiter);
+
+        // stop after the first error, as the position of the next item is unknown.
+        if item.is_err() {
+            self.bytes = &[];
+        }
+
+        Some(item)
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Element, ElementMap};
+use crate::error::TychoResult;
+use crate::read::element::{parse_codec, parse_element_ident};
+use crate::read::func::read_byte;
+use crate::read::options::{Limiter, ReadOptions};
+use crate::read::slice::{read_sized_slice, read_tstr};
+use crate::read::value::read_value_ident;
+use crate::types::ident::{ElementIdent, ValueIdent};
+use crate::view::types::{ArrayRef, CompressionRef, ListRef, MapRef, StructRef};
+use crate::view::value::{read_value_ref, ValueRef};
+
+#[cfg(feature="compression")]
+use crate::into::compression::Compression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// An element borrowed from a tycho buffer.
+pub enum ElementRef<'a> {
+    Unit,
+    Value(ValueRef<'a>),
+    Option(Option<Box<ElementRef<'a>>>),
+    Variant(&'a str, Box<ElementRef<'a>>),
+    Struct(StructRef<'a>),
+    List(ListRef<'a>),
+    Map(MapRef<'a>),
+    Array(ArrayRef<'a>),
+    Compression(CompressionRef<'a>),
+}
+
+impl<'a> ElementRef<'a> {
+    /// View the element at the start of a buffer.
+    ///
+    /// Only the outermost prefix is decoded, containers are decoded as they are accessed.
+    pub fn new(mut bytes: &'a [u8]) -> TychoResult<Self> {
+        read_element_ref(&mut bytes, &mut Limiter::default())
+    }
+
+    /// View the element at the start of a buffer, within a set of limits.
+    ///
+    /// The limits apply to the containers within the element as they are accessed,
+    /// and to decompressing any compression elements within it.
+    ///
+    /// ### Example
+    /// ```
+    /// use tycho::ReadOptions;
+    /// use tycho::view::ElementRef;
+    ///
+    /// let options = ReadOptions { max_depth: Some(2), ..ReadOptions::default() };
+    ///
+    /// // `Some(Value(10))` is within the depth limit.
+    /// assert!(ElementRef::with_options(&[3, 1, 4, 1, 10], options).is_ok());
+    ///
+    /// // `Some(Some(Value(10)))` is not.
+    /// assert!(ElementRef::with_options(&[3, 3, 1, 4, 1, 10], options).is_err());
+    /// ```
+    pub fn with_options(mut bytes: &'a [u8], options: ReadOptions) -> TychoResult<Self> {
+        read_element_ref(&mut bytes, &mut Limiter::new(options))
+    }
+
+    /// Decode the element into an owned element.
+    pub fn to_element(&self) -> TychoResult<Element> {
+        match self {
+            ElementRef::Unit => Ok(Element::Unit),
+            ElementRef::Value(v) => Ok(Element::Value(v.to_value())),
+            ElementRef::Option(None) => Ok(Element::Option(None)),
+            ElementRef::Option(Some(e)) => Ok(Element::Option(Some(Box::new(e.to_element()?)))),
+            ElementRef::Variant(name, e) => Ok(Element::Variant(name.to_string(), Box::new(e.to_element()?))),
+            ElementRef::Struct(s) => {
+                let mut items = ElementMap::new();
+                for item in s.iter() {
+                    let (key, value) = item?;
+                    items.insert(key.to_string(), value.to_element()?);
+                }
+                Ok(Element::Struct(items))
+            }
+            ElementRef::List(l) => {
+                let mut items = Vec::new();
+                for item in l.iter() {
+                    items.push(item?.to_element()?);
+                }
+                Ok(Element::List(items))
+            }
+            ElementRef::Map(m) => {
+                let mut items = ElementMap::new();
+                for item in m.iter() {
+                    let (key, value) = item?;
+                    items.insert(key.to_value(), value.to_element()?);
+                }
+                Ok(Element::Map(m.param.clone(), items))
+            }
+            ElementRef::Array(a) => {
+                let mut items = Vec::new();
+                for item in a.iter() {
+                    items.push(item?.to_value());
+                }
+                Ok(Element::Array(a.param.clone(), items))
+            }
+
+            #[cfg(feature="compression")]
+            ElementRef::Compression(c) => Ok(Element::Compression(Compression::new(c.codec), Box::new(c.element()?))),
+
+            #[cfg(not(feature="compression"))]
+            ElementRef::Compression(c) => Ok(Element::Compression(c.codec, c.bytes.to_vec())),
+        }
+    }
+}
+
+pub(crate) fn read_element_ref<'a>(bytes: &mut &'a [u8], limiter: &mut Limiter) -> TychoResult<ElementRef<'a>> {
+    let byte = read_byte(bytes)?;
+    let ident = parse_element_ident(byte)?;
+
+    limiter.enter()?;
+    let element = read_element_ref_body(bytes, ident, byte, limiter);
+    limiter.exit();
+
+    element
+}
+
+/// Read the slice of a container, within the limits.
+fn read_container_slice<'a>(bytes: &mut &'a [u8], limiter: &mut Limiter) -> TychoResult<&'a [u8]> {
+    let slice = read_sized_slice(bytes)?;
+    limiter.container(slice.len())?;
+    Ok(slice)
+}
+
+fn read_element_ref_body<'a>(bytes: &mut &'a [u8], ident: ElementIdent, byte: u8, limiter: &mut Limiter) -> TychoResult<ElementRef<'a>> {
+    match ident {
+        ElementIdent::Unit => Ok(ElementRef::Unit),
+        ElementIdent::Value => {
+            let prefix = read_value_ident(bytes)?;
+            Ok(ElementRef::Value(read_value_ref(bytes, &prefix, limiter)?))
+        },
+
+        ElementIdent::None => Ok(ElementRef::Option(None)),
+        ElementIdent::Some => Ok(ElementRef::Option(Some(Box::new(read_element_ref(bytes, limiter)?)))),
+
+        ElementIdent::Variant => {
+            let name = read_tstr(bytes)?;
+            limiter.string(name.len())?;
+            let value = read_element_ref(bytes, limiter)?;
+            Ok(ElementRef::Variant(name, Box::new(value)))
+        }
+
+        // containers keep the limiter, so their items are read from the depth of the container.
+        ElementIdent::Struct => Ok(ElementRef::Struct(StructRef::new(read_container_slice(bytes, limiter)?, (), *limiter))),
+        ElementIdent::List => Ok(ElementRef::List(ListRef::new(read_container_slice(bytes, limiter)?, (), *limiter))),
+
+        ElementIdent::Array => {
+            let array_type = read_value_ident(bytes)?;
+
+            if array_type == ValueIdent::Null {
+                return Ok(ElementRef::Array(ArrayRef::new(&[], array_type, *limiter)))
+            }
+
+            Ok(ElementRef::Array(ArrayRef::new(read_container_slice(bytes, limiter)?, array_type, *limiter)))
+        },
+
+        ElementIdent::Map => {
+            let key_type = read_value_ident(bytes)?;
+
+            if key_type == ValueIdent::Null {
+                return Ok(ElementRef::Map(MapRef::new(&[], key_type, *limiter)))
+            }
+
+            Ok(ElementRef::Map(MapRef::new(read_container_slice(bytes, limiter)?, key_type, *limiter)))
+        },
+
+        ElementIdent::Compression => {
+            let codec = parse_codec(byte)?;
+            Ok(ElementRef::Compression(CompressionRef::new(read_container_slice(bytes, limiter)?, codec, *limiter)))
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Borrowed, lazily decoded views over in-memory tycho bytes.
+//!
+//! ## Applications
+//! Views are the in-memory counterpart to `partial` reading,
+//! for buffers that are already loaded, such as mapped files or received packets.
+//!
+//! Only the prefix of an element is decoded when it is viewed,
+//! containers are decoded item by item as they are accessed.
+//! Strings and bytes are borrowed from the buffer without copying.
+//!
+//! When viewing untrusted input, set limits with `ElementRef::with_options`.
+//! Containers keep the limits they were viewed within, and apply them to their items as they are accessed.
+//!
+//! ## Quick Example
+//! ```
+//! use tycho::view::{ElementRef, ValueRef};
+//!
+//! // Example bytes of `{"foo": 10, "bar": "Hello World"}`
+//! let bytes = vec![
+//!     /* ... */
+//! # 5, 26, 102, 111, 111, 0, 1, 4, 1, 10, 98, 97, 114, 0, 1, 2, 11, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100
+//! ];
+//!
+//! // View the root element
+//! let root = ElementRef::new(&bytes).unwrap();
+//!
+//! // check if the root element is a structure
+//! if let ElementRef::Struct(s) = root {
+//!
+//!     // look up a field, borrowing its string from the buffer.
+//!     let bar = s.get("bar").unwrap();
+//!     assert_eq!(bar, Some(ElementRef::Value(ValueRef::String("Hello World"))));
+//! }
+//! ```
+//!
+//! ### Containers
+//! All container types (Struct, List, Map, Array) share a `ContainerRef` which takes a generic,
+//! and hold the slice of bytes within the container.
+//!
+//! Containers can be iterated over, yielding a result for each item,
+//! or searched for a key or index with `get`.
+
+pub use element::ElementRef;
+pub use types::{ArrayRef, CompressionRef, ListRef, MapRef, StructRef};
+pub use value::ValueRef;
+
+pub mod container;
+pub(crate) mod element;
+pub mod types;
+pub(crate) mod value;
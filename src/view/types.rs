@@ -0,0 +1,156 @@
+//! Container types returned when viewing a buffer.
+
+use crate::error::TychoResult;
+use crate::ident::{Codec, ValueIdent};
+use crate::read::slice::read_tstr;
+use crate::view::container::{ContainerRef, ContainerRefType};
+use crate::view::element::{ElementRef, read_element_ref};
+use crate::view::value::{read_value_ref, ValueRef};
+
+#[cfg(feature="compression")]
+use crate::Element;
+use crate::read::options::Limiter;
+
+#[cfg(feature="compression")]
+use crate::read::compress::decompress_limited;
+#[cfg(feature="compression")]
+use crate::read::element::read_element;
+#[cfg(feature="compression")]
+use crate::read::position::PositionReader;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The inner implementation structure for a struct.
+pub struct StructRefInner;
+impl<'a> ContainerRefType<'a> for StructRefInner {
+    type ItemType = (&'a str, ElementRef<'a>);
+    type ItemParam = ();
+
+    fn read_item(bytes: &mut &'a [u8], _: &(), limiter: &mut Limiter) -> TychoResult<Self::ItemType> {
+        let key = read_tstr(bytes)?;
+        limiter.string(key.len())?;
+        let value = read_element_ref(bytes, limiter)?;
+        Ok((key, value))
+    }
+}
+/// A borrowed struct object.
+pub type StructRef<'a> = ContainerRef<'a, StructRefInner>;
+
+impl<'a> StructRef<'a> {
+    /// Get the value of a field by its key.
+    pub fn get(&self, key: &str) -> TychoResult<Option<ElementRef<'a>>> {
+        for item in self.iter() {
+            let (k, v) = item?;
+            if k == key {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The inner implementation structure for a list.
+pub struct ListRefInner;
+impl<'a> ContainerRefType<'a> for ListRefInner {
+    type ItemType = ElementRef<'a>;
+    type ItemParam = ();
+
+    fn read_item(bytes: &mut &'a [u8], _: &(), limiter: &mut Limiter) -> TychoResult<Self::ItemType> {
+        read_element_ref(bytes, limiter)
+    }
+}
+/// A borrowed list object.
+pub type ListRef<'a> = ContainerRef<'a, ListRefInner>;
+
+impl<'a> ListRef<'a> {
+    /// Get an item by its index.
+    pub fn get(&self, index: usize) -> TychoResult<Option<ElementRef<'a>>> {
+        self.iter().nth(index).transpose()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The inner implementation structure for a map.
+pub struct MapRefInner;
+impl<'a> ContainerRefType<'a> for MapRefInner {
+    type ItemType = (ValueRef<'a>, ElementRef<'a>);
+    type ItemParam = ValueIdent;
+
+    fn read_item(bytes: &mut &'a [u8], params: &ValueIdent, limiter: &mut Limiter) -> TychoResult<Self::ItemType> {
+        let key = read_value_ref(bytes, params, limiter)?;
+        let value = read_element_ref(bytes, limiter)?;
+        Ok((key, value))
+    }
+}
+/// A borrowed map object.
+pub type MapRef<'a> = ContainerRef<'a, MapRefInner>;
+
+impl<'a> MapRef<'a> {
+    /// Get the value of an entry by its key.
+    pub fn get(&self, key: &ValueRef) -> TychoResult<Option<ElementRef<'a>>> {
+        for item in self.iter() {
+            let (k, v) = item?;
+            if &k == key {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The inner implementation structure for a array.
+pub struct ArrayRefInner;
+impl<'a> ContainerRefType<'a> for ArrayRefInner {
+    type ItemType = ValueRef<'a>;
+    type ItemParam = ValueIdent;
+
+    fn read_item(bytes: &mut &'a [u8], params: &ValueIdent, limiter: &mut Limiter) -> TychoResult<Self::ItemType> {
+        read_value_ref(bytes, params, limiter)
+    }
+}
+/// A borrowed array object.
+pub type ArrayRef<'a> = ContainerRef<'a, ArrayRefInner>;
+
+impl<'a> ArrayRef<'a> {
+    /// Get an item by its index.
+    pub fn get(&self, index: usize) -> TychoResult<Option<ValueRef<'a>>> {
+        self.iter().nth(index).transpose()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A borrowed compression object.
+pub struct CompressionRef<'a> {
+    pub bytes: &'a [u8],
+    /// The codec the bytes were compressed with.
+    pub codec: Codec,
+    #[cfg_attr(not(feature="compression"), allow(dead_code))]
+    /// The limits the decompressed element is read within.
+    limiter: Limiter,
+}
+
+impl<'a> PartialEq for CompressionRef<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.codec == other.codec
+    }
+}
+
+impl<'a> CompressionRef<'a> {
+    pub(crate) fn new(bytes: &'a [u8], codec: Codec, limiter: Limiter) -> Self {
+        CompressionRef { bytes, codec, limiter }
+    }
+
+    #[cfg(feature="compression")]
+    /// Decompress and read the element within the compression object.
+    ///
+    /// The decompressed bytes can not be borrowed, so an owned element is returned.
+    /// Decompression stops once the limits the compression object was viewed within are exceeded.
+    ///
+    /// (requires `compression` feature)
+    pub fn element(&self) -> TychoResult<Element> {
+        let mut limiter = self.limiter;
+        let bytes = decompress_limited(self.bytes, self.codec, &mut limiter)?;
+        read_element(&mut PositionReader::new(bytes.as_slice()), &mut limiter)
+    }
+}
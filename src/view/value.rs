@@ -0,0 +1,71 @@
+use crate::{Number, Uuid, Value};
+use crate::error::TychoResult;
+use crate::ident::ValueIdent;
+use crate::into::ident::Ident;
+use crate::read::func::read_byte;
+use crate::read::number::read_number;
+use crate::read::options::Limiter;
+use crate::read::slice::{read_sized_slice, read_slice, read_str};
+use crate::read::string::read_char;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A value borrowed from a tycho buffer.
+///
+/// Strings and bytes are slices into the original buffer.
+pub enum ValueRef<'a> {
+    Null,
+    Boolean(bool),
+    String(&'a str),
+    Char(char),
+    Number(Number),
+    Bytes(&'a [u8]),
+    UUID(Uuid),
+}
+
+impl<'a> ValueRef<'a> {
+    /// Get the identity of the value.
+    pub fn ident(&self) -> ValueIdent {
+        match self {
+            ValueRef::Null => ValueIdent::Null,
+            ValueRef::Boolean(_) => ValueIdent::Boolean,
+            ValueRef::String(_) => ValueIdent::String,
+            ValueRef::Char(_) => ValueIdent::Char,
+            ValueRef::Number(n) => ValueIdent::Number(n.ident()),
+            ValueRef::Bytes(_) => ValueIdent::Bytes,
+            ValueRef::UUID(_) => ValueIdent::UUID,
+        }
+    }
+
+    /// Copy the value into an owned value.
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Boolean(b) => Value::Boolean(*b),
+            ValueRef::String(s) => Value::String(s.to_string()),
+            ValueRef::Char(c) => Value::Char(*c),
+            ValueRef::Number(n) => Value::Number(n.clone()),
+            ValueRef::Bytes(b) => Value::Bytes(b.to_vec()),
+            ValueRef::UUID(u) => Value::UUID(u.clone()),
+        }
+    }
+}
+
+pub(crate) fn read_value_ref<'a>(bytes: &mut &'a [u8], ident: &ValueIdent, limiter: &mut Limiter) -> TychoResult<ValueRef<'a>> {
+    match ident {
+        ValueIdent::Null => Ok(ValueRef::Null),
+        ValueIdent::Boolean => Ok(ValueRef::Boolean(read_byte(bytes)? == 0x01)),
+        ValueIdent::String => {
+            let string = read_str(bytes)?;
+            limiter.string(string.len())?;
+            Ok(ValueRef::String(string))
+        }
+        ValueIdent::Char => Ok(ValueRef::Char(read_char(bytes)?)),
+        ValueIdent::Number(n) => Ok(ValueRef::Number(read_number(bytes, n)?)),
+        ValueIdent::Bytes => {
+            let slice = read_sized_slice(bytes)?;
+            limiter.string(slice.len())?;
+            Ok(ValueRef::Bytes(slice))
+        }
+        ValueIdent::UUID => Ok(ValueRef::UUID(Uuid::from_bytes(read_slice(bytes, 16)?))),
+    }
+}
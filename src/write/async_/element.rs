@@ -0,0 +1,226 @@
+use futures::future::{BoxFuture, FutureExt};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::Element;
+use crate::error::{parse_io, TychoResult, TychoStatus};
+use crate::into::ident::Ident;
+use crate::types::ident::ValueIdent;
+use crate::write::func::write_byte;
+use crate::write::length::write_length;
+use crate::write::size::{length_size, tstring_size, value_ident_size, value_size};
+use crate::write::element::codec_prefix;
+use crate::write::string::write_tstring;
+use crate::write::value::{write_value, write_value_ident};
+
+#[cfg(feature="compression")]
+use crate::write::compress::compress;
+#[cfg(feature="compression")]
+use crate::write::element::write_element;
+
+/// The size of the buffer filled before writing to the inner writer.
+const CHUNK_SIZE: usize = 8192;
+
+/// The sizes of containers and the contents of compressed elements, in the order they are written.
+#[derive(Default)]
+struct SizePlan {
+    sizes: Vec<usize>,
+    #[cfg(feature="compression")]
+    compressed: Vec<Vec<u8>>,
+}
+
+/// Find the size of an element, recording the sizes of the containers within it.
+fn plan_element(element: &Element, plan: &mut SizePlan) -> TychoResult<usize> {
+    Ok(match element {
+        Element::Unit => 1,
+        Element::Value(value) => 1 + value_ident_size(&value.ident()) + value_size(value),
+        Element::Option(None) => 1,
+        Element::Option(Some(x)) => 1 + plan_element(x, plan)?,
+        Element::Variant(name, x) => 1 + tstring_size(name) + plan_element(x, plan)?,
+        Element::Struct(data) => {
+            let slot = plan_slot(plan);
+            let mut size = 0;
+            for (key, value) in data {
+                size += tstring_size(key) + plan_element(value, plan)?;
+            }
+            plan.sizes[slot] = size;
+            1 + length_size(size) + size
+        }
+        Element::List(data) => {
+            let slot = plan_slot(plan);
+            let mut size = 0;
+            for item in data {
+                size += plan_element(item, plan)?;
+            }
+            plan.sizes[slot] = size;
+            1 + length_size(size) + size
+        }
+        Element::Array(array_type, data) => {
+            if array_type == &ValueIdent::Null || data.is_empty() {
+                2
+            } else {
+                let size = data.iter().map(value_size).sum();
+                plan.sizes.push(size);
+                1 + value_ident_size(array_type) + length_size(size) + size
+            }
+        }
+        Element::Map(key_type, data) => {
+            if key_type == &ValueIdent::Null || data.is_empty() {
+                2
+            } else {
+                let slot = plan_slot(plan);
+                let mut size = 0;
+                for (key, value) in data {
+                    size += value_size(key) + plan_element(value, plan)?;
+                }
+                plan.sizes[slot] = size;
+                1 + value_ident_size(key_type) + length_size(size) + size
+            }
+        }
+        #[cfg(feature="compression")]
+        Element::Compression(compression, x) => {
+            let mut buffer = Vec::new();
+            write_element(&mut buffer, x)?;
+            let bytes = compress(&buffer, compression)?;
+            let size = 1 + length_size(bytes.len()) + bytes.len();
+            plan.compressed.push(bytes);
+            size
+        }
+        #[cfg(not(feature="compression"))]
+        Element::Compression(_, x) => 1 + length_size(x.len()) + x.len(),
+    })
+}
+
+/// Reserve the size of a container, before its contents are planned.
+fn plan_slot(plan: &mut SizePlan) -> usize {
+    plan.sizes.push(0);
+    plan.sizes.len() - 1
+}
+
+/// Writes elements to an async writer in chunks, following a size plan.
+struct ChunkWriter<'a, W> {
+    writer: &'a mut W,
+    buffer: Vec<u8>,
+    sizes: std::vec::IntoIter<usize>,
+    #[cfg(feature="compression")]
+    compressed: std::vec::IntoIter<Vec<u8>>,
+}
+
+impl<'a, W: AsyncWrite + Unpin + Send> ChunkWriter<'a, W> {
+    /// Write the buffer to the inner writer once it is full.
+    async fn fill(&mut self) -> TychoStatus {
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> TychoStatus {
+        parse_io(self.writer.write_all(&self.buffer).await)?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn next_size(&mut self) -> usize {
+        self.sizes.next().unwrap_or_default()
+    }
+
+    fn element<'b>(&'b mut self, element: &'b Element) -> BoxFuture<'b, TychoStatus> where 'a: 'b {
+        async move {
+            match element {
+                Element::Unit => write_byte(&mut self.buffer, &0x00)?,
+                Element::Value(value) => {
+                    write_byte(&mut self.buffer, &0x01)?;
+                    write_value_ident(&mut self.buffer, &value.ident())?;
+                    write_value(&mut self.buffer, value)?;
+                }
+                Element::Option(None) => write_byte(&mut self.buffer, &0x02)?,
+                Element::Option(Some(x)) => {
+                    write_byte(&mut self.buffer, &0x03)?;
+                    self.element(x).await?;
+                }
+                Element::Variant(name, x) => {
+                    write_byte(&mut self.buffer, &0x04)?;
+                    write_tstring(&mut self.buffer, name)?;
+                    self.element(x).await?;
+                }
+                Element::Struct(data) => {
+                    write_byte(&mut self.buffer, &0x05)?;
+                    let size = self.next_size();
+                    write_length(&mut self.buffer, size)?;
+                    for (key, value) in data {
+                        write_tstring(&mut self.buffer, key)?;
+                        self.element(value).await?;
+                    }
+                }
+                Element::List(data) => {
+                    write_byte(&mut self.buffer, &0x06)?;
+                    let size = self.next_size();
+                    write_length(&mut self.buffer, size)?;
+                    for item in data {
+                        self.element(item).await?;
+                    }
+                }
+                Element::Array(array_type, data) => {
+                    write_byte(&mut self.buffer, &0x07)?;
+                    if array_type == &ValueIdent::Null || data.is_empty() {
+                        write_value_ident(&mut self.buffer, &ValueIdent::Null)?;
+                    } else {
+                        write_value_ident(&mut self.buffer, array_type)?;
+                        let size = self.next_size();
+                        write_length(&mut self.buffer, size)?;
+                        for item in data {
+                            write_value(&mut self.buffer, item)?;
+                            self.fill().await?;
+                        }
+                    }
+                }
+                Element::Map(key_type, data) => {
+                    write_byte(&mut self.buffer, &0x08)?;
+                    if key_type == &ValueIdent::Null || data.is_empty() {
+                        write_value_ident(&mut self.buffer, &ValueIdent::Null)?;
+                    } else {
+                        write_value_ident(&mut self.buffer, key_type)?;
+                        let size = self.next_size();
+                        write_length(&mut self.buffer, size)?;
+                        for (key, value) in data {
+                            write_value(&mut self.buffer, key)?;
+                            self.element(value).await?;
+                        }
+                    }
+                }
+                #[cfg(feature="compression")]
+                Element::Compression(compression, _) => {
+                    write_byte(&mut self.buffer, &codec_prefix(compression.codec()))?;
+                    let bytes = self.compressed.next().unwrap_or_default();
+                    write_length(&mut self.buffer, bytes.len())?;
+                    self.buffer.extend_from_slice(&bytes);
+                }
+                #[cfg(not(feature="compression"))]
+                Element::Compression(codec, x) => {
+                    write_byte(&mut self.buffer, &codec_prefix(*codec))?;
+                    write_length(&mut self.buffer, x.len())?;
+                    self.buffer.extend_from_slice(x);
+                }
+            }
+            self.fill().await
+        }.boxed()
+    }
+}
+
+/// Write an element to an async writer, producing the same bytes as `write_element`.
+///
+/// Container sizes are found before writing, so only a single chunk is held in memory.
+pub(crate) async fn write_element_async<W: AsyncWrite + Unpin + Send>(writer: &mut W, element: &Element) -> TychoStatus {
+    let mut plan = SizePlan::default();
+    plan_element(element, &mut plan)?;
+
+    let mut chunks = ChunkWriter {
+        writer,
+        buffer: Vec::with_capacity(CHUNK_SIZE),
+        sizes: plan.sizes.into_iter(),
+        #[cfg(feature="compression")]
+        compressed: plan.compressed.into_iter(),
+    };
+    chunks.element(element).await?;
+    chunks.flush().await
+}
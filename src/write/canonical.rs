@@ -0,0 +1,114 @@
+use std::io::Write;
+
+use crate::{Element, Number, Value};
+use crate::error::{TychoError, TychoStatus};
+use crate::into::ident::Ident;
+use crate::types::ident::ValueIdent;
+use crate::write::func::{write_byte, write_sized};
+use crate::write::string::write_tstring;
+use crate::write::value::{write_value, write_value_ident};
+
+/// Write an element in its canonical form.
+///
+/// Struct and map items are sorted by their encoded keys, float NaNs are normalised,
+/// and duplicate keys or compression markers are rejected.
+pub(crate) fn write_element_canonical<W: Write>(writer: &mut W, element: &Element) -> TychoStatus {
+    match element {
+        Element::Unit => write_byte(writer, &0x00),
+        Element::Value(value) => {
+            write_byte(writer, &0x01)?;
+            write_value_ident(writer, &value.ident())?;
+            write_value_canonical(writer, value)
+        },
+        Element::Option(opt) => match opt {
+            None => write_byte(writer, &0x02),
+            Some(x) => {
+                write_byte(writer, &0x03)?;
+                write_element_canonical(writer, x)
+            }
+        }
+        Element::Variant(name, element) => {
+            write_byte(writer, &0x04)?;
+            write_tstring(writer, name)?;
+            write_element_canonical(writer, element)
+        }
+        Element::Struct(data) => {
+            let mut items = Vec::with_capacity(data.len());
+            for (key, value) in data {
+                let mut key_bytes = Vec::new();
+                write_tstring(&mut key_bytes, key)?;
+                let mut value_bytes = Vec::new();
+                write_element_canonical(&mut value_bytes, value)?;
+                items.push((key_bytes, value_bytes));
+            }
+
+            write_byte(writer, &0x05)?;
+            write_sorted(writer, items)
+        }
+        Element::List(data) => {
+            let mut buffer = Vec::new();
+            for item in data {
+                write_element_canonical(&mut buffer, item)?;
+            }
+
+            write_byte(writer, &0x06)?;
+            write_sized(writer, &buffer)
+        }
+        Element::Array(array_type, data) => {
+            write_byte(writer, &0x07)?;
+            if array_type == &ValueIdent::Null || data.is_empty() {
+                write_value_ident(writer, &ValueIdent::Null)
+            } else {
+                let mut buffer = Vec::new();
+                for item in data {
+                    write_value_canonical(&mut buffer, item)?;
+                }
+                write_value_ident(writer, array_type)?;
+                write_sized(writer, &buffer)
+            }
+        }
+        Element::Map(key_type, data) => {
+            write_byte(writer, &0x08)?;
+            if key_type == &ValueIdent::Null || data.is_empty() {
+                write_value_ident(writer, &ValueIdent::Null)
+            } else {
+                let mut items = Vec::with_capacity(data.len());
+                for (key, value) in data {
+                    let mut key_bytes = Vec::new();
+                    write_value_canonical(&mut key_bytes, key)?;
+                    let mut value_bytes = Vec::new();
+                    write_element_canonical(&mut value_bytes, value)?;
+                    items.push((key_bytes, value_bytes));
+                }
+
+                write_value_ident(writer, key_type)?;
+                write_sorted(writer, items)
+            }
+        }
+        Element::Compression(..) => Err(TychoError::NonCanonical)
+    }
+}
+
+pub(crate) fn write_value_canonical<W: Write>(writer: &mut W, value: &Value) -> TychoStatus {
+    match value {
+        Value::Number(Number::Float32(x)) if x.is_nan() => write_value(writer, &Value::Number(Number::Float32(f32::NAN))),
+        Value::Number(Number::Float64(x)) if x.is_nan() => write_value(writer, &Value::Number(Number::Float64(f64::NAN))),
+        _ => write_value(writer, value)
+    }
+}
+
+/// Write encoded key-value pairs, sorted by their key bytes, with a size prefix.
+fn write_sorted<W: Write>(writer: &mut W, mut items: Vec<(Vec<u8>, Vec<u8>)>) -> TychoStatus {
+    items.sort();
+
+    if items.windows(2).any(|x| x[0].0 == x[1].0) {
+        return Err(TychoError::DuplicateKey);
+    }
+
+    let mut buffer = Vec::new();
+    for (key, value) in items {
+        buffer.extend_from_slice(&key);
+        buffer.extend_from_slice(&value);
+    }
+    write_sized(writer, &buffer)
+}
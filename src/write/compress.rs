@@ -1,13 +1,49 @@
+use std::io::Write;
+
 use flate2::write::GzEncoder;
-use flate2::Compression;
+
 use crate::error::{TychoResult, TychoError};
-use std::io::Write;
+use crate::into::compression::Compression;
+use crate::types::ident::Codec;
+
+#[cfg(feature="compression_brotli")]
+/// The size of the buffer used by the brotli encoder.
+const BROTLI_BUFFER: usize = 4096;
+
+#[cfg(feature="compression_brotli")]
+/// The base 2 logarithm of the brotli window size (`lgwin`), between 10 and 24.
+///
+/// 22 is a 4 MiB window, the largest in the brotli format specification and the encoder's default.
+/// A larger window finds matches further apart, but decoders must keep the whole window in memory.
+const BROTLI_WINDOW: u32 = 22;
 
-pub(crate) fn compress(bytes: Vec<u8>) -> TychoResult<Vec<u8>> {
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-    encoder.write_all(&bytes)?;
-    match encoder.finish() {
-        Ok(x) => Ok(x),
-        Err(e) => Err(TychoError::Io(e))
+pub(crate) fn compress(bytes: &[u8], compression: &Compression) -> TychoResult<Vec<u8>> {
+    match compression.codec() {
+        Codec::Gzip => {
+            let level = compression.level().map(|x| flate2::Compression::new(x.min(9))).unwrap_or_default();
+            let mut encoder = GzEncoder::new(Vec::new(), level);
+            encoder.write_all(bytes).map_err(TychoError::Compression)?;
+            encoder.finish().map_err(TychoError::Compression)
+        }
+        #[cfg(feature="compression_zstd")]
+        Codec::Zstd => {
+            let level = compression.level().map(|x| x.min(22) as i32).unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
+            zstd::bulk::compress(bytes, level).map_err(TychoError::Compression)
+        }
+        #[cfg(feature="compression_lz4")]
+        Codec::Lz4 => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+            encoder.write_all(bytes).map_err(TychoError::Compression)?;
+            encoder.finish().map_err(|e| TychoError::Compression(e.into()))
+        }
+        #[cfg(feature="compression_brotli")]
+        Codec::Brotli => {
+            let quality = compression.level().map(|x| x.min(11)).unwrap_or(11);
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), BROTLI_BUFFER, quality, BROTLI_WINDOW);
+            encoder.write_all(bytes).map_err(TychoError::Compression)?;
+            Ok(encoder.into_inner())
+        }
+        #[allow(unreachable_patterns)]
+        codec => Err(TychoError::UnsupportedCodec(codec))
     }
-}
\ No newline at end of file
+}
@@ -3,8 +3,8 @@ use std::io::{BufWriter, Write};
 use crate::Element;
 use crate::error::TychoStatus;
 use crate::into::ident::Ident;
-use crate::types::ident::ValueIdent;
-use crate::write::func::{write_buffer, write_byte, write_bytes};
+use crate::types::ident::{Codec, ValueIdent};
+use crate::write::func::{write_buffer, write_byte, write_sized};
 use crate::write::string::write_tstring;
 use crate::write::value::{write_value, write_value_ident};
 
@@ -76,16 +76,26 @@ pub(crate) fn write_element<W: Write>(writer: &mut W, element: &Element) -> Tych
            }
         }
         #[cfg(feature="compression")]
-        Element::Compression(compression) => {
-            write_byte(writer, &0xF0)?;
-            let mut buffer = BufWriter::new(Vec::new());
-            write_element(&mut buffer, compression)?;
-            write_bytes(writer, &*compress(buffer.buffer().to_vec())?.to_vec())
+        Element::Compression(compression, element) => {
+            write_byte(writer, &codec_prefix(compression.codec()))?;
+            let mut buffer = Vec::new();
+            write_element(&mut buffer, element)?;
+            write_sized(writer, &compress(&buffer, compression)?)
         }
         #[cfg(not(feature="compression"))]
-        Element::Compression(compression) => {
-            write_byte(writer, &0xF0)?;
-            write_bytes(writer, compression)
+        Element::Compression(codec, bytes) => {
+            write_byte(writer, &codec_prefix(*codec))?;
+            write_sized(writer, bytes)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Get the prefix of a compression element with a codec.
+pub(crate) fn codec_prefix(codec: Codec) -> u8 {
+    match codec {
+        Codec::Gzip => 0xF0,
+        Codec::Zstd => 0xF1,
+        Codec::Lz4 => 0xF2,
+        Codec::Brotli => 0xF3,
+    }
+}
@@ -23,4 +23,8 @@ pub(crate) fn write_buffer<W: Write>(writer: &mut W, buffer: BufWriter<Vec<u8>>)
     let bytes = buffer.into_inner().unwrap(); // issue here?
     write_length(writer, bytes.len())?;
     write_bytes(writer, &bytes)
-}
\ No newline at end of file
+}
+pub(crate) fn write_sized<W: Write>(writer: &mut W, bytes: &[u8]) -> TychoStatus {
+    write_length(writer, bytes.len())?;
+    write_bytes(writer, bytes)
+}
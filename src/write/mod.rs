@@ -4,6 +4,12 @@ pub(crate) mod string;
 pub(crate) mod number;
 pub(crate) mod value;
 pub(crate) mod element;
+pub(crate) mod canonical;
+
+#[cfg(any(feature="async_tokio", feature="serde"))]
+pub(crate) mod size;
+#[cfg(feature="async_tokio")]
+pub(crate) mod async_;
 
 #[cfg(feature="compression")]
 pub(crate) mod compress;
\ No newline at end of file
@@ -0,0 +1,53 @@
+//! The sizes of encoded values, matching the bytes written by `write::value`.
+
+use crate::{Number, Value};
+use crate::types::ident::ValueIdent;
+
+/// The size of a length prefix.
+pub(crate) fn length_size(mut length: usize) -> usize {
+    let mut size = 1;
+    while length > 0x7F {
+        length >>= 7;
+        size += 1;
+    }
+    size
+}
+
+/// The size of a value ident.
+pub(crate) fn value_ident_size(ident: &ValueIdent) -> usize {
+    match ident {
+        ValueIdent::Number(_) => 2,
+        _ => 1
+    }
+}
+
+#[cfg(feature="async_tokio")]
+/// The size of a terminated string.
+pub(crate) fn tstring_size(s: &str) -> usize {
+    s.len() + 1
+}
+
+#[cfg(feature="async_tokio")]
+/// The size of a value, without its ident.
+pub(crate) fn value_size(value: &Value) -> usize {
+    match value {
+        Value::Null => 0,
+        Value::Boolean(_) => 1,
+        Value::String(v) => length_size(v.len()) + v.len(),
+        Value::Char(v) => v.len_utf8(),
+        Value::Number(v) => number_size(v),
+        Value::Bytes(v) => length_size(v.len()) + v.len(),
+        Value::UUID(_) => 16,
+    }
+}
+
+#[cfg(feature="async_tokio")]
+fn number_size(number: &Number) -> usize {
+    match number {
+        Number::Bit(_) | Number::Unsigned8(_) | Number::Signed8(_) => 1,
+        Number::Unsigned16(_) | Number::Signed16(_) => 2,
+        Number::Unsigned32(_) | Number::Signed32(_) | Number::Float32(_) => 4,
+        Number::Unsigned64(_) | Number::Signed64(_) | Number::Float64(_) => 8,
+        Number::Unsigned128(_) | Number::Signed128(_) => 16,
+    }
+}